@@ -0,0 +1,9 @@
+var {parseAccolades} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let accolades = parseAccolades(filePath)
+
+accolades
+    .filter(a => a.position === 1)
+    .forEach(a => console.log(a.name, a.accolade, a.value))
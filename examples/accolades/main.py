@@ -0,0 +1,8 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+df = parser.parse_accolades()
+
+# Awards shown on the end of match screen, best first
+print(df.sort_values("position")[["name", "accolade", "value", "position"]])
@@ -0,0 +1,13 @@
+var {parseAim} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let evidence = parseAim(filePath)
+
+// Flicks onto enemies that were not even spotted are the most interesting
+evidence
+    .filter(e => e.kind == "flick_to_head" && e.target_spotted === false)
+    .forEach(e => console.log(e.name, "->", e.target_name, "ticks", e.review_start_tick, "-", e.review_end_tick))
+
+// Top 10 moments to review
+console.log(evidence.slice(0, 10))
@@ -0,0 +1,12 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+
+evidence = parser.parse_aim()
+# Most unusual moments first, watch these ticks back in the demo
+print(evidence.head(20)[["kind", "name", "target_name", "round", "review_start_tick", "review_end_tick", "score"]])
+
+# Who tracks unspotted enemies the most
+tracking = evidence[evidence["kind"] == "occluded_tracking"]
+print(tracking.groupby("name")["duration"].agg(["count", "sum"]).sort_values("sum", ascending=False))
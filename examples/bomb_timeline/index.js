@@ -0,0 +1,11 @@
+var {parseBomb} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let rounds = parseBomb(filePath)
+
+rounds.forEach(round => {
+    let drops = round.events.filter(e => e.event_type == "drop").map(e => e.name)
+    let outcome = round.defused_tick != null ? "defused" : round.exploded_tick != null ? "exploded" : "not decided by the bomb"
+    console.log(`round ${round.round}: planted ${round.site ?? "-"} by ${round.planter_name ?? "-"}, ${outcome}, dropped by [${drops}]`)
+})
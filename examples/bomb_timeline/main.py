@@ -0,0 +1,16 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+
+timeline = parser.parse_bomb()
+rounds = parser.parse_bomb(per_round=True)
+
+print(rounds[["round", "planter_name", "site", "defuser_name", "exploded_tick"]])
+
+# Defuses that were stopped and how close they got
+aborted = timeline[timeline["event_type"] == "defuse_abort"]
+print(aborted[["round", "name", "defuse_progress"]])
+
+# Plants per site
+print(rounds["site"].value_counts())
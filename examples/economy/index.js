@@ -0,0 +1,16 @@
+var {parseEconomy} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let economy = parseEconomy(filePath)
+
+// Buy type of both teams every round
+economy.teams.forEach(t => console.log(t.round, t.team_num == 2 ? "T" : "CT", t.buy_type, t.equipment_value))
+
+// Players that saved the most money during full buys of their team
+let fullBuys = economy.teams.filter(t => t.buy_type == "full_buy")
+economy.players
+    .filter(p => fullBuys.some(t => t.round == p.round && t.team_num == p.team))
+    .sort((a, b) => b.balance - a.balance)
+    .slice(0, 5)
+    .forEach(p => console.log(p.name, p.round, p.balance))
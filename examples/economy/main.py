@@ -0,0 +1,17 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+
+# Buy type of both teams every round
+df = parser.parse_economy()
+print(df[["round", "team_num", "start_money", "spent", "equipment_value", "loss_bonus_level", "buy_type"]])
+
+# Win rate of force buys against full buys, with stricter thresholds
+df = parser.parse_economy(eco=1500, semi_eco=2500, force=4500)
+wins = parser.parse_event("round_end")[["round", "winner"]]
+wins["winner"] = wins["winner"].map({"T": 2, "CT": 3})
+rounds = df.merge(df, on="round", suffixes=("", "_enemy"))
+rounds = rounds[rounds["team_num"] != rounds["team_num_enemy"]].merge(wins, on="round")
+forces = rounds[(rounds["buy_type"] == "force") & (rounds["buy_type_enemy"] == "full_buy")]
+print((forces["winner"] == forces["team_num"]).mean())
@@ -0,0 +1,8 @@
+var {parseFileInfo} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+// Reads only the end of the demo
+let info = parseFileInfo(filePath)
+console.log((info.playback_time / 60).toFixed(1), "minutes,", info.playback_ticks, "ticks")
+console.log("rounds start at", info.round_start_ticks)
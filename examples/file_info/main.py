@@ -0,0 +1,9 @@
+from demoparser2 import DemoParser
+import glob
+
+
+# Reads only the end of each demo, so this is fast even for a folder of HLTV demos
+for path in glob.glob("path/to/demos/*.dem"):
+    info = DemoParser(path).parse_file_info()
+    minutes = info["playback_time"] / 60
+    print(f"{path}: {minutes:.1f} min, {info['playback_ticks']} ticks, {len(info['round_start_ticks'])} rounds")
@@ -0,0 +1,15 @@
+var {parseFires, parseSmokes} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let fires = parseFires(filePath)
+let smokes = parseSmokes(filePath)
+
+// How long did each molotov burn and how big did it get
+fires.forEach(fire => {
+    let maxPoints = Math.max(...fire.timeline.map(t => t.points.length))
+    let seconds = (fire.end_tick - fire.start_tick) / 64
+    console.log(`${fire.thrower_name} round ${fire.round}: ${seconds.toFixed(1)}s, ${maxPoints} flames`)
+})
+
+console.log(smokes.filter(s => s.duration != null && s.duration < 17))
@@ -0,0 +1,19 @@
+from demoparser2 import DemoParser
+import math
+
+
+parser = DemoParser("path_to_demo.dem")
+
+fires = parser.parse_fires()
+smokes = parser.parse_smokes()
+ticks = parser.parse_ticks(["X", "Y", "Z", "health"], ticks=fires["tick"].unique().tolist())
+
+# Players standing within 60 units of a burning point
+fires_per_tick = fires.groupby("tick")["points"].sum()
+for _, row in ticks.iterrows():
+    for x, y, z in fires_per_tick.get(row["tick"], []):
+        if math.dist((x, y), (row["X"], row["Y"])) < 60 and abs(row["Z"] - z) < 80:
+            print(f"{row['name']} in fire at tick {row['tick']}")
+            break
+
+print(smokes[["thrower_name", "round", "X", "Y", "duration"]])
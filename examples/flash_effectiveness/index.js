@@ -0,0 +1,14 @@
+var {parseFlashes} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let flashes = parseFlashes(filePath)
+
+// Flashes that blinded more teammates than enemies
+let teamFlashes = flashes.filter(f => f.teammates_blinded > f.enemies_blinded)
+console.log(teamFlashes)
+
+// Best flashers by enemy blind time
+let perPlayer = parseFlashes(filePath, 3.0, true)
+perPlayer.sort((a, b) => b.enemies_blind_duration - a.enemies_blind_duration)
+console.log(perPlayer)
@@ -0,0 +1,14 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+
+df = parser.parse_flashes()
+
+# Flashes that blinded more teammates than enemies
+team_flashes = df[df["teammates_blinded"] > df["enemies_blinded"]]
+print(team_flashes[["thrower_name", "round", "tick", "enemies_blinded", "teammates_blinded"]])
+
+# Best flashers by enemy blind time
+per_player = parser.parse_flashes(per_player=True)
+print(per_player.sort_values(by="enemies_blind_duration", ascending=False))
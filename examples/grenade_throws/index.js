@@ -0,0 +1,11 @@
+var {parseGrenadeThrows} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let throws = parseGrenadeThrows(filePath)
+
+// Time from throw to detonation for every HE grenade
+throws.filter(t => t.grenade_type == "he_grenade" && t.detonate_tick != null).forEach(t => {
+    let seconds = (t.detonate_tick - t.throw_tick) / 64
+    console.log(`${t.thrower_name} round ${t.round}: ${seconds.toFixed(2)}s, ${t.bounces} bounces`)
+})
@@ -0,0 +1,13 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+df = parser.parse_grenade_throws()
+
+# Where did each smoke land and how long was it in the air
+smokes = df[df["grenade_type"] == "smoke"].copy()
+smokes["air_time"] = (smokes["detonate_tick"] - smokes["throw_tick"]) / 64
+print(smokes[["thrower_name", "round", "detonate_X", "detonate_Y", "air_time", "bounces"]])
+
+# Full path of the first smoke
+print(smokes.iloc[0]["path"])
@@ -0,0 +1,14 @@
+var {parseHighlights} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+// Start clips 5 seconds before the action and end them 5 seconds after
+let highlights = parseHighlights(filePath, 5.0)
+
+let clutches = highlights.filter(h => h.highlight_type == "clutch_won")
+console.log(clutches)
+
+// Commands to jump to each ace when watching the demo in game
+highlights.filter(h => h.highlight_type == "ace").forEach(ace => {
+    console.log(`${ace.name} round ${ace.round}: demo_gototick ${ace.start_tick}`)
+})
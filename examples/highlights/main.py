@@ -0,0 +1,14 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+
+# Start clips 5 seconds before the action and end them 5 seconds after
+df = parser.parse_highlights(padding=5.0)
+
+clutches = df[df["highlight_type"] == "clutch_won"]
+print(clutches[["name", "round", "opponents", "start_tick", "end_tick"]])
+
+# Commands to jump to each ace when watching the demo in game
+for _, ace in df[df["highlight_type"] == "ace"].iterrows():
+    print(f"{ace['name']} round {ace['round']}: demo_gototick {ace['start_tick']}")
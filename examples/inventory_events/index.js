@@ -0,0 +1,15 @@
+var {parseEvents} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let events = parseEvents(filePath, ["item_dropped", "item_picked_up"])
+
+// Weapons dropped for a teammate
+let dropped = events.filter(e => e.event_name == "item_dropped")
+let pickedUp = events.filter(e => e.event_name == "item_picked_up")
+dropped.forEach(drop => {
+    let pickup = pickedUp.find(p => p.item_id == drop.item_id && p.tick >= drop.tick)
+    if (pickup) {
+        console.log(drop.user_name, "dropped", drop.item, "for", pickup.user_name)
+    }
+})
@@ -0,0 +1,16 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path/to/demo.dem")
+events = dict(parser.parse_events(["item_bought", "item_picked_up", "item_dropped"], other=["total_rounds_played"]))
+
+# Money spent per player per round
+bought = events["item_bought"]
+print(bought.groupby(["total_rounds_played", "user_name"])["price"].sum())
+
+# Every AWP bought and who ended up with it
+awps = bought[bought["item"] == "AWP"]
+pickups = events["item_picked_up"]
+for _, awp in awps.iterrows():
+    holders = pickups[pickups["item_id"] == awp["item_id"]]["user_name"].tolist()
+    print(awp["user_name"], "->", holders)
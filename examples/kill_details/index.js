@@ -0,0 +1,12 @@
+var {parseKills} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let kills = parseKills(filePath)
+
+// Kills while in the air and kills on moving victims
+let jumpKills = kills.filter(k => k.attacker_airborne)
+let runningVictims = kills.filter(k => k.user_velocity > 200)
+
+console.log(`jump kills: ${jumpKills.length}, kills on running players: ${runningVictims.length}`)
+jumpKills.forEach(k => console.log(`${k.attacker_name} -> ${k.user_name} (${k.weapon}, ${k.distance.toFixed(0)} units)`))
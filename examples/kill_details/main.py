@@ -0,0 +1,15 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+kills = parser.parse_kills()
+
+# Flicks: crosshair far off the victim just before the kill but on target at the kill
+flicks = kills[(kills["view_angle_offset_before"] > 20) & (kills["view_angle_offset"] < 3)]
+print(flicks[["attacker_name", "user_name", "weapon", "distance", "view_angle_offset_before"]])
+
+# Kills on victims that never saw the attacker
+print(kills[kills["victim_spotted_attacker"] == False].groupby("attacker_name").size())
+
+# Average kill distance per weapon
+print(kills.groupby("weapon")["distance"].mean().sort_values())
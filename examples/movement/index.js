@@ -0,0 +1,19 @@
+var {parseMovement} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let movement = parseMovement(filePath)
+
+// Share of accurate shots per player
+let accuracy = {}
+movement.players.forEach(p => {
+    let a = accuracy[p.name] || {gun_shots: 0, accurate_shots: 0}
+    a.gun_shots += p.gun_shots
+    a.accurate_shots += p.accurate_shots
+    accuracy[p.name] = a
+})
+Object.entries(accuracy).forEach(([name, a]) => console.log(name, (a.accurate_shots / a.gun_shots).toFixed(2)))
+
+// Shots fired while running
+let runningShots = movement.shots.filter(s => s.accurate === false && !s.airborne)
+console.log(runningShots.length)
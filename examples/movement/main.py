@@ -0,0 +1,16 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+movement = parser.parse_movement()
+
+# Share of shots fired slow enough to be accurate, low values mean poor counter-strafing
+per_player = movement.groupby("name")[["gun_shots", "accurate_shots"]].sum()
+print((per_player["accurate_shots"] / per_player["gun_shots"]).sort_values())
+
+# Distance covered over the match
+print(movement.groupby("name")["distance"].sum().sort_values(ascending=False))
+
+# Average speed of the shots that were not accurate, per weapon
+shots = parser.parse_movement(per_shot=True)
+print(shots[shots["accurate"] == False].groupby("weapon")["speed"].mean())
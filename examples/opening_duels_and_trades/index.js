@@ -0,0 +1,12 @@
+var {parseDuels} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+// player_death events with is_opening_kill, is_trade_kill and was_traded fields.
+// A kill counts as a trade if it happens within 3 seconds of the teammate's death.
+let kills = parseDuels(filePath, 3.0)
+console.log(kills.filter(kill => kill.is_opening_kill))
+
+// Opening duel win rate and trade rates per player
+let perPlayer = parseDuels(filePath, 3.0, true)
+console.log(perPlayer)
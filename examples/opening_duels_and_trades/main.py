@@ -0,0 +1,13 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+
+# player_death events with is_opening_kill, is_trade_kill and was_traded columns.
+# A kill counts as a trade if it happens within 3 seconds of the teammate's death.
+kills = parser.parse_duels(trade_window=3.0)
+print(kills[kills["is_opening_kill"]][["round", "attacker_name", "user_name", "weapon"]])
+
+# Opening duel win rate and trade rates per player
+per_player = parser.parse_duels(trade_window=3.0, per_player=True)
+print(per_player.sort_values("opening_win_percentage", ascending=False))
@@ -0,0 +1,12 @@
+var {parsePlayerStats} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+// Kills, deaths, assists, ADR, HS% and KAST over the whole match
+let stats = parsePlayerStats(filePath)
+stats.sort((a, b) => b.adr - a.adr)
+console.log(stats)
+
+// Same stats but one row per player per round
+let perRound = parsePlayerStats(filePath, true)
+console.log(perRound.filter(row => row.kills >= 3))
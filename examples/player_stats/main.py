@@ -0,0 +1,12 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+
+# Kills, deaths, assists, ADR, HS% and KAST over the whole match
+df = parser.parse_player_stats()
+print(df.sort_values("adr", ascending=False))
+
+# Same stats but one row per player per round
+per_round = parser.parse_player_stats(per_round=True)
+print(per_round[per_round["kills"] >= 3])
@@ -0,0 +1,11 @@
+var {parseTicks, parseEvent} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+// Only needed for workshop maps
+const overviewPath = null
+
+let ticks = parseTicks(filePath, ["radar_x", "radar_y", "radar_level"], null, null, false, overviewPath)
+console.log(ticks.slice(0, 10))
+
+let kills = parseEvent(filePath, "player_death", ["radar_x", "radar_y"], [], overviewPath)
+kills.forEach(k => console.log(`${k.user_name} died at (${k.user_radar_x.toFixed(0)}, ${k.user_radar_y.toFixed(0)})`))
@@ -0,0 +1,18 @@
+from demoparser2 import DemoParser
+
+
+# Official maps are built-in, for workshop maps pass the overview file:
+# parser = DemoParser("path_to_demo.dem", overview="path/to/de_mymap.txt")
+parser = DemoParser("path_to_demo.dem")
+
+# Player positions on the radar image (1024x1024 pixels)
+df = parser.parse_ticks(["radar_x", "radar_y", "radar_level"])
+print(df)
+
+# Where kills happened on the radar, split by level on Nuke and Vertigo
+kills = parser.parse_event("player_death", player=["radar_x", "radar_y", "radar_level"])
+print(kills[["attacker_name", "user_name", "user_radar_x", "user_radar_y", "user_radar_level"]])
+
+# Grenade paths on the radar
+grenades = parser.parse_grenades(radar=True)
+print(grenades[["grenade_type", "tick", "radar_x", "radar_y"]])
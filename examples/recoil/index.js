@@ -0,0 +1,16 @@
+var {parseRecoil} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let recoil = parseRecoil(filePath)
+
+// Spray control scores with rifles
+recoil.players
+    .filter(p => ["ak47", "m4a1", "m4a1_silencer"].includes(p.weapon) && p.bullets >= 50)
+    .sort((a, b) => a.avg_compensation_error - b.avg_compensation_error)
+    .forEach(p => console.log(p.name, p.weapon, p.avg_compensation_error.toFixed(2)))
+
+// Aim points of the longest spray, for plotting
+let longest = recoil.bullets.reduce((a, b) => b.spray_index > a.spray_index ? b : a)
+let spray = recoil.bullets.filter(b => b.steamid == longest.steamid && b.spray_start_tick == longest.spray_start_tick)
+console.log(spray.map(b => [b.aim_yaw, b.aim_pitch]))
@@ -0,0 +1,14 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+
+# Spray control per player with the AK, lower error is better
+players = parser.parse_recoil(per_player=True)
+ak = players[(players["weapon"] == "ak47") & (players["bullets"] >= 50)]
+print(ak.sort_values("avg_compensation_error")[["name", "sprays", "avg_compensation_error", "spray_accuracy"]])
+
+# How the error grows over the spray
+bullets = parser.parse_recoil()
+ak_bullets = bullets[bullets["weapon"] == "ak47"]
+print(ak_bullets.groupby("spray_index")["compensation_error"].mean().head(30))
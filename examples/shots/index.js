@@ -0,0 +1,15 @@
+var {parseShots} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let shots = parseShots(filePath)
+
+// Accuracy of the first bullet of every spray
+let firstBullets = shots.shots.filter(s => s.spray_index == 1)
+console.log(firstBullets.filter(s => s.hit).length / firstBullets.length)
+
+// Best headshot per hit rates with at least 20 hits
+shots.accuracy
+    .filter(a => a.hits >= 20)
+    .sort((a, b) => b.headshots_per_hit - a.headshots_per_hit)
+    .forEach(a => console.log(a.name, a.weapon, a.headshots_per_hit.toFixed(2)))
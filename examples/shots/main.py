@@ -0,0 +1,15 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+shots = parser.parse_shots()
+
+# How accuracy drops the longer the spray goes
+print(shots.groupby("spray_index")["hit"].mean().head(15))
+
+# Where the hits land
+print(shots[shots["hit"]].groupby("hitgroup").size())
+
+# Accuracy and headshots per hit with rifles
+accuracy = parser.parse_shots(per_weapon=True)
+print(accuracy[accuracy["weapon"].isin(["ak47", "m4a1", "m4a1_silencer"])])
@@ -0,0 +1,13 @@
+var {parseStatsUpdates} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let {player_stats} = parseStatsUpdates(filePath)
+
+// Last value of every stat per player
+let totals = {}
+for (let update of player_stats.filter(u => u.stat !== null)) {
+    totals[update.name] = totals[update.name] || {}
+    totals[update.name][update.stat] = update.value
+}
+console.log(totals)
@@ -0,0 +1,13 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+df = parser.parse_stats_updates()
+
+# Final value of every general stat per player, as counted by the server
+final = df[df["stat"].notna()].groupby(["steamid", "name", "stat"])["value"].last().unstack()
+print(final)
+
+# Compare the server's kill count with the one from the events
+deaths = parser.parse_event("player_death")
+print(deaths.groupby("attacker_steamid").size())
@@ -0,0 +1,11 @@
+var {parseUserCmds} = require('@laihoe/demoparser2');
+
+// Only POV demos (recorded by the player) have user commands
+const filePath = "path/to/pov_demo.dem"
+
+let cmds = parseUserCmds(filePath)
+
+// Ticks where the player was strafing left and right within the same tick
+cmds
+    .filter(c => c.subtick_moves.some(m => m.button_name === "LEFT") && c.subtick_moves.some(m => m.button_name === "RIGHT"))
+    .forEach(c => console.log(c.tick, c.subtick_moves))
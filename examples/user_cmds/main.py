@@ -0,0 +1,12 @@
+from demoparser2 import DemoParser
+
+
+# Only POV demos (recorded by the player) have user commands
+parser = DemoParser("path_to_pov_demo.dem")
+df = parser.parse_user_cmds()
+print(df[["tick", "pitch", "yaw", "forward_move", "side_move", "button_names"]])
+
+# When inside the tick each shot was pressed
+moves = parser.parse_user_cmds(subtick=True)
+shots = moves[(moves["button_name"] == "FIRE") & (moves["pressed"] == True)]
+print(shots[["tick", "when"]])
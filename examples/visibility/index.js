@@ -0,0 +1,14 @@
+var {parseVisibility} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let visibility = parseVisibility(filePath)
+
+// Kills where the victim saw the attacker first and still died
+let lostDuels = visibility.kills.filter(k => k.spotted_first == "victim")
+lostDuels.forEach(k => console.log(`${k.victim_name} saw ${k.attacker_name} first and died (round ${k.round})`))
+
+// Seconds seen by enemies per player
+let seen = {}
+visibility.intervals.forEach(i => seen[i.target_name] = (seen[i.target_name] || 0) + i.duration)
+console.log(seen)
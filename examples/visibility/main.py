@@ -0,0 +1,15 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+kills = parser.parse_visibility(per_kill=True)
+
+# Who wins the fights where they were seen first
+print(kills.groupby("spotted_first").size())
+
+# Average time from spotting an enemy to killing them
+print(kills.groupby("attacker_name")["time_to_kill"].mean().sort_values())
+
+# Total seconds each player was seen by enemies
+intervals = parser.parse_visibility()
+print(intervals.groupby("target_name")["duration"].sum().sort_values(ascending=False))
@@ -0,0 +1,14 @@
+var {parseVoiceActivity} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let segments = parseVoiceActivity(filePath)
+
+// Seconds of talking per player per round
+let talkTime = {}
+for (let segment of segments) {
+    let key = segment.name || segment.steamid || "client_" + segment.client
+    talkTime[key] = talkTime[key] || {}
+    talkTime[key][segment.round] = (talkTime[key][segment.round] || 0) + segment.duration
+}
+console.log(talkTime)
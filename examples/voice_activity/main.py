@@ -0,0 +1,17 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+df = parser.parse_voice_activity()
+
+# Seconds of talking per player
+print(df.groupby(["steamid", "name"])["duration"].sum().sort_values(ascending=False))
+
+# Who talks during freezetime, usually the in-game leader calling the round
+freeze_ends = parser.parse_event("round_freeze_end", other=["total_rounds_played", "is_warmup_period"])
+freeze_ends = freeze_ends[freeze_ends["is_warmup_period"] == False]
+freeze_ends["round"] = freeze_ends["total_rounds_played"] + 1
+freeze_ends = freeze_ends[["round", "tick"]].rename(columns={"tick": "freeze_end_tick"})
+df = df.merge(freeze_ends, on="round")
+freezetime = df[df["start_tick"] < df["freeze_end_tick"]]
+print(freezetime.groupby("name")["duration"].sum().sort_values(ascending=False))
@@ -0,0 +1,12 @@
+var {parseWeaponSkins} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let weapons = parseWeaponSkins(filePath)
+
+// Every skinned weapon once, with its float and stickers
+let seen = new Set()
+weapons
+    .filter(w => w.paint_index !== null && w.item_id !== null)
+    .filter(w => !seen.has(w.item_id) && seen.add(w.item_id))
+    .forEach(w => console.log(w.skin_name, w.paint_wear, w.paint_seed, w.stickers.map(s => s.id)))
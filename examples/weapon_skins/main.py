@@ -0,0 +1,13 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+df = parser.parse_weapon_skins()
+
+# One row per skin: the same weapon shows up once per owner
+skins = df[df["paint_index"].notna()].drop_duplicates("item_id")
+print(skins[["weapon", "skin_name", "paint_wear", "paint_seed", "stattrak", "original_owner_steamid"]])
+
+# Weapons with stickers
+stickers = skins[skins["sticker_0_id"].notna()]
+print(stickers[["weapon", "skin_name", "sticker_0_id", "sticker_1_id", "sticker_2_id", "sticker_3_id"]])
@@ -0,0 +1,14 @@
+var {parseWeapons} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+let weapons = parseWeapons(filePath)
+
+// Most deadly weapon of every player
+let best = {}
+weapons.forEach(w => {
+    if (best[w.steamid] === undefined || w.kills > best[w.steamid].kills) {
+        best[w.steamid] = w
+    }
+})
+Object.values(best).forEach(w => console.log(w.name, w.weapon, w.kills))
@@ -0,0 +1,13 @@
+from demoparser2 import DemoParser
+
+
+parser = DemoParser("path_to_demo.dem")
+df = parser.parse_weapons()
+
+# Best AWPers of the match
+awp = df[df["weapon"] == "awp"].sort_values("kills", ascending=False)
+print(awp[["name", "kills", "deaths", "damage", "accuracy"]])
+
+# Utility damage per player
+nades = df[df["weapon"].isin(["hegrenade", "molotov"])]
+print(nades.groupby("name")["damage"].sum().sort_values(ascending=False))
@@ -0,0 +1,19 @@
+var {parseEvent} = require('@laihoe/demoparser2');
+
+const filePath = "path/to/demo.dem"
+
+// Kills and deaths per zone from zones.json
+let deaths = parseEvent(filePath, "player_death", ["zone"], [], null, "zones.json")
+
+let perZone = {}
+deaths.forEach(d => {
+    if (d.attacker_zone) {
+        perZone[d.attacker_zone] = perZone[d.attacker_zone] || {kills: 0, deaths: 0}
+        perZone[d.attacker_zone].kills += 1
+    }
+    if (d.user_zone) {
+        perZone[d.user_zone] = perZone[d.user_zone] || {kills: 0, deaths: 0}
+        perZone[d.user_zone].deaths += 1
+    }
+})
+console.log(perZone)
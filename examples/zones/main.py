@@ -0,0 +1,18 @@
+from demoparser2 import DemoParser
+
+MY_STEAMID = 765611111111111
+
+# Same as examples/kda_per_zone but with your own zones instead of last_place_name
+parser = DemoParser("path_to_demo.dem", zones="zones.json")
+df = parser.parse_event("player_death", player=["zone"])
+
+df["attacker_steamid"] = df["attacker_steamid"].astype(int)
+df["user_steamid"] = df["user_steamid"].astype(int)
+
+kills = df[df["attacker_steamid"] == MY_STEAMID].groupby("attacker_zone").size()
+deaths = df[df["user_steamid"] == MY_STEAMID].groupby("user_zone").size()
+print(kills.to_frame("kills").join(deaths.to_frame("deaths"), how="outer").fillna(0))
+
+# Where the smokes landed
+throws = parser.parse_grenade_throws()
+print(throws[throws["grenade_type"] == "smoke"].groupby("detonate_zone").size())
@@ -5,9 +5,32 @@
 
 export function parseChatMessages(path: string): any
 export function listGameEvents(path: string): any
-export function parseGrenades(path: string): any
+export function parseGrenades(path: string, overviewPath?: string | undefined | null, zonesPath?: string | undefined | null): any
 export function parseHeader(path: string): any
-export function parseEvent(path: string, eventName: string, playerExtra?: Array<string> | undefined | null, otherExtra?: Array<string> | undefined | null): any
-export function parseEvents(path: string, eventNames?: Array<string> | undefined | null, playerExtra?: Array<string> | undefined | null, otherExtra?: Array<string> | undefined | null): any
-export function parseTicks(path: string, wantedProps: Array<string>, wantedTicks?: Array<number> | undefined | null, structOfArrays?: boolean | undefined | null): any
+export function parseEvent(path: string, eventName: string, playerExtra?: Array<string> | undefined | null, otherExtra?: Array<string> | undefined | null, overviewPath?: string | undefined | null, zonesPath?: string | undefined | null): any
+export function parseEvents(path: string, eventNames?: Array<string> | undefined | null, playerExtra?: Array<string> | undefined | null, otherExtra?: Array<string> | undefined | null, overviewPath?: string | undefined | null, zonesPath?: string | undefined | null): any
+export function parseTicks(path: string, wantedProps: Array<string>, wantedTicks?: Array<number> | undefined | null, wantedPlayers?: Array<string> | undefined | null, structOfArrays?: boolean | undefined | null, overviewPath?: string | undefined | null, zonesPath?: string | undefined | null): any
+export function parseFileInfo(path: string): any
 export function parsePlayerInfo(path: string): any
+export function parseAccolades(path: string): any
+export function parsePlayerStats(path: string, perRound?: boolean | undefined | null): any
+export function parseDuels(path: string, tradeWindow?: number | undefined | null, perPlayer?: boolean | undefined | null): any
+export function parseHighlights(path: string, padding?: number | undefined | null): any
+export function parseFlashes(path: string, flashAssistWindow?: number | undefined | null, perPlayer?: boolean | undefined | null): any
+export function parseGrenadeThrows(path: string, zonesPath?: string | undefined | null): any
+export function parseFires(path: string): any
+export function parseSmokes(path: string): any
+export function parseBomb(path: string): any
+export function parseKills(path: string): any
+export function parseVisibility(path: string): any
+export function parseMovement(path: string): any
+export function parseShots(path: string): any
+export function parseRecoil(path: string): any
+export function parseAim(path: string): any
+export function parseEconomy(path: string, eco?: number | undefined | null, semiEco?: number | undefined | null, force?: number | undefined | null): any
+export function parseWeapons(path: string): any
+export function parseWeaponSkins(path: string): any
+export function parseStatsUpdates(path: string): any
+export function parseUserCmds(path: string): any
+export function parseVoice(path: string): Record<string, Buffer>
+export function parseVoiceActivity(path: string, gapSeconds?: number | undefined | null): any
@@ -252,7 +252,7 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { parseChatMessages, listGameEvents, parseGrenades, parseHeader, parseEvent, parseEvents, parseTicks, parsePlayerInfo } = nativeBinding
+const { parseChatMessages, listGameEvents, parseGrenades, parseHeader, parseEvent, parseEvents, parseTicks, parseFileInfo, parsePlayerInfo, parseAccolades, parsePlayerStats, parseDuels, parseHighlights, parseFlashes, parseGrenadeThrows, parseFires, parseSmokes, parseBomb, parseKills, parseVisibility, parseMovement, parseShots, parseRecoil, parseAim, parseEconomy, parseWeapons, parseWeaponSkins, parseStatsUpdates, parseUserCmds, parseVoice, parseVoiceActivity } = nativeBinding
 
 module.exports.parseChatMessages = parseChatMessages
 module.exports.listGameEvents = listGameEvents
@@ -261,4 +261,27 @@ module.exports.parseHeader = parseHeader
 module.exports.parseEvent = parseEvent
 module.exports.parseEvents = parseEvents
 module.exports.parseTicks = parseTicks
+module.exports.parseFileInfo = parseFileInfo
 module.exports.parsePlayerInfo = parsePlayerInfo
+module.exports.parseAccolades = parseAccolades
+module.exports.parsePlayerStats = parsePlayerStats
+module.exports.parseDuels = parseDuels
+module.exports.parseHighlights = parseHighlights
+module.exports.parseFlashes = parseFlashes
+module.exports.parseGrenadeThrows = parseGrenadeThrows
+module.exports.parseFires = parseFires
+module.exports.parseSmokes = parseSmokes
+module.exports.parseBomb = parseBomb
+module.exports.parseKills = parseKills
+module.exports.parseVisibility = parseVisibility
+module.exports.parseMovement = parseMovement
+module.exports.parseShots = parseShots
+module.exports.parseRecoil = parseRecoil
+module.exports.parseAim = parseAim
+module.exports.parseEconomy = parseEconomy
+module.exports.parseWeapons = parseWeapons
+module.exports.parseWeaponSkins = parseWeaponSkins
+module.exports.parseStatsUpdates = parseStatsUpdates
+module.exports.parseUserCmds = parseUserCmds
+module.exports.parseVoice = parseVoice
+module.exports.parseVoiceActivity = parseVoiceActivity
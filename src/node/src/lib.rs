@@ -6,14 +6,68 @@ use ahash::AHashMap;
 use memmap2::MmapOptions;
 use napi::bindgen_prelude::*;
 use napi::Either;
+use parser::analysis::aim::aim;
+use parser::analysis::aim::aim_inputs;
+use parser::analysis::bomb::bomb_inputs;
+use parser::analysis::bomb::bomb_timeline;
+use parser::analysis::duels::duels;
+use parser::analysis::duels::duels_inputs;
+use parser::analysis::duels::DEFAULT_TRADE_WINDOW_SECONDS;
+use parser::analysis::economy::economy;
+use parser::analysis::economy::economy_inputs;
+use parser::analysis::economy::BuyThresholds;
+use parser::analysis::fires_and_smokes::fires_and_smokes;
+use parser::analysis::fires_and_smokes::fires_and_smokes_inputs;
+use parser::analysis::flashes::flashes;
+use parser::analysis::flashes::flashes_inputs;
+use parser::analysis::flashes::DEFAULT_FLASH_ASSIST_WINDOW_SECONDS;
+use parser::analysis::grenades::grenade_throws;
+use parser::analysis::grenades::grenade_throws_inputs;
+use parser::analysis::highlights::highlights;
+use parser::analysis::highlights::highlights_inputs;
+use parser::analysis::highlights::DEFAULT_HIGHLIGHT_PADDING_SECONDS;
+use parser::analysis::kills::kills;
+use parser::analysis::kills::kills_inputs;
+use parser::analysis::movement::movement;
+use parser::analysis::movement::movement_inputs;
+use parser::analysis::player_stats::player_stats;
+use parser::analysis::player_stats::player_stats_inputs;
+use parser::analysis::recoil::recoil;
+use parser::analysis::recoil::recoil_inputs;
+use parser::analysis::shots::shots;
+use parser::analysis::shots::shots_inputs;
+use parser::analysis::stats_updates::stats_updates;
+use parser::analysis::stats_updates::stats_updates_inputs;
+use parser::analysis::user_cmds::user_cmds_inputs;
+use parser::analysis::visibility::visibility;
+use parser::analysis::visibility::visibility_inputs;
+use parser::analysis::voice_activity::voice_activity;
+use parser::analysis::voice_activity::voice_activity_inputs;
+use parser::analysis::voice_activity::DEFAULT_VOICE_GAP_SECONDS;
+use parser::analysis::weapon_skins::weapon_skins_inputs;
+use parser::analysis::weapons::weapons;
+use parser::analysis::weapons::weapons_inputs;
+use parser::first_pass::parser::parse_file_info as parse_demo_file_info;
 use parser::first_pass::parser_settings::rm_user_friendly_names;
 use parser::first_pass::parser_settings::ParserInputs;
+use parser::first_pass::read_bits::DemoParserError;
 use parser::parse_demo::DemoOutput;
 use parser::parse_demo::Parser;
+use parser::radar::MapOverview;
 use parser::second_pass::parser_settings::create_huffman_lookup_table;
+use parser::second_pass::parser_settings::PlayerAccolade;
 use parser::second_pass::variants::soa_to_aos;
 use parser::second_pass::variants::BytesVariant;
 use parser::second_pass::variants::OutputSerdeHelperStruct;
+#[cfg(feature = "voice")]
+use parser::voice::voice_to_wav;
+#[cfg(feature = "voice")]
+use parser::voice::OpusDecoder;
+use parser::voice::PlayerVoiceWav;
+use parser::voice::VoiceRecord;
+#[cfg(feature = "voice")]
+use parser::voice::VOICE_SAMPLE_RATE;
+use parser::zones::Zones;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs::File;
@@ -46,6 +100,15 @@ pub fn list_game_events(path_or_buf: Either<String, Buffer>) -> napi::Result<Val
     parse_ents: false,
     wanted_ticks: vec![],
     parse_projectiles: false,
+    collect_bomb: false,
+    collect_infernos: false,
+    collect_weapon_skins: false,
+    collect_economy: false,
+    collect_aim: false,
+    collect_movement: false,
+    collect_spotted: false,
+    track_player_history: false,
+    parse_user_cmds: false,
     only_header: false,
     count_props: false,
     only_convars: false,
@@ -63,7 +126,11 @@ pub fn list_game_events(path_or_buf: Either<String, Buffer>) -> napi::Result<Val
 }
 
 #[napi]
-pub fn parse_grenades(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+pub fn parse_grenades(
+  path_or_buf: Either<String, Buffer>,
+  overview_path: Option<String>,
+  zones_path: Option<String>,
+) -> napi::Result<Value> {
   let bytes = resolve_byte_type(path_or_buf)?;
   let huf = create_huffman_lookup_table();
 
@@ -76,12 +143,23 @@ pub fn parse_grenades(path_or_buf: Either<String, Buffer>) -> napi::Result<Value
     parse_ents: true,
     wanted_ticks: vec![],
     parse_projectiles: true,
+    collect_bomb: false,
+    collect_infernos: false,
+    collect_weapon_skins: false,
+    collect_economy: false,
+    collect_aim: false,
+    collect_movement: false,
+    collect_spotted: false,
+    track_player_history: false,
+    parse_user_cmds: false,
     only_header: true,
     count_props: false,
     only_convars: false,
     huffman_lookup_table: &huf,
   };
   let mut parser = Parser::new(settings, false);
+  parser.overview = resolve_overview(overview_path)?;
+  parser.zones = resolve_zones(zones_path)?;
   let output = parse_demo(bytes, &mut parser)?;
 
   let s = match serde_json::to_value(&output.projectiles) {
@@ -104,6 +182,15 @@ pub fn parse_header(path_or_buf: Either<String, Buffer>) -> napi::Result<Value>
     parse_ents: false,
     wanted_ticks: vec![],
     parse_projectiles: false,
+    collect_bomb: false,
+    collect_infernos: false,
+    collect_weapon_skins: false,
+    collect_economy: false,
+    collect_aim: false,
+    collect_movement: false,
+    collect_spotted: false,
+    track_player_history: false,
+    parse_user_cmds: false,
     only_header: true,
     count_props: false,
     only_convars: false,
@@ -129,6 +216,8 @@ pub fn parse_event(
   event_name: String,
   player_extra: Option<Vec<String>>,
   other_extra: Option<Vec<String>>,
+  overview_path: Option<String>,
+  zones_path: Option<String>,
 ) -> napi::Result<Value> {
   let player_props = match player_extra {
     Some(p) => p,
@@ -167,12 +256,23 @@ pub fn parse_event(
     parse_ents: true,
     wanted_ticks: vec![],
     parse_projectiles: false,
+    collect_bomb: false,
+    collect_infernos: false,
+    collect_weapon_skins: false,
+    collect_economy: false,
+    collect_aim: false,
+    collect_movement: false,
+    collect_spotted: false,
+    track_player_history: false,
+    parse_user_cmds: false,
     only_header: true,
     count_props: false,
     only_convars: false,
     huffman_lookup_table: &huf,
   };
   let mut parser = Parser::new(settings, false);
+  parser.overview = resolve_overview(overview_path)?;
+  parser.zones = resolve_zones(zones_path)?;
   let output = parse_demo(bytes, &mut parser)?;
   let s = match serde_json::to_value(&output.game_events) {
     Ok(s) => s,
@@ -186,6 +286,8 @@ pub fn parse_events(
   event_names: Option<Vec<String>>,
   player_extra: Option<Vec<String>>,
   other_extra: Option<Vec<String>>,
+  overview_path: Option<String>,
+  zones_path: Option<String>,
 ) -> napi::Result<Value> {
   let event_names = match event_names {
     None => return Err(Error::new(Status::InvalidArg, "No events provided!")),
@@ -228,12 +330,23 @@ pub fn parse_events(
     parse_ents: true,
     wanted_ticks: vec![],
     parse_projectiles: false,
+    collect_bomb: false,
+    collect_infernos: false,
+    collect_weapon_skins: false,
+    collect_economy: false,
+    collect_aim: false,
+    collect_movement: false,
+    collect_spotted: false,
+    track_player_history: false,
+    parse_user_cmds: false,
     only_header: true,
     count_props: false,
     only_convars: false,
     huffman_lookup_table: &huf,
   };
   let mut parser = Parser::new(settings, false);
+  parser.overview = resolve_overview(overview_path)?;
+  parser.zones = resolve_zones(zones_path)?;
   let output = parse_demo(bytes, &mut parser)?;
   let s = match serde_json::to_value(&output.game_events) {
     Ok(s) => s,
@@ -249,6 +362,8 @@ pub fn parse_ticks(
   wanted_ticks: Option<Vec<i32>>,
   wanted_players: Option<Vec<String>>,
   struct_of_arrays: Option<bool>,
+  overview_path: Option<String>,
+  zones_path: Option<String>,
 ) -> napi::Result<Value> {
   let mut real_names = match rm_user_friendly_names(&wanted_props) {
     Ok(names) => names,
@@ -281,12 +396,23 @@ pub fn parse_ticks(
     parse_ents: true,
     wanted_ticks: wanted_ticks,
     parse_projectiles: false,
+    collect_bomb: false,
+    collect_infernos: false,
+    collect_weapon_skins: false,
+    collect_economy: false,
+    collect_aim: false,
+    collect_movement: false,
+    collect_spotted: false,
+    track_player_history: false,
+    parse_user_cmds: false,
     only_header: false,
     count_props: false,
     only_convars: false,
     huffman_lookup_table: &huf,
   };
   let mut parser = Parser::new(settings, false);
+  parser.overview = resolve_overview(overview_path)?;
+  parser.zones = resolve_zones(zones_path)?;
   let output = parse_demo(bytes, &mut parser)?;
   real_names.push("tick".to_owned());
   real_names.push("steamid".to_owned());
@@ -322,6 +448,20 @@ pub fn parse_ticks(
   }
 }
 
+#[napi]
+pub fn parse_file_info(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let file_info = match parse_demo_file_info(&bytes[..]) {
+    Ok(file_info) => file_info,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let s = match serde_json::to_value(&file_info) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
 #[napi]
 pub fn parse_player_info(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
   let bytes = resolve_byte_type(path_or_buf)?;
@@ -336,6 +476,15 @@ pub fn parse_player_info(path_or_buf: Either<String, Buffer>) -> napi::Result<Va
     parse_ents: true,
     wanted_ticks: vec![],
     parse_projectiles: false,
+    collect_bomb: false,
+    collect_infernos: false,
+    collect_weapon_skins: false,
+    collect_economy: false,
+    collect_aim: false,
+    collect_movement: false,
+    collect_spotted: false,
+    track_player_history: false,
+    parse_user_cmds: false,
     only_header: true,
     count_props: false,
     only_convars: false,
@@ -350,6 +499,592 @@ pub fn parse_player_info(path_or_buf: Either<String, Buffer>) -> napi::Result<Va
   Ok(s)
 }
 
+#[napi]
+pub fn parse_accolades(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = ParserInputs {
+    wanted_players: vec![],
+    real_name_to_og_name: AHashMap::default(),
+    wanted_player_props: vec![],
+    wanted_other_props: vec![],
+    wanted_events: vec![],
+    parse_ents: true,
+    wanted_ticks: vec![],
+    parse_projectiles: false,
+    collect_bomb: false,
+    collect_infernos: false,
+    collect_weapon_skins: false,
+    collect_economy: false,
+    collect_aim: false,
+    collect_movement: false,
+    collect_spotted: false,
+    track_player_history: false,
+    parse_user_cmds: false,
+    only_header: true,
+    count_props: false,
+    only_convars: false,
+    huffman_lookup_table: &huf,
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let accolades: Vec<PlayerAccolade> = output
+    .player_md
+    .iter()
+    .filter_map(|p| p.accolade.clone())
+    .collect();
+  let s = match serde_json::to_value(&accolades) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_player_stats(path_or_buf: Either<String, Buffer>, per_round: Option<bool>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match player_stats_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let stats = player_stats(&output.game_events);
+
+  let s = match per_round {
+    Some(true) => serde_json::to_value(&stats.per_round),
+    _ => serde_json::to_value(&stats.per_match),
+  };
+  match s {
+    Ok(s) => Ok(s),
+    Err(e) => Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  }
+}
+
+#[napi]
+pub fn parse_duels(
+  path_or_buf: Either<String, Buffer>,
+  trade_window: Option<f64>,
+  per_player: Option<bool>,
+) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match duels_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let trade_window = trade_window.map(|w| w as f32).unwrap_or(DEFAULT_TRADE_WINDOW_SECONDS);
+  let duels = duels(&output.game_events, trade_window);
+
+  let s = match per_player {
+    Some(true) => serde_json::to_value(&duels.players),
+    _ => serde_json::to_value(&duels.kills),
+  };
+  match s {
+    Ok(s) => Ok(s),
+    Err(e) => Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  }
+}
+
+#[napi]
+pub fn parse_highlights(path_or_buf: Either<String, Buffer>, padding: Option<f64>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match highlights_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let padding = padding.map(|p| p as f32).unwrap_or(DEFAULT_HIGHLIGHT_PADDING_SECONDS);
+  let highlights = highlights(&output.game_events, padding);
+
+  let s = match serde_json::to_value(&highlights) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_flashes(
+  path_or_buf: Either<String, Buffer>,
+  flash_assist_window: Option<f64>,
+  per_player: Option<bool>,
+) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match flashes_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let flash_assist_window = flash_assist_window
+    .map(|w| w as f32)
+    .unwrap_or(DEFAULT_FLASH_ASSIST_WINDOW_SECONDS);
+  let flashes = flashes(&output.game_events, &output.projectiles, flash_assist_window);
+
+  let s = match per_player {
+    Some(true) => serde_json::to_value(&flashes.players),
+    _ => serde_json::to_value(&flashes.flashes),
+  };
+  match s {
+    Ok(s) => Ok(s),
+    Err(e) => Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  }
+}
+
+#[napi]
+pub fn parse_grenade_throws(path_or_buf: Either<String, Buffer>, zones_path: Option<String>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match grenade_throws_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  parser.zones = resolve_zones(zones_path)?;
+  let output = parse_demo(bytes, &mut parser)?;
+  let throws = grenade_throws(&output.game_events, &output.projectiles);
+
+  let s = match serde_json::to_value(&throws) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_fires(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match fires_and_smokes_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let fires = fires_and_smokes(&output.game_events, &output.infernos).fires;
+
+  let s = match serde_json::to_value(&fires) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_smokes(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match fires_and_smokes_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let smokes = fires_and_smokes(&output.game_events, &output.infernos).smokes;
+
+  let s = match serde_json::to_value(&smokes) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_bomb(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match bomb_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let timeline = bomb_timeline(&output.game_events, &output.bombs);
+
+  let s = match serde_json::to_value(&timeline.rounds) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_kills(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match kills_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let kills = kills(&output.game_events);
+
+  let s = match serde_json::to_value(&kills) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_visibility(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match visibility_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let visibility = visibility(&output.game_events, &output.spotted);
+
+  let s = match serde_json::to_value(&visibility) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_movement(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match movement_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let movement = movement(&output.game_events, &output.movement);
+
+  let s = match serde_json::to_value(&movement) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_shots(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match shots_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let shots = shots(&output.game_events);
+
+  let s = match serde_json::to_value(&shots) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_recoil(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match recoil_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let recoil = recoil(&output.game_events);
+
+  let s = match serde_json::to_value(&recoil) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_aim(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match aim_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let aim = aim(&output.game_events, &output.aim);
+
+  let s = match serde_json::to_value(&aim) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_economy(
+  path_or_buf: Either<String, Buffer>,
+  eco: Option<i32>,
+  semi_eco: Option<i32>,
+  force: Option<i32>,
+) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match economy_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let defaults = BuyThresholds::default();
+  let thresholds = BuyThresholds {
+    eco: eco.unwrap_or(defaults.eco),
+    semi_eco: semi_eco.unwrap_or(defaults.semi_eco),
+    force: force.unwrap_or(defaults.force),
+  };
+  let economy = economy(&output.game_events, &output.economy, &thresholds);
+
+  let s = match serde_json::to_value(&economy) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_weapons(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match weapons_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let weapons = weapons(&output.game_events);
+
+  let s = match serde_json::to_value(&weapons) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_weapon_skins(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match weapon_skins_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let weapon_skins = output.weapon_skins;
+
+  let s = match serde_json::to_value(&weapon_skins) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_stats_updates(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match stats_updates_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let stats_updates = stats_updates(
+    &output.game_events,
+    &output.player_stats_updates,
+    &output.match_stats_updates,
+  );
+
+  let s = match serde_json::to_value(&stats_updates) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[napi]
+pub fn parse_user_cmds(path_or_buf: Either<String, Buffer>) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match user_cmds_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let user_cmds = output.user_cmds;
+
+  let s = match serde_json::to_value(&user_cmds) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+#[cfg(feature = "voice")]
+struct LibOpus(opus::Decoder);
+
+#[cfg(feature = "voice")]
+impl OpusDecoder for LibOpus {
+  fn decode(&mut self, frame: &[u8], pcm: &mut [i16]) -> Result<usize, DemoParserError> {
+    match self.0.decode(frame, pcm, false) {
+      Ok(n_samples) => Ok(n_samples),
+      Err(e) => Err(DemoParserError::VoiceDataWriteError(format!("{}", e))),
+    }
+  }
+}
+
+#[cfg(feature = "voice")]
+fn new_opus_decoder() -> Result<LibOpus, DemoParserError> {
+  match opus::Decoder::new(VOICE_SAMPLE_RATE, opus::Channels::Mono) {
+    Ok(decoder) => Ok(LibOpus(decoder)),
+    Err(e) => Err(DemoParserError::VoiceDataWriteError(format!("{}", e))),
+  }
+}
+
+#[cfg(feature = "voice")]
+fn voice_wavs(voice_data: &[VoiceRecord]) -> Result<Vec<PlayerVoiceWav>, DemoParserError> {
+  voice_to_wav(voice_data, new_opus_decoder)
+}
+
+// Decoding needs libopus, which only gets linked with the "voice" feature
+#[cfg(not(feature = "voice"))]
+fn voice_wavs(_voice_data: &[VoiceRecord]) -> Result<Vec<PlayerVoiceWav>, DemoParserError> {
+  Err(DemoParserError::VoiceDataWriteError(
+    "built without the \"voice\" feature".to_string(),
+  ))
+}
+
+#[napi]
+pub fn parse_voice(path_or_buf: Either<String, Buffer>) -> napi::Result<HashMap<String, Buffer>> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = ParserInputs {
+    wanted_players: vec![],
+    real_name_to_og_name: AHashMap::default(),
+    wanted_player_props: vec![],
+    wanted_other_props: vec![],
+    wanted_events: vec![],
+    parse_ents: false,
+    wanted_ticks: vec![],
+    parse_projectiles: false,
+    collect_bomb: false,
+    collect_infernos: false,
+    collect_weapon_skins: false,
+    collect_economy: false,
+    collect_aim: false,
+    collect_movement: false,
+    collect_spotted: false,
+    track_player_history: false,
+    parse_user_cmds: false,
+    only_header: true,
+    count_props: false,
+    only_convars: false,
+    huffman_lookup_table: &huf,
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let wavs = match voice_wavs(&output.voice_data) {
+    Ok(wavs) => wavs,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut per_player = HashMap::default();
+  for w in wavs {
+    per_player.insert(w.player_id(), w.wav.into());
+  }
+  Ok(per_player)
+}
+
+#[napi]
+pub fn parse_voice_activity(
+  path_or_buf: Either<String, Buffer>,
+  gap_seconds: Option<f64>,
+) -> napi::Result<Value> {
+  let bytes = resolve_byte_type(path_or_buf)?;
+  let huf = create_huffman_lookup_table();
+
+  let settings = match voice_activity_inputs(&huf) {
+    Ok(settings) => settings,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  let mut parser = Parser::new(settings, false);
+  let output = parse_demo(bytes, &mut parser)?;
+  let gap_seconds = gap_seconds
+    .map(|g| g as f32)
+    .unwrap_or(DEFAULT_VOICE_GAP_SECONDS);
+  let segments = voice_activity(
+    &output.game_events,
+    &output.voice_data,
+    &output.player_md,
+    gap_seconds,
+  );
+
+  let s = match serde_json::to_value(&segments) {
+    Ok(s) => s,
+    Err(e) => return Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+  };
+  Ok(s)
+}
+
+fn resolve_overview(overview_path: Option<String>) -> Result<Option<MapOverview>, napi::Error> {
+  match overview_path {
+    Some(path) => match MapOverview::from_file(&path) {
+      Ok(overview) => Ok(Some(overview)),
+      Err(e) => Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+    },
+    None => Ok(None),
+  }
+}
+
+fn resolve_zones(zones_path: Option<String>) -> Result<Option<Zones>, napi::Error> {
+  match zones_path {
+    Some(path) => match Zones::from_file(&path) {
+      Ok(zones) => Ok(Some(zones)),
+      Err(e) => Err(Error::new(Status::InvalidArg, format!("{}", e).to_owned())),
+    },
+    None => Ok(None),
+  }
+}
+
 fn resolve_byte_type(path_or_buf: Either<String, Buffer>) -> Result<BytesVariant, napi::Error> {
   match path_or_buf {
     Either::A(path) => {
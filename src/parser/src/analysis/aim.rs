@@ -0,0 +1,267 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field_steamid;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::collect_data::AimRecord;
+use crate::second_pass::game_events::GameEvent;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+
+pub const AIM_EVENTS: &[&str] = &["weapon_fire", "player_death", "round_end", "round_start"];
+// Asking for angular_velocity in events turns on collecting flicks and tracking
+pub const AIM_PLAYER_PROPS: &[&str] = &["team_num", "angular_velocity"];
+pub const AIM_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// A flick counts as ending on the head when it stops this close to it
+pub const HEAD_OFFSET_DEGREES: f32 = 2.0;
+// ...and the player shoots at most this many ticks after it stops
+pub const SHOT_AFTER_FLICK_TICKS: i32 = 8;
+// Tracking has to follow the target at least this many degrees, not just hold an angle
+pub const TRACKING_MIN_ANGLE: f32 = 5.0;
+// Ticks shown around the evidence for reviewing it
+pub const REVIEW_PADDING_TICKS: i32 = 128;
+
+pub fn aim_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(AIM_EVENTS, AIM_PLAYER_PROPS, AIM_OTHER_PROPS, huffman_lookup_table)?;
+    inputs.track_player_history = true;
+    inputs.collect_aim = true;
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AimEvidence {
+    // "flick_to_head" or "occluded_tracking"
+    pub kind: String,
+    pub round: i32,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    pub target_steamid: Option<u64>,
+    pub target_name: Option<String>,
+    pub start_tick: i32,
+    pub end_tick: i32,
+    pub review_start_tick: i32,
+    pub review_end_tick: i32,
+    pub duration: f32,
+    // Degrees the view moved
+    pub angle: f32,
+    // Degrees per second
+    pub peak_speed: f32,
+    // Degrees per second squared
+    pub peak_acceleration: f32,
+    // Degrees from the target's head, at the end of a flick and on average while tracking
+    pub target_offset: Option<f32>,
+    pub target_spotted: Option<bool>,
+    // First shot of the player after the flick / during the tracking
+    pub shot_tick: Option<i32>,
+    pub killed_target: bool,
+    // Standard score of the peak speed among all flicks / of the duration among all tracking
+    pub score: f32,
+}
+
+fn standard_scores(values: &[f32]) -> Vec<f32> {
+    let n = values.len() as f32;
+    let mean = values.iter().sum::<f32>() / n;
+    let std = (values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / n).sqrt();
+    values
+        .iter()
+        .map(|v| if std > 0.0 { (v - mean) / std } else { 0.0 })
+        .collect()
+}
+
+/// Picks the suspicious moments out of the flicks and tracking collected during parsing:
+/// flicks that stop within HEAD_OFFSET_DEGREES of an enemy head right before a shot and the
+/// crosshair following an enemy head through walls while the enemy is not spotted. Every row
+/// has the ticks to review and a score that tells how unusual it is compared to the rest of
+/// the demo, the rows are sorted by it so the review can start from the top.
+/// Expects the events and aim records from aim_inputs().
+pub fn aim(events: &[GameEvent], records: &[AimRecord]) -> Vec<AimEvidence> {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+    let first_tick = rounds.round_starts.first().map(|(tick, _)| *tick).unwrap_or(0);
+    let shots: Vec<(PlayerKey, i32)> = events
+        .iter()
+        .filter(|e| e.name == "weapon_fire")
+        .filter_map(|e| Some((PlayerKey::from_event(e, "user")?, e.tick)))
+        .collect();
+    let deaths: Vec<(Option<u64>, Option<u64>, i32)> = events
+        .iter()
+        .filter(|e| e.name == "player_death")
+        .map(|e| (field_steamid(e, "attacker"), field_steamid(e, "user"), e.tick))
+        .collect();
+    let first_shot = |player: &Option<PlayerKey>, from: i32, to: i32| {
+        shots
+            .iter()
+            .find(|(key, tick)| Some(key) == player.as_ref() && (from..=to).contains(tick))
+            .map(|(_, tick)| *tick)
+    };
+
+    let mut evidence = vec![];
+    for kind in ["flick", "occluded_tracking"] {
+        let records: Vec<&AimRecord> = records
+            .iter()
+            .filter(|r| r.kind == kind && r.start_tick >= first_tick)
+            .collect();
+        if records.is_empty() {
+            continue;
+        }
+        let scores = match kind {
+            "flick" => standard_scores(&records.iter().map(|r| r.peak_speed).collect::<Vec<f32>>()),
+            _ => standard_scores(
+                &records
+                    .iter()
+                    .map(|r| (r.end_tick - r.start_tick) as f32)
+                    .collect::<Vec<f32>>(),
+            ),
+        };
+        for (record, score) in records.iter().zip(scores) {
+            let player = PlayerKey::from_record(record.steamid, &record.name);
+            let (evidence_kind, shot_tick) = match kind {
+                "flick" => {
+                    let shot_tick = first_shot(&player, record.start_tick, record.end_tick + SHOT_AFTER_FLICK_TICKS);
+                    match (shot_tick, record.target_offset) {
+                        (Some(_), Some(offset)) if offset <= HEAD_OFFSET_DEGREES => ("flick_to_head", shot_tick),
+                        _ => continue,
+                    }
+                }
+                _ if record.angle >= TRACKING_MIN_ANGLE => {
+                    ("occluded_tracking", first_shot(&player, record.start_tick, record.end_tick))
+                }
+                _ => continue,
+            };
+            let review_end_tick = record.end_tick + REVIEW_PADDING_TICKS;
+            let killed_target = record.steamid.is_some()
+                && deaths.iter().any(|(attacker, victim, tick)| {
+                    *attacker == record.steamid
+                        && *victim == record.target_steamid
+                        && (record.start_tick..=review_end_tick).contains(tick)
+                });
+            evidence.push(AimEvidence {
+                kind: evidence_kind.to_string(),
+                round: rounds.round_at(record.start_tick),
+                steamid: record.steamid,
+                name: record.name.clone(),
+                target_steamid: record.target_steamid,
+                target_name: record.target_name.clone(),
+                start_tick: record.start_tick,
+                end_tick: record.end_tick,
+                review_start_tick: record.start_tick - REVIEW_PADDING_TICKS,
+                review_end_tick,
+                duration: (record.end_tick - record.start_tick + 1) as f32 / TICKRATE,
+                angle: record.angle,
+                peak_speed: record.peak_speed,
+                peak_acceleration: record.peak_acceleration,
+                target_offset: record.target_offset,
+                target_spotted: record.target_spotted,
+                shot_tick,
+                killed_target,
+                score,
+            });
+        }
+    }
+    evidence.sort_by(|a, b| b.score.total_cmp(&a.score));
+    evidence
+}
+
+impl Serialize for AimEvidence {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("AimEvidence", 19)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("target_steamid", &self.target_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("target_name", &self.target_name)?;
+        state.serialize_field("start_tick", &self.start_tick)?;
+        state.serialize_field("end_tick", &self.end_tick)?;
+        state.serialize_field("review_start_tick", &self.review_start_tick)?;
+        state.serialize_field("review_end_tick", &self.review_end_tick)?;
+        state.serialize_field("duration", &self.duration)?;
+        state.serialize_field("angle", &self.angle)?;
+        state.serialize_field("peak_speed", &self.peak_speed)?;
+        state.serialize_field("peak_acceleration", &self.peak_acceleration)?;
+        state.serialize_field("target_offset", &self.target_offset)?;
+        state.serialize_field("target_spotted", &self.target_spotted)?;
+        state.serialize_field("shot_tick", &self.shot_tick)?;
+        state.serialize_field("killed_target", &self.killed_target)?;
+        state.serialize_field("score", &self.score)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::aim::*;
+    use crate::analysis::test_event;
+    use crate::second_pass::variants::Variant;
+
+    fn record(kind: &str, (start_tick, end_tick): (i32, i32), angle: f32, peak_speed: f32, target_offset: f32) -> AimRecord {
+        AimRecord {
+            kind: kind.to_string(),
+            steamid: Some(1),
+            name: Some("player1".to_string()),
+            target_steamid: Some(2),
+            target_name: Some("player2".to_string()),
+            start_tick,
+            end_tick,
+            angle,
+            peak_speed,
+            peak_acceleration: 0.0,
+            target_offset: Some(target_offset),
+            target_spotted: Some(true),
+        }
+    }
+
+    #[test]
+    fn test_standard_scores() {
+        let scores = standard_scores(&[1.0, 2.0, 3.0]);
+        assert!((scores[0] + 1.2247449).abs() < 1e-5);
+        assert_eq!(scores[1], 0.0);
+        assert!((scores[2] - 1.2247449).abs() < 1e-5);
+        assert_eq!(standard_scores(&[5.0, 5.0]), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_aim() {
+        let events = vec![
+            test_event("round_start", 0, vec![("round", Variant::I32(1))]),
+            test_event("weapon_fire", 110, vec![("user_steamid", Variant::String("1".to_string()))]),
+            test_event(
+                "player_death",
+                120,
+                vec![
+                    ("attacker_steamid", Variant::String("1".to_string())),
+                    ("user_steamid", Variant::String("2".to_string())),
+                ],
+            ),
+        ];
+        let records = vec![
+            // Stops on the head and shoots 5 ticks later
+            record("flick", (100, 105), 40.0, 1500.0, 1.0),
+            // Stops too far from the head
+            record("flick", (100, 105), 40.0, 800.0, 10.0),
+            // Nobody shoots after it
+            record("flick", (1000, 1005), 40.0, 700.0, 1.0),
+            record("occluded_tracking", (2000, 2100), 10.0, 100.0, 1.5),
+            // Holding an angle, not following anyone
+            record("occluded_tracking", (3000, 3020), 2.0, 10.0, 1.5),
+        ];
+        let out = aim(&events, &records);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].kind, "flick_to_head");
+        assert_eq!(out[0].shot_tick, Some(110));
+        assert!(out[0].killed_target);
+        assert_eq!((out[0].review_start_tick, out[0].review_end_tick), (100 - 128, 105 + 128));
+        assert!(out[0].score > 1.0);
+        assert_eq!(out[1].kind, "occluded_tracking");
+        assert_eq!(out[1].shot_tick, None);
+        assert!(!out[1].killed_target);
+        assert!(out[0].score >= out[1].score);
+    }
+}
@@ -0,0 +1,426 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field_bool;
+use crate::analysis::field_f32;
+use crate::analysis::field_steamid;
+use crate::analysis::field_string;
+use crate::analysis::sorted_events;
+use crate::analysis::RoundTimeline;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::collect_data::BombRecord;
+use crate::second_pass::game_events::GameEvent;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const BOMB_EVENTS: &[&str] = &[
+    "bomb_beginplant",
+    "bomb_abortplant",
+    "bomb_planted",
+    "bomb_begindefuse",
+    "bomb_abortdefuse",
+    "bomb_defused",
+    "bomb_exploded",
+    "round_end",
+    "round_start",
+];
+pub const BOMB_PLAYER_PROPS: &[&str] = &["team_num", "X", "Y", "Z"];
+pub const BOMB_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+pub fn bomb_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(BOMB_EVENTS, BOMB_PLAYER_PROPS, BOMB_OTHER_PROPS, huffman_lookup_table)?;
+    inputs.collect_bomb = true;
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BombEvent {
+    pub round: i32,
+    pub tick: i32,
+    // pickup, drop, plant_start, plant_abort, planted, defuse_start, defuse_abort, defused, exploded
+    pub event_type: String,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub z: Option<f32>,
+    pub site: Option<String>,
+    pub has_kit: Option<bool>,
+    pub defuse_progress: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BombRound {
+    pub round: i32,
+    // Whoever had the bomb first in the round
+    pub first_carrier_steamid: Option<u64>,
+    pub first_carrier_name: Option<String>,
+    pub drops: i32,
+    pub plant_attempts: i32,
+    pub planter_steamid: Option<u64>,
+    pub planter_name: Option<String>,
+    pub plant_tick: Option<i32>,
+    pub site: Option<String>,
+    pub plant_x: Option<f32>,
+    pub plant_y: Option<f32>,
+    pub plant_z: Option<f32>,
+    pub defuse_attempts: i32,
+    pub defuser_steamid: Option<u64>,
+    pub defuser_name: Option<String>,
+    pub defused_tick: Option<i32>,
+    pub exploded_tick: Option<i32>,
+    // Seconds left on the bomb when it was defused
+    pub time_left_on_defuse: Option<f32>,
+    pub events: Vec<BombEvent>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BombTimeline {
+    pub rounds: Vec<BombRound>,
+}
+
+fn site_name(site: Option<i32>) -> Option<String> {
+    match site? {
+        0 => Some("A".to_string()),
+        1 => Some("B".to_string()),
+        _ => None,
+    }
+}
+
+fn event_type(event_name: &str) -> Option<&'static str> {
+    match event_name {
+        "bomb_beginplant" => Some("plant_start"),
+        "bomb_abortplant" => Some("plant_abort"),
+        "bomb_planted" => Some("planted"),
+        "bomb_begindefuse" => Some("defuse_start"),
+        "bomb_abortdefuse" => Some("defuse_abort"),
+        "bomb_defused" => Some("defused"),
+        "bomb_exploded" => Some("exploded"),
+        _ => None,
+    }
+}
+
+/// Combines the per tick CC4/CPlantedC4 records with the bomb events into one timeline per
+/// round. Carrier changes (pickups and drops) come from the owner of the bomb entity so the
+/// bomb being handed out at the start of the round is included, plants and defuses come from
+/// the events with the position of the player. The planted bomb gives the site and defuse
+/// progress, so aborted defuses show how close they were.
+/// Expects the events and bomb records from bomb_inputs().
+pub fn bomb_timeline(events: &[GameEvent], bombs: &[BombRecord]) -> BombTimeline {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+    let first_tick = rounds.round_starts.first().map(|(tick, _)| *tick).unwrap_or(0);
+
+    let mut records: Vec<&BombRecord> = bombs.iter().filter(|r| r.tick >= first_tick).collect();
+    records.sort_by_key(|r| r.tick);
+    records.dedup_by_key(|r| r.tick);
+
+    let mut timeline: Vec<BombEvent> = vec![];
+    let mut prev: Option<&BombRecord> = None;
+    for record in &records {
+        if !record.is_planted {
+            let prev_carrier = prev
+                .filter(|p| !p.is_planted && p.entity_id == record.entity_id)
+                .and_then(|p| p.carrier_steamid);
+            if record.carrier_steamid != prev_carrier {
+                if let Some(p) = prev.filter(|_| prev_carrier.is_some()) {
+                    timeline.push(BombEvent {
+                        round: rounds.round_at(record.tick),
+                        tick: record.tick,
+                        event_type: "drop".to_string(),
+                        steamid: p.carrier_steamid,
+                        name: p.carrier_name.clone(),
+                        x: record.x,
+                        y: record.y,
+                        z: record.z,
+                        ..Default::default()
+                    });
+                }
+                if record.carrier_steamid.is_some() {
+                    timeline.push(BombEvent {
+                        round: rounds.round_at(record.tick),
+                        tick: record.tick,
+                        event_type: "pickup".to_string(),
+                        steamid: record.carrier_steamid,
+                        name: record.carrier_name.clone(),
+                        x: record.x,
+                        y: record.y,
+                        z: record.z,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+        prev = Some(record);
+    }
+    let same_round = |a: i32, b: i32| rounds.round_at(a) == rounds.round_at(b);
+    let last_planted_record = |tick: i32| {
+        records
+            .iter()
+            .rev()
+            .find(|r| r.is_planted && r.tick <= tick && same_round(r.tick, tick))
+    };
+
+    for event in &events {
+        let event_type = match event_type(&event.name) {
+            Some(t) => t,
+            None => continue,
+        };
+        let planted = last_planted_record(event.tick);
+        let site = match event_type {
+            "plant_start" | "plant_abort" => None,
+            _ => planted.and_then(|r| site_name(r.site)),
+        };
+        let defuse_progress = match event_type {
+            "defuse_start" => Some(0.0),
+            // The bomb stops being defused in the same tick, use the tick before
+            "defuse_abort" => records
+                .iter()
+                .rev()
+                .find(|r| r.is_planted && r.tick < event.tick && same_round(r.tick, event.tick) && r.being_defused == Some(true))
+                .and_then(|r| r.defuse_progress),
+            "defused" => Some(1.0),
+            _ => None,
+        };
+        // The planted bomb is more accurate than the planter's position
+        let (x, y, z) = match (event_type, planted) {
+            ("planted", Some(r)) if r.x.is_some() => (r.x, r.y, r.z),
+            _ => (
+                field_f32(event, "user_X"),
+                field_f32(event, "user_Y"),
+                field_f32(event, "user_Z"),
+            ),
+        };
+        timeline.push(BombEvent {
+            round: rounds.round_at(event.tick),
+            tick: event.tick,
+            event_type: event_type.to_string(),
+            steamid: field_steamid(event, "user"),
+            name: field_string(event, "user_name").map(|s| s.to_string()),
+            x,
+            y,
+            z,
+            site,
+            has_kit: match event_type {
+                "defuse_start" => field_bool(event, "haskit"),
+                _ => None,
+            },
+            defuse_progress,
+        });
+    }
+    timeline.sort_by_key(|e| e.tick);
+
+    let mut per_round: BTreeMap<i32, BombRound> = BTreeMap::default();
+    for event in timeline {
+        let round = per_round.entry(event.round).or_insert_with(|| BombRound {
+            round: event.round,
+            ..Default::default()
+        });
+        match event.event_type.as_str() {
+            "pickup" if round.first_carrier_steamid.is_none() => {
+                round.first_carrier_steamid = event.steamid;
+                round.first_carrier_name = event.name.clone();
+            }
+            "drop" => round.drops += 1,
+            "plant_start" => round.plant_attempts += 1,
+            "planted" => {
+                round.planter_steamid = event.steamid;
+                round.planter_name = event.name.clone();
+                round.plant_tick = Some(event.tick);
+                round.site = event.site.clone();
+                round.plant_x = event.x;
+                round.plant_y = event.y;
+                round.plant_z = event.z;
+            }
+            "defuse_start" => round.defuse_attempts += 1,
+            "defused" => {
+                round.defuser_steamid = event.steamid;
+                round.defuser_name = event.name.clone();
+                round.defused_tick = Some(event.tick);
+                round.time_left_on_defuse = last_planted_record(event.tick).and_then(|r| r.time_left);
+            }
+            "exploded" => round.exploded_tick = Some(event.tick),
+            _ => {}
+        }
+        round.events.push(event);
+    }
+    BombTimeline {
+        rounds: per_round.into_values().collect(),
+    }
+}
+
+impl Serialize for BombEvent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("BombEvent", 11)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("event_type", &self.event_type)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("x", &self.x)?;
+        state.serialize_field("y", &self.y)?;
+        state.serialize_field("z", &self.z)?;
+        state.serialize_field("site", &self.site)?;
+        state.serialize_field("has_kit", &self.has_kit)?;
+        state.serialize_field("defuse_progress", &self.defuse_progress)?;
+        state.end()
+    }
+}
+impl Serialize for BombRound {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("BombRound", 19)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("first_carrier_steamid", &self.first_carrier_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("first_carrier_name", &self.first_carrier_name)?;
+        state.serialize_field("drops", &self.drops)?;
+        state.serialize_field("plant_attempts", &self.plant_attempts)?;
+        state.serialize_field("planter_steamid", &self.planter_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("planter_name", &self.planter_name)?;
+        state.serialize_field("plant_tick", &self.plant_tick)?;
+        state.serialize_field("site", &self.site)?;
+        state.serialize_field("plant_x", &self.plant_x)?;
+        state.serialize_field("plant_y", &self.plant_y)?;
+        state.serialize_field("plant_z", &self.plant_z)?;
+        state.serialize_field("defuse_attempts", &self.defuse_attempts)?;
+        state.serialize_field("defuser_steamid", &self.defuser_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("defuser_name", &self.defuser_name)?;
+        state.serialize_field("defused_tick", &self.defused_tick)?;
+        state.serialize_field("exploded_tick", &self.exploded_tick)?;
+        state.serialize_field("time_left_on_defuse", &self.time_left_on_defuse)?;
+        state.serialize_field("events", &self.events)?;
+        state.end()
+    }
+}
+impl Serialize for BombTimeline {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("BombTimeline", 1)?;
+        state.serialize_field("rounds", &self.rounds)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::bomb::*;
+    use crate::analysis::test_event;
+    use crate::second_pass::variants::Variant;
+
+    fn carried(tick: i32, carrier: Option<u64>) -> BombRecord {
+        BombRecord {
+            tick,
+            entity_id: 100,
+            is_planted: false,
+            carrier_steamid: carrier,
+            carrier_name: carrier.map(|s| format!("player{}", s)),
+            x: Some(tick as f32),
+            y: Some(0.0),
+            z: Some(0.0),
+            site: None,
+            time_left: None,
+            being_defused: None,
+            defuser_steamid: None,
+            defuse_progress: None,
+        }
+    }
+    fn planted(tick: i32, being_defused: bool, defuse_progress: f32, time_left: f32) -> BombRecord {
+        BombRecord {
+            tick,
+            entity_id: 200,
+            is_planted: true,
+            carrier_steamid: None,
+            carrier_name: None,
+            x: Some(500.0),
+            y: Some(500.0),
+            z: Some(0.0),
+            site: Some(1),
+            time_left: Some(time_left),
+            being_defused: Some(being_defused),
+            defuser_steamid: None,
+            defuse_progress: Some(defuse_progress),
+        }
+    }
+    fn bomb_event(name: &str, tick: i32, steamid: u64, haskit: Option<bool>) -> GameEvent {
+        let mut fields = vec![("user_steamid", Variant::String(steamid.to_string()))];
+        if let Some(haskit) = haskit {
+            fields.push(("haskit", Variant::Bool(haskit)));
+        }
+        test_event(name, tick, fields)
+    }
+
+    #[test]
+    fn test_site_name() {
+        assert_eq!(site_name(Some(0)), Some("A".to_string()));
+        assert_eq!(site_name(Some(1)), Some("B".to_string()));
+        assert_eq!(site_name(Some(2)), None);
+        assert_eq!(site_name(None), None);
+    }
+
+    #[test]
+    fn test_bomb_timeline() {
+        let events = vec![
+            test_event("round_start", 0, vec![("round", Variant::I32(1))]),
+            bomb_event("bomb_beginplant", 40, 2, None),
+            bomb_event("bomb_abortplant", 45, 2, None),
+            bomb_event("bomb_beginplant", 50, 2, None),
+            bomb_event("bomb_planted", 60, 2, None),
+            bomb_event("bomb_begindefuse", 90, 3, Some(false)),
+            bomb_event("bomb_abortdefuse", 101, 3, None),
+            bomb_event("bomb_begindefuse", 110, 3, Some(true)),
+            bomb_event("bomb_defused", 125, 3, None),
+        ];
+        let bombs = vec![
+            carried(10, Some(1)),
+            carried(20, None),
+            carried(30, Some(2)),
+            planted(60, false, 0.0, 40.0),
+            planted(100, true, 0.5, 30.0),
+            planted(101, false, 0.0, 29.0),
+            planted(120, true, 0.9, 10.0),
+        ];
+        let timeline = bomb_timeline(&events, &bombs);
+        assert_eq!(timeline.rounds.len(), 1);
+        let round = &timeline.rounds[0];
+        let event_types: Vec<&str> = round.events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(
+            event_types,
+            vec![
+                "pickup",
+                "drop",
+                "pickup",
+                "plant_start",
+                "plant_abort",
+                "plant_start",
+                "planted",
+                "defuse_start",
+                "defuse_abort",
+                "defuse_start",
+                "defused",
+            ]
+        );
+        // The drop is by the previous carrier
+        assert_eq!((round.events[1].steamid, round.events[1].tick), (Some(1), 20));
+        assert_eq!((round.first_carrier_steamid, round.drops), (Some(1), 1));
+        assert_eq!((round.plant_attempts, round.planter_steamid), (2, Some(2)));
+        assert_eq!((round.site.as_deref(), round.plant_x), (Some("B"), Some(500.0)));
+        let kits: Vec<Option<bool>> = round
+            .events
+            .iter()
+            .filter(|e| e.event_type == "defuse_start")
+            .map(|e| e.has_kit)
+            .collect();
+        assert_eq!(kits, vec![Some(false), Some(true)]);
+        // Progress from the tick before the abort
+        assert_eq!(round.events[8].defuse_progress, Some(0.5));
+        assert_eq!((round.defuse_attempts, round.defuser_steamid), (2, Some(3)));
+        assert_eq!((round.defused_tick, round.time_left_on_defuse), (Some(125), Some(10.0)));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field_i32;
+use crate::analysis::field_string;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::game_events::EventField;
+use crate::second_pass::game_events::GameEvent;
+use crate::second_pass::variants::Variant;
+use ahash::AHashSet;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const DUELS_EVENTS: &[&str] = &["player_death", "round_end", "round_start"];
+pub const DUELS_PLAYER_PROPS: &[&str] = &["team_num"];
+pub const DUELS_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// A death counts as traded if the killer dies within this many seconds
+pub const DEFAULT_TRADE_WINDOW_SECONDS: f32 = 5.0;
+
+pub fn duels_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    event_inputs(DUELS_EVENTS, DUELS_PLAYER_PROPS, DUELS_OTHER_PROPS, huffman_lookup_table)
+}
+
+#[derive(Debug, Clone)]
+pub struct Death {
+    pub tick: i32,
+    pub round: i32,
+    pub victim: PlayerKey,
+    pub victim_team: Option<i32>,
+    pub attacker: Option<PlayerKey>,
+    pub attacker_team: Option<i32>,
+}
+
+impl Death {
+    pub fn from_event(event: &GameEvent, round: i32) -> Option<Death> {
+        Some(Death {
+            tick: event.tick,
+            round,
+            victim: PlayerKey::from_event(event, "user")?,
+            victim_team: field_i32(event, "user_team_num"),
+            attacker: PlayerKey::from_event(event, "attacker"),
+            attacker_team: field_i32(event, "attacker_team_num"),
+        })
+    }
+    /// False for suicides, world damage and teamkills
+    pub fn is_enemy_kill(&self) -> bool {
+        match &self.attacker {
+            Some(attacker) if attacker != &self.victim => is_enemy(self.victim_team, self.attacker_team),
+            _ => false,
+        }
+    }
+}
+
+pub fn is_enemy(a: Option<i32>, b: Option<i32>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a != b,
+        // Without team info we can't tell teamkills apart, assume enemies
+        _ => true,
+    }
+}
+
+/// For every death returns the index of the death that traded it, meaning the killer was
+/// killed by an enemy (a teammate of the victim) within trade_window_ticks in the same round.
+pub fn find_trades(deaths: &[Death], trade_window_ticks: i32) -> Vec<Option<usize>> {
+    let mut trades = vec![None; deaths.len()];
+    for (idx, death) in deaths.iter().enumerate() {
+        if !death.is_enemy_kill() {
+            continue;
+        }
+        trades[idx] = (0..deaths.len()).find(|other_idx| {
+            let other = &deaths[*other_idx];
+            *other_idx != idx
+                && Some(&other.victim) == death.attacker.as_ref()
+                && other.is_enemy_kill()
+                && other.round == death.round
+                && other.tick >= death.tick
+                && other.tick - death.tick <= trade_window_ticks
+        });
+    }
+    trades
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerDuelStats {
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub kills: i32,
+    pub deaths: i32,
+    pub opening_kills: i32,
+    pub opening_deaths: i32,
+    pub opening_attempts: i32,
+    pub opening_win_percentage: f32,
+    pub trade_kills: i32,
+    pub traded_deaths: i32,
+    pub trade_kill_percentage: f32,
+    pub traded_death_percentage: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Duels {
+    // player_death events with round, is_opening_kill, is_trade_kill, was_traded,
+    // traded_steamid and trade_time added
+    pub kills: Vec<GameEvent>,
+    pub players: Vec<PlayerDuelStats>,
+}
+
+/// Finds the opening kill of every round and kills that traded a teammate's death.
+/// Expects the events from duels_inputs().
+pub fn duels(events: &[GameEvent], trade_window_seconds: f32) -> Duels {
+    let rounds = RoundTimeline::from_events(events);
+    let trade_window = (trade_window_seconds * TICKRATE) as i32;
+    let death_events: Vec<&GameEvent> = sorted_events(events)
+        .into_iter()
+        .filter(|e| e.name == "player_death")
+        .collect();
+    let deaths: Vec<Option<Death>> = death_events
+        .iter()
+        .map(|e| Death::from_event(e, rounds.round_at(e.tick)))
+        .collect();
+
+    // find_trades works on indicies so keep the events and deaths aligned
+    let valid: Vec<usize> = (0..deaths.len()).filter(|idx| deaths[*idx].is_some()).collect();
+    let valid_deaths: Vec<Death> = deaths.iter().flatten().cloned().collect();
+    let trades = find_trades(&valid_deaths, trade_window);
+
+    let mut traded_by: BTreeMap<usize, usize> = BTreeMap::default();
+    for (idx, trade) in trades.iter().enumerate() {
+        if let Some(trade) = trade {
+            traded_by.insert(valid[idx], valid[*trade]);
+        }
+    }
+    // The kill that avenged a death, pointing back at the death
+    let mut trade_of: BTreeMap<usize, usize> = BTreeMap::default();
+    for (death_idx, trade_idx) in &traded_by {
+        trade_of.entry(*trade_idx).or_insert(*death_idx);
+    }
+    let mut rounds_with_opening: AHashSet<i32> = AHashSet::default();
+    let mut players: BTreeMap<PlayerKey, PlayerDuelStats> = BTreeMap::default();
+    let mut kills = vec![];
+
+    for (idx, event) in death_events.iter().enumerate() {
+        let death = match &deaths[idx] {
+            Some(d) => d,
+            None => {
+                kills.push(enrich_death(event, rounds.round_at(event.tick), false, false, None));
+                continue;
+            }
+        };
+        let enemy_kill = death.is_enemy_kill();
+        let is_opening_kill = enemy_kill && rounds_with_opening.insert(death.round);
+        let was_traded = traded_by.contains_key(&idx);
+        let traded_death = trade_of.get(&idx).map(|d| death_events[*d]);
+
+        let victim = player_entry(&mut players, &death.victim, event, "user");
+        victim.deaths += 1;
+        if is_opening_kill {
+            victim.opening_deaths += 1;
+        }
+        if was_traded {
+            victim.traded_deaths += 1;
+        }
+        if let (Some(attacker), true) = (&death.attacker, enemy_kill) {
+            let attacker = player_entry(&mut players, attacker, event, "attacker");
+            attacker.kills += 1;
+            if is_opening_kill {
+                attacker.opening_kills += 1;
+            }
+            if traded_death.is_some() {
+                attacker.trade_kills += 1;
+            }
+        }
+        kills.push(enrich_death(event, death.round, is_opening_kill, was_traded, traded_death));
+    }
+    for stats in players.values_mut() {
+        stats.opening_attempts = stats.opening_kills + stats.opening_deaths;
+        if stats.opening_attempts > 0 {
+            stats.opening_win_percentage = stats.opening_kills as f32 / stats.opening_attempts as f32 * 100.0;
+        }
+        if stats.kills > 0 {
+            stats.trade_kill_percentage = stats.trade_kills as f32 / stats.kills as f32 * 100.0;
+        }
+        if stats.deaths > 0 {
+            stats.traded_death_percentage = stats.traded_deaths as f32 / stats.deaths as f32 * 100.0;
+        }
+    }
+    Duels {
+        kills,
+        players: players.into_values().collect(),
+    }
+}
+
+fn enrich_death(
+    event: &GameEvent,
+    round: i32,
+    is_opening_kill: bool,
+    was_traded: bool,
+    traded_death: Option<&GameEvent>,
+) -> GameEvent {
+    let mut fields = event.fields.clone();
+    fields.push(EventField {
+        name: "round".to_string(),
+        data: Some(Variant::I32(round)),
+    });
+    fields.push(EventField {
+        name: "is_opening_kill".to_string(),
+        data: Some(Variant::Bool(is_opening_kill)),
+    });
+    fields.push(EventField {
+        name: "is_trade_kill".to_string(),
+        data: Some(Variant::Bool(traded_death.is_some())),
+    });
+    fields.push(EventField {
+        name: "was_traded".to_string(),
+        data: Some(Variant::Bool(was_traded)),
+    });
+    fields.push(EventField {
+        name: "traded_steamid".to_string(),
+        data: traded_death
+            .and_then(|d| field_string(d, "user_steamid"))
+            .map(|s| Variant::String(s.to_string())),
+    });
+    fields.push(EventField {
+        name: "trade_time".to_string(),
+        data: traded_death.map(|d| Variant::F32((event.tick - d.tick) as f32 / TICKRATE)),
+    });
+    GameEvent {
+        name: event.name.clone(),
+        fields,
+        tick: event.tick,
+    }
+}
+
+fn player_entry<'a>(
+    players: &'a mut BTreeMap<PlayerKey, PlayerDuelStats>,
+    key: &PlayerKey,
+    event: &GameEvent,
+    prefix: &str,
+) -> &'a mut PlayerDuelStats {
+    let stats = players.entry(key.clone()).or_insert_with(|| PlayerDuelStats {
+        steamid: key.steamid,
+        ..Default::default()
+    });
+    if let Some(name) = field_string(event, &(prefix.to_owned() + "_name")) {
+        stats.name = Some(name.to_string());
+    }
+    stats
+}
+
+impl Serialize for PlayerDuelStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PlayerDuelStats", 12)?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("kills", &self.kills)?;
+        state.serialize_field("deaths", &self.deaths)?;
+        state.serialize_field("opening_kills", &self.opening_kills)?;
+        state.serialize_field("opening_deaths", &self.opening_deaths)?;
+        state.serialize_field("opening_attempts", &self.opening_attempts)?;
+        state.serialize_field("opening_win_percentage", &self.opening_win_percentage)?;
+        state.serialize_field("trade_kills", &self.trade_kills)?;
+        state.serialize_field("traded_deaths", &self.traded_deaths)?;
+        state.serialize_field("trade_kill_percentage", &self.trade_kill_percentage)?;
+        state.serialize_field("traded_death_percentage", &self.traded_death_percentage)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::duels::*;
+
+    fn player(steamid: u64) -> PlayerKey {
+        PlayerKey { steamid, bot_name: None }
+    }
+
+    fn death(tick: i32, round: i32, victim: (u64, i32), attacker: (u64, i32)) -> Death {
+        Death {
+            tick,
+            round,
+            victim: player(victim.0),
+            victim_team: Some(victim.1),
+            attacker: Some(player(attacker.0)),
+            attacker_team: Some(attacker.1),
+        }
+    }
+
+    #[test]
+    fn test_find_trades() {
+        let deaths = vec![
+            // 1 kills 2, 3 trades 2 by killing 1
+            death(100, 1, (2, 3), (1, 2)),
+            death(150, 1, (1, 2), (3, 3)),
+            // 4 kills 5, 6 kills 4 too late
+            death(200, 1, (5, 3), (4, 2)),
+            death(600, 1, (4, 2), (6, 3)),
+            // 7 kills 8 at the end of a round and dies early in the next one
+            death(900, 1, (8, 3), (7, 2)),
+            death(950, 2, (7, 2), (9, 3)),
+            // 10 is killed by a teammate after killing 11
+            death(1200, 2, (11, 3), (10, 2)),
+            death(1210, 2, (10, 2), (12, 2)),
+        ];
+        assert_eq!(
+            find_trades(&deaths, 320),
+            vec![Some(1), None, None, None, None, None, None, None]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field_i32;
+use crate::analysis::sorted_events;
+use crate::analysis::RoundTimeline;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::collect_data::EconomyRecord;
+use crate::second_pass::game_events::GameEvent;
+use ahash::AHashMap;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const ECONOMY_EVENTS: &[&str] = &["round_start", "round_end", "money_adjusted"];
+// Asking for cash_earned_this_round in events turns on collecting the money at the end of buy time
+pub const ECONOMY_PLAYER_PROPS: &[&str] = &["team_num", "cash_earned_this_round"];
+pub const ECONOMY_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// Average equipment value per player below which the buy is an eco, semi-eco and force buy
+pub const DEFAULT_ECO_THRESHOLD: i32 = 1000;
+pub const DEFAULT_SEMI_ECO_THRESHOLD: i32 = 2000;
+pub const DEFAULT_FORCE_THRESHOLD: i32 = 4000;
+// Everyone starts the half with this, a round where nobody had more is a pistol round
+pub const PISTOL_ROUND_MONEY: i32 = 800;
+// Loss bonus is 1400 and grows by 500 for every lost round up to 3400
+pub const LOSS_BONUS_BASE: i32 = 1400;
+pub const LOSS_BONUS_STEP: i32 = 500;
+pub const MAX_LOSS_BONUS_LEVEL: i32 = 4;
+
+pub fn economy_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(
+        ECONOMY_EVENTS,
+        ECONOMY_PLAYER_PROPS,
+        ECONOMY_OTHER_PROPS,
+        huffman_lookup_table,
+    )?;
+    inputs.collect_economy = true;
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BuyThresholds {
+    pub eco: i32,
+    pub semi_eco: i32,
+    pub force: i32,
+}
+
+impl Default for BuyThresholds {
+    fn default() -> Self {
+        BuyThresholds {
+            eco: DEFAULT_ECO_THRESHOLD,
+            semi_eco: DEFAULT_SEMI_ECO_THRESHOLD,
+            force: DEFAULT_FORCE_THRESHOLD,
+        }
+    }
+}
+
+impl BuyThresholds {
+    /// "eco", "semi_eco", "force" or "full_buy" for the average equipment value per player.
+    pub fn classify(&self, avg_equipment_value: i32) -> &'static str {
+        if avg_equipment_value < self.eco {
+            "eco"
+        } else if avg_equipment_value < self.semi_eco {
+            "semi_eco"
+        } else if avg_equipment_value < self.force {
+            "force"
+        } else {
+            "full_buy"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TeamRoundEconomy {
+    pub round: i32,
+    // 2 = T, 3 = CT
+    pub team_num: i32,
+    pub players: i32,
+    // Summed over the players of the team
+    pub start_money: i32,
+    pub spent: i32,
+    pub saved: i32,
+    // Equipment kept from the previous round plus what was bought
+    pub equipment_value: i32,
+    // Money earned during the round, kills and the round reward
+    pub earned: i32,
+    // Sum of CS_UM_AdjustMoney, only in POV demos and only for the recording player
+    pub money_adjusted: i32,
+    pub loss_streak: Option<i32>,
+    pub loss_bonus_level: Option<i32>,
+    pub loss_bonus: Option<i32>,
+    // "pistol", "eco", "semi_eco", "force" or "full_buy"
+    pub buy_type: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Economy {
+    pub teams: Vec<TeamRoundEconomy>,
+    pub players: Vec<EconomyRecord>,
+}
+
+/// Sums the money of every player at the end of buy time into a row per team per round: the
+/// money the team started with, what it spent and saved, the equipment value it went into the
+/// round with and what it earned. The buy is classified from the average equipment value per
+/// player with the thresholds, rounds where nobody had more than PISTOL_ROUND_MONEY are pistol
+/// rounds. Loss bonus level comes from the losing streak of the team.
+/// Expects the events and economy records from economy_inputs().
+pub fn economy(events: &[GameEvent], records: &[EconomyRecord], thresholds: &BuyThresholds) -> Economy {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+    // Warmup has buy time too
+    let first_tick = rounds.round_starts.first().map(|(tick, _)| *tick).unwrap_or(0);
+    let players: Vec<EconomyRecord> = records.iter().filter(|r| r.tick >= first_tick).cloned().collect();
+
+    let mut adjusted: AHashMap<(i32, i32), i32> = AHashMap::default();
+    for event in events.iter().filter(|e| e.name == "money_adjusted" && e.tick >= first_tick) {
+        if let (Some(team), Some(amount)) = (field_i32(event, "user_team_num"), field_i32(event, "amount")) {
+            *adjusted.entry((rounds.buy_round_at(event.tick), team)).or_default() += amount;
+        }
+    }
+
+    // (stats, if every player started with at most pistol round money)
+    let mut per_team: BTreeMap<(i32, i32), (TeamRoundEconomy, bool)> = BTreeMap::default();
+    for record in &players {
+        let team_num = match record.team {
+            Some(team) if team == 2 || team == 3 => team as i32,
+            _ => continue,
+        };
+        let (entry, pistol) = per_team.entry((record.round, team_num)).or_insert_with(|| {
+            let team = TeamRoundEconomy {
+                round: record.round,
+                team_num,
+                money_adjusted: adjusted.get(&(record.round, team_num)).copied().unwrap_or(0),
+                ..Default::default()
+            };
+            (team, true)
+        });
+        let start_money = record.start_balance.unwrap_or(0);
+        let spent = record.spent.unwrap_or(0);
+        entry.players += 1;
+        entry.start_money += start_money;
+        entry.spent += spent;
+        entry.saved += start_money - spent;
+        entry.equipment_value += record.start_equip_value.unwrap_or(0) + spent;
+        entry.earned += record.earned.unwrap_or(0);
+        if entry.loss_streak.is_none() {
+            entry.loss_streak = record.loss_streak;
+        }
+        *pistol &= start_money <= PISTOL_ROUND_MONEY;
+    }
+    let teams = per_team
+        .into_values()
+        .map(|(mut team, pistol)| {
+            team.loss_bonus_level = team.loss_streak.map(|streak| streak.clamp(0, MAX_LOSS_BONUS_LEVEL));
+            team.loss_bonus = team.loss_bonus_level.map(|level| LOSS_BONUS_BASE + LOSS_BONUS_STEP * level);
+            team.buy_type = match pistol {
+                true => "pistol".to_string(),
+                false => thresholds.classify(team.equipment_value / team.players).to_string(),
+            };
+            team
+        })
+        .collect();
+    Economy { teams, players }
+}
+
+impl Serialize for TeamRoundEconomy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("TeamRoundEconomy", 13)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("team_num", &self.team_num)?;
+        state.serialize_field("players", &self.players)?;
+        state.serialize_field("start_money", &self.start_money)?;
+        state.serialize_field("spent", &self.spent)?;
+        state.serialize_field("saved", &self.saved)?;
+        state.serialize_field("equipment_value", &self.equipment_value)?;
+        state.serialize_field("earned", &self.earned)?;
+        state.serialize_field("money_adjusted", &self.money_adjusted)?;
+        state.serialize_field("loss_streak", &self.loss_streak)?;
+        state.serialize_field("loss_bonus_level", &self.loss_bonus_level)?;
+        state.serialize_field("loss_bonus", &self.loss_bonus)?;
+        state.serialize_field("buy_type", &self.buy_type)?;
+        state.end()
+    }
+}
+impl Serialize for Economy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Economy", 2)?;
+        state.serialize_field("teams", &self.teams)?;
+        state.serialize_field("players", &self.players)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::economy::*;
+
+    fn record(steamid: u64, team: u32, round: i32, start_balance: i32, spent: i32, start_equip_value: i32) -> EconomyRecord {
+        EconomyRecord {
+            steamid,
+            name: None,
+            team: Some(team),
+            round,
+            tick: round * 1000,
+            start_balance: Some(start_balance),
+            balance: Some(start_balance - spent),
+            spent: Some(spent),
+            start_equip_value: Some(start_equip_value),
+            equip_value: Some(start_equip_value + spent),
+            earned: None,
+            loss_streak: Some(round + 4),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let thresholds = BuyThresholds::default();
+        assert_eq!(thresholds.classify(0), "eco");
+        assert_eq!(thresholds.classify(999), "eco");
+        assert_eq!(thresholds.classify(1000), "semi_eco");
+        assert_eq!(thresholds.classify(3999), "force");
+        assert_eq!(thresholds.classify(4000), "full_buy");
+    }
+
+    #[test]
+    fn test_team_buy_types() {
+        let records = vec![
+            record(1, 2, 1, 800, 700, 200),
+            record(2, 2, 1, 800, 0, 200),
+            record(3, 3, 2, 5000, 4500, 200),
+            record(4, 3, 2, 4000, 3500, 1000),
+            record(1, 2, 2, 1900, 0, 1000),
+            record(2, 2, 2, 2400, 200, 800),
+        ];
+        let economy = economy(&[], &records, &BuyThresholds::default());
+        let buy_types: Vec<(i32, i32, &str)> = economy
+            .teams
+            .iter()
+            .map(|t| (t.round, t.team_num, t.buy_type.as_str()))
+            .collect();
+        assert_eq!(buy_types, vec![(1, 2, "pistol"), (2, 2, "semi_eco"), (2, 3, "full_buy")]);
+        let ct = &economy.teams[2];
+        assert_eq!(
+            (ct.start_money, ct.spent, ct.saved, ct.equipment_value),
+            (9000, 8000, 1000, 9200)
+        );
+        assert_eq!((ct.loss_bonus_level, ct.loss_bonus), (Some(4), Some(3400)));
+    }
+}
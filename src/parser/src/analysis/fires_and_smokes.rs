@@ -0,0 +1,277 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field_f32;
+use crate::analysis::field_i32;
+use crate::analysis::field_steamid;
+use crate::analysis::field_string;
+use crate::analysis::sorted_events;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::collect_data::InfernoRecord;
+use crate::second_pass::game_events::GameEvent;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const FIRES_AND_SMOKES_EVENTS: &[&str] = &[
+    "inferno_startburn",
+    "inferno_expire",
+    "smokegrenade_detonate",
+    "smokegrenade_expired",
+    "round_end",
+    "round_start",
+];
+pub const FIRES_AND_SMOKES_PLAYER_PROPS: &[&str] = &["team_num"];
+pub const FIRES_AND_SMOKES_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// Rough sizes of the volumes in game units, good enough for "stood in fire" / "through smoke"
+pub const FIRE_RADIUS: f32 = 60.0;
+pub const FIRE_HEIGHT: f32 = 80.0;
+pub const SMOKE_RADIUS: f32 = 144.0;
+
+pub fn fires_and_smokes_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(
+        FIRES_AND_SMOKES_EVENTS,
+        FIRES_AND_SMOKES_PLAYER_PROPS,
+        FIRES_AND_SMOKES_OTHER_PROPS,
+        huffman_lookup_table,
+    )?;
+    inputs.collect_infernos = true;
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone)]
+pub struct FireTick {
+    pub tick: i32,
+    pub points: Vec<[f32; 3]>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Fire {
+    pub unique_id: u64,
+    pub entity_id: i32,
+    pub thrower_steamid: Option<u64>,
+    pub thrower_name: Option<String>,
+    pub round: i32,
+    pub start_tick: i32,
+    pub end_tick: i32,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub z: Option<f32>,
+    // Burning points on every tick the fire was alive
+    pub timeline: Vec<FireTick>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Smoke {
+    pub entity_id: i32,
+    pub thrower_steamid: Option<u64>,
+    pub thrower_name: Option<String>,
+    pub thrower_team_num: Option<i32>,
+    pub round: i32,
+    pub start_tick: i32,
+    // None if the smoke was still up when the demo ended
+    pub end_tick: Option<i32>,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub duration: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FiresAndSmokes {
+    pub fires: Vec<Fire>,
+    pub smokes: Vec<Smoke>,
+}
+
+impl Fire {
+    /// Burning points at the given tick, empty if the fire was not alive.
+    pub fn points_at(&self, tick: i32) -> &[[f32; 3]] {
+        match self.timeline.binary_search_by_key(&tick, |t| t.tick) {
+            Ok(idx) => &self.timeline[idx].points,
+            Err(_) => &[],
+        }
+    }
+    /// True if the position is within FIRE_RADIUS of a burning point at the given tick.
+    pub fn is_burning_at(&self, tick: i32, pos: [f32; 3]) -> bool {
+        self.points_at(tick).iter().any(|p| {
+            let (dx, dy, dz) = (p[0] - pos[0], p[1] - pos[1], pos[2] - p[2]);
+            dx * dx + dy * dy <= FIRE_RADIUS * FIRE_RADIUS && (-FIRE_RADIUS..=FIRE_HEIGHT).contains(&dz)
+        })
+    }
+}
+
+impl Smoke {
+    pub fn is_active_at(&self, tick: i32) -> bool {
+        tick >= self.start_tick && self.end_tick.is_none_or(|end| tick <= end)
+    }
+    /// True if the line between the two positions goes through the smoke at the given tick.
+    /// The smoke is treated as a sphere of SMOKE_RADIUS around the detonation point.
+    pub fn blocks_line(&self, tick: i32, from: [f32; 3], to: [f32; 3]) -> bool {
+        if !self.is_active_at(tick) {
+            return false;
+        }
+        let center = [self.x, self.y, self.z];
+        let d = [to[0] - from[0], to[1] - from[1], to[2] - from[2]];
+        let f = [center[0] - from[0], center[1] - from[1], center[2] - from[2]];
+        let len_sq = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+        // Closest point on the segment to the center
+        let t = match len_sq > 0.0 {
+            true => ((f[0] * d[0] + f[1] * d[1] + f[2] * d[2]) / len_sq).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        let closest = [d[0] * t - f[0], d[1] * t - f[1], d[2] * t - f[2]];
+        closest[0] * closest[0] + closest[1] * closest[1] + closest[2] * closest[2] <= SMOKE_RADIUS * SMOKE_RADIUS
+    }
+}
+
+/// Groups the per tick inferno records into one entry per fire with its burning points on
+/// every tick, and pairs smokegrenade_detonate with smokegrenade_expired to get every smoke
+/// with its center and lifetime.
+/// Expects the events and infernos from fires_and_smokes_inputs().
+pub fn fires_and_smokes(events: &[GameEvent], infernos: &[InfernoRecord]) -> FiresAndSmokes {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+
+    let mut per_fire: BTreeMap<u64, Vec<&InfernoRecord>> = BTreeMap::default();
+    for record in infernos {
+        if let Some(unique_id) = record.unique_id {
+            per_fire.entry(unique_id).or_default().push(record);
+        }
+    }
+    let mut fires = vec![];
+    for (unique_id, mut records) in per_fire {
+        records.sort_by_key(|r| r.tick);
+        let first = records[0];
+        let last = records[records.len() - 1];
+        let startburn = events.iter().find(|e| {
+            e.name == "inferno_startburn" && field_i32(e, "entityid") == Some(first.entity_id) && e.tick <= first.tick + 1
+        });
+        let thrower = records.iter().rev().find(|r| r.steamid.is_some());
+        fires.push(Fire {
+            unique_id,
+            entity_id: first.entity_id,
+            thrower_steamid: thrower.and_then(|r| r.steamid),
+            thrower_name: thrower.and_then(|r| r.name.clone()),
+            round: rounds.round_at(first.tick),
+            start_tick: first.tick,
+            end_tick: last.tick,
+            x: startburn.and_then(|e| field_f32(e, "x")),
+            y: startburn.and_then(|e| field_f32(e, "y")),
+            z: startburn.and_then(|e| field_f32(e, "z")),
+            timeline: records
+                .iter()
+                .map(|r| FireTick {
+                    tick: r.tick,
+                    points: r.fire_positions.clone(),
+                })
+                .collect(),
+        });
+    }
+    fires.sort_by_key(|f| (f.start_tick, f.unique_id));
+
+    let mut smokes: Vec<Smoke> = vec![];
+    for event in &events {
+        let entity_id = match field_i32(event, "entityid") {
+            Some(id) => id,
+            None => continue,
+        };
+        match event.name.as_str() {
+            "smokegrenade_detonate" => {
+                let (x, y, z) = match (field_f32(event, "x"), field_f32(event, "y"), field_f32(event, "z")) {
+                    (Some(x), Some(y), Some(z)) => (x, y, z),
+                    _ => continue,
+                };
+                smokes.push(Smoke {
+                    entity_id,
+                    thrower_steamid: field_steamid(event, "user"),
+                    thrower_name: field_string(event, "user_name").map(|s| s.to_string()),
+                    thrower_team_num: field_i32(event, "user_team_num"),
+                    round: rounds.round_at(event.tick),
+                    start_tick: event.tick,
+                    x,
+                    y,
+                    z,
+                    ..Default::default()
+                });
+            }
+            "smokegrenade_expired" => {
+                // Entity ids get reused, the latest smoke with this id is the one expiring
+                if let Some(smoke) = smokes
+                    .iter_mut()
+                    .rev()
+                    .find(|s| s.entity_id == entity_id && s.end_tick.is_none())
+                {
+                    smoke.end_tick = Some(event.tick);
+                    smoke.duration = Some((event.tick - smoke.start_tick) as f32 / TICKRATE);
+                }
+            }
+            _ => {}
+        }
+    }
+    FiresAndSmokes { fires, smokes }
+}
+
+impl Serialize for FireTick {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("FireTick", 2)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("points", &self.points)?;
+        state.end()
+    }
+}
+impl Serialize for Fire {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Fire", 11)?;
+        state.serialize_field("unique_id", &self.unique_id)?;
+        state.serialize_field("entity_id", &self.entity_id)?;
+        state.serialize_field("thrower_steamid", &self.thrower_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("thrower_name", &self.thrower_name)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("start_tick", &self.start_tick)?;
+        state.serialize_field("end_tick", &self.end_tick)?;
+        state.serialize_field("x", &self.x)?;
+        state.serialize_field("y", &self.y)?;
+        state.serialize_field("z", &self.z)?;
+        state.serialize_field("timeline", &self.timeline)?;
+        state.end()
+    }
+}
+impl Serialize for Smoke {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Smoke", 11)?;
+        state.serialize_field("entity_id", &self.entity_id)?;
+        state.serialize_field("thrower_steamid", &self.thrower_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("thrower_name", &self.thrower_name)?;
+        state.serialize_field("thrower_team_num", &self.thrower_team_num)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("start_tick", &self.start_tick)?;
+        state.serialize_field("end_tick", &self.end_tick)?;
+        state.serialize_field("x", &self.x)?;
+        state.serialize_field("y", &self.y)?;
+        state.serialize_field("z", &self.z)?;
+        state.serialize_field("duration", &self.duration)?;
+        state.end()
+    }
+}
+impl Serialize for FiresAndSmokes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("FiresAndSmokes", 2)?;
+        state.serialize_field("fires", &self.fires)?;
+        state.serialize_field("smokes", &self.smokes)?;
+        state.end()
+    }
+}
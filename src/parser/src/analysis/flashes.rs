@@ -0,0 +1,380 @@
+use crate::analysis::duels::Death;
+use crate::analysis::event_inputs;
+use crate::analysis::field_f32;
+use crate::analysis::field_i32;
+use crate::analysis::field_steamid;
+use crate::analysis::field_string;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::collect_data::ProjectileRecord;
+use crate::second_pass::game_events::GameEvent;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const FLASHES_EVENTS: &[&str] = &[
+    "flashbang_detonate",
+    "player_blind",
+    "player_death",
+    "round_end",
+    "round_start",
+];
+pub const FLASHES_PLAYER_PROPS: &[&str] = &["team_num"];
+pub const FLASHES_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// Kills on a flashed player within this many seconds of the blind count as flash assists
+pub const DEFAULT_FLASH_ASSIST_WINDOW_SECONDS: f32 = 3.0;
+// player_blind comes in the same tick as the detonation, allow some slack anyway
+const BLIND_AFTER_DETONATE_TICKS: i32 = 8;
+
+pub fn flashes_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(
+        FLASHES_EVENTS,
+        FLASHES_PLAYER_PROPS,
+        FLASHES_OTHER_PROPS,
+        huffman_lookup_table,
+    )?;
+    // Needed for the thrower when the detonate event can't resolve it
+    inputs.parse_projectiles = true;
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FlashRecord {
+    pub entity_id: i32,
+    pub round: i32,
+    pub tick: i32,
+    pub thrower_steamid: Option<u64>,
+    pub thrower_name: Option<String>,
+    pub thrower_team_num: Option<i32>,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub z: Option<f32>,
+    pub enemies_blinded: i32,
+    pub enemies_blind_duration: f32,
+    pub teammates_blinded: i32,
+    pub teammates_blind_duration: f32,
+    pub self_blind_duration: f32,
+    pub flash_assists: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerFlashStats {
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub flashes_thrown: i32,
+    pub enemies_blinded: i32,
+    pub enemies_blind_duration: f32,
+    pub avg_enemy_blind_duration: f32,
+    pub teammates_blinded: i32,
+    pub teammates_blind_duration: f32,
+    pub flash_assists: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Flashes {
+    pub flashes: Vec<FlashRecord>,
+    pub players: Vec<PlayerFlashStats>,
+}
+
+struct Blind {
+    tick: i32,
+    round: i32,
+    victim: PlayerKey,
+    victim_team: Option<i32>,
+    duration: f32,
+}
+
+/// Attributes every player_blind to the flashbang that caused it and sums up enemies and
+/// teammates blinded per flash. The thrower's team comes from the projectile when the event
+/// doesn't have it, blinds where a team is still unknown are left out of both counts. Kills by
+/// the thrower's team on players still within flash_assist_window_seconds of being blinded count
+/// as flash assists.
+/// Expects the events and projectiles from flashes_inputs().
+pub fn flashes(events: &[GameEvent], projectiles: &[ProjectileRecord], flash_assist_window_seconds: f32) -> Flashes {
+    let rounds = RoundTimeline::from_events(events);
+    let window = (flash_assist_window_seconds * TICKRATE) as i32;
+    let mut events = sorted_events(events);
+    // Blinds are matched to an earlier detonation so make sure it comes first within a tick
+    events.sort_by_key(|e| (e.tick, e.name != "flashbang_detonate"));
+
+    let mut flashes: Vec<FlashRecord> = vec![];
+    let mut blinds: Vec<Vec<Blind>> = vec![];
+    let mut thrower_keys: Vec<Option<PlayerKey>> = vec![];
+
+    for event in &events {
+        let entity_id = match field_i32(event, "entityid") {
+            Some(id) => id,
+            None => continue,
+        };
+        match event.name.as_str() {
+            "flashbang_detonate" => {
+                let projectile = last_projectile_record(projectiles, entity_id, event.tick);
+                let (steamid, name) = match field_steamid(event, "user") {
+                    Some(steamid) => (Some(steamid), field_string(event, "user_name").map(|s| s.to_string())),
+                    None => (projectile.and_then(|p| p.steamid), projectile.and_then(|p| p.name.clone())),
+                };
+                flashes.push(FlashRecord {
+                    entity_id,
+                    round: rounds.round_at(event.tick),
+                    tick: event.tick,
+                    thrower_steamid: steamid,
+                    thrower_name: name,
+                    thrower_team_num: field_i32(event, "user_team_num").or(projectile.and_then(|p| p.team_num)),
+                    x: field_f32(event, "x"),
+                    y: field_f32(event, "y"),
+                    z: field_f32(event, "z"),
+                    ..Default::default()
+                });
+                blinds.push(vec![]);
+                thrower_keys.push(PlayerKey::from_event(event, "user"));
+            }
+            "player_blind" => {
+                let victim = match PlayerKey::from_event(event, "user") {
+                    Some(v) => v,
+                    None => continue,
+                };
+                // Entity ids get reused so pick the latest detonation of this entity
+                let idx = flashes
+                    .iter()
+                    .rposition(|f| f.entity_id == entity_id && event.tick - f.tick <= BLIND_AFTER_DETONATE_TICKS);
+                let idx = match idx {
+                    Some(idx) => idx,
+                    None => {
+                        // No detonate event, fall back to the attacker in the blind event
+                        let projectile = last_projectile_record(projectiles, entity_id, event.tick);
+                        let (steamid, name) = match field_steamid(event, "attacker") {
+                            Some(steamid) => (Some(steamid), field_string(event, "attacker_name").map(|s| s.to_string())),
+                            None => (projectile.and_then(|p| p.steamid), projectile.and_then(|p| p.name.clone())),
+                        };
+                        flashes.push(FlashRecord {
+                            entity_id,
+                            round: rounds.round_at(event.tick),
+                            tick: event.tick,
+                            thrower_steamid: steamid,
+                            thrower_name: name,
+                            thrower_team_num: field_i32(event, "attacker_team_num").or(projectile.and_then(|p| p.team_num)),
+                            ..Default::default()
+                        });
+                        blinds.push(vec![]);
+                        thrower_keys.push(PlayerKey::from_event(event, "attacker"));
+                        flashes.len() - 1
+                    }
+                };
+                blinds[idx].push(Blind {
+                    tick: event.tick,
+                    round: rounds.round_at(event.tick),
+                    victim,
+                    victim_team: field_i32(event, "user_team_num"),
+                    duration: field_f32(event, "blind_duration").unwrap_or(0.0),
+                });
+            }
+            _ => {}
+        }
+    }
+    let deaths: Vec<Death> = events
+        .iter()
+        .filter(|e| e.name == "player_death")
+        .filter_map(|e| Death::from_event(e, rounds.round_at(e.tick)))
+        .collect();
+
+    for ((flash, flash_blinds), thrower) in flashes.iter_mut().zip(&blinds).zip(&thrower_keys) {
+        for blind in flash_blinds {
+            // Without both teams the blind is neither an enemy nor a teammate
+            let teams = match (flash.thrower_team_num, blind.victim_team) {
+                (Some(thrower_team), Some(victim_team)) => Some((thrower_team, victim_team)),
+                _ => None,
+            };
+            if thrower.as_ref() == Some(&blind.victim) {
+                flash.self_blind_duration += blind.duration;
+            } else if teams.is_some_and(|(thrower_team, victim_team)| thrower_team != victim_team) {
+                flash.enemies_blinded += 1;
+                flash.enemies_blind_duration += blind.duration;
+                let assisted = deaths.iter().any(|d| {
+                    d.victim == blind.victim
+                        && d.round == blind.round
+                        && d.tick >= blind.tick
+                        && d.tick - blind.tick <= window
+                        && d.is_enemy_kill()
+                        && d.attacker_team == flash.thrower_team_num
+                });
+                if assisted {
+                    flash.flash_assists += 1;
+                }
+            } else if teams.is_some() {
+                flash.teammates_blinded += 1;
+                flash.teammates_blind_duration += blind.duration;
+            }
+        }
+    }
+
+    let mut players: BTreeMap<u64, PlayerFlashStats> = BTreeMap::default();
+    for flash in &flashes {
+        let steamid = match flash.thrower_steamid {
+            Some(s) => s,
+            None => continue,
+        };
+        let stats = players.entry(steamid).or_insert_with(|| PlayerFlashStats {
+            steamid,
+            ..Default::default()
+        });
+        if flash.thrower_name.is_some() {
+            stats.name = flash.thrower_name.clone();
+        }
+        stats.flashes_thrown += 1;
+        stats.enemies_blinded += flash.enemies_blinded;
+        stats.enemies_blind_duration += flash.enemies_blind_duration;
+        stats.teammates_blinded += flash.teammates_blinded;
+        stats.teammates_blind_duration += flash.teammates_blind_duration;
+        stats.flash_assists += flash.flash_assists;
+    }
+    for stats in players.values_mut() {
+        if stats.enemies_blinded > 0 {
+            stats.avg_enemy_blind_duration = stats.enemies_blind_duration / stats.enemies_blinded as f32;
+        }
+    }
+    Flashes {
+        flashes,
+        players: players.into_values().collect(),
+    }
+}
+
+// The projectile entity is gone by the time of the event so use the last record of it
+fn last_projectile_record(projectiles: &[ProjectileRecord], entity_id: i32, tick: i32) -> Option<&ProjectileRecord> {
+    projectiles
+        .iter()
+        .filter(|p| p.entity_id == Some(entity_id) && p.tick.is_some_and(|t| t <= tick))
+        .max_by_key(|p| p.tick)
+}
+
+impl Serialize for FlashRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("FlashRecord", 15)?;
+        state.serialize_field("entity_id", &self.entity_id)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("thrower_steamid", &self.thrower_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("thrower_name", &self.thrower_name)?;
+        state.serialize_field("thrower_team_num", &self.thrower_team_num)?;
+        state.serialize_field("x", &self.x)?;
+        state.serialize_field("y", &self.y)?;
+        state.serialize_field("z", &self.z)?;
+        state.serialize_field("enemies_blinded", &self.enemies_blinded)?;
+        state.serialize_field("enemies_blind_duration", &self.enemies_blind_duration)?;
+        state.serialize_field("teammates_blinded", &self.teammates_blinded)?;
+        state.serialize_field("teammates_blind_duration", &self.teammates_blind_duration)?;
+        state.serialize_field("self_blind_duration", &self.self_blind_duration)?;
+        state.serialize_field("flash_assists", &self.flash_assists)?;
+        state.end()
+    }
+}
+impl Serialize for PlayerFlashStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PlayerFlashStats", 9)?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("flashes_thrown", &self.flashes_thrown)?;
+        state.serialize_field("enemies_blinded", &self.enemies_blinded)?;
+        state.serialize_field("enemies_blind_duration", &self.enemies_blind_duration)?;
+        state.serialize_field("avg_enemy_blind_duration", &self.avg_enemy_blind_duration)?;
+        state.serialize_field("teammates_blinded", &self.teammates_blinded)?;
+        state.serialize_field("teammates_blind_duration", &self.teammates_blind_duration)?;
+        state.serialize_field("flash_assists", &self.flash_assists)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::flashes::*;
+    use crate::analysis::test_event;
+    use crate::second_pass::variants::Variant;
+
+    #[test]
+    fn test_blind_before_detonate_in_same_tick() {
+        let events = vec![
+            test_event(
+                "player_blind",
+                100,
+                vec![
+                    ("entityid", Variant::I32(50)),
+                    ("user_steamid", Variant::String("2".to_string())),
+                    ("user_team_num", Variant::I32(3)),
+                    ("blind_duration", Variant::F32(2.5)),
+                ],
+            ),
+            test_event(
+                "flashbang_detonate",
+                100,
+                vec![
+                    ("entityid", Variant::I32(50)),
+                    ("user_steamid", Variant::String("1".to_string())),
+                    ("user_team_num", Variant::I32(2)),
+                ],
+            ),
+        ];
+        let out = flashes(&events, &[], DEFAULT_FLASH_ASSIST_WINDOW_SECONDS);
+        assert_eq!(out.flashes.len(), 1);
+        assert_eq!(out.flashes[0].thrower_steamid, Some(1));
+        assert_eq!(out.flashes[0].enemies_blinded, 1);
+        assert_eq!(out.flashes[0].enemies_blind_duration, 2.5);
+    }
+
+    #[test]
+    fn test_thrower_team_from_projectile() {
+        let blind = |tick: i32, entity_id: i32| {
+            test_event(
+                "player_blind",
+                tick,
+                vec![
+                    ("entityid", Variant::I32(entity_id)),
+                    ("user_steamid", Variant::String("2".to_string())),
+                    ("user_team_num", Variant::I32(3)),
+                    ("blind_duration", Variant::F32(2.0)),
+                ],
+            )
+        };
+        // The thrower disconnected, the events can't resolve them
+        let events = vec![
+            test_event("flashbang_detonate", 100, vec![("entityid", Variant::I32(50))]),
+            blind(100, 50),
+            test_event("flashbang_detonate", 200, vec![("entityid", Variant::I32(60))]),
+            blind(200, 60),
+        ];
+        let projectile = ProjectileRecord {
+            steamid: Some(1),
+            name: Some("player1".to_string()),
+            team_num: Some(2),
+            x: None,
+            y: None,
+            z: None,
+            tick: Some(90),
+            grenade_type: Some("flashbang".to_string()),
+            entity_id: Some(50),
+            unique_id: None,
+            bounces: None,
+            radar_x: None,
+            radar_y: None,
+            radar_level: None,
+            zone: None,
+        };
+        let out = flashes(&events, &[projectile], DEFAULT_FLASH_ASSIST_WINDOW_SECONDS);
+        assert_eq!(out.flashes.len(), 2);
+        assert_eq!(out.flashes[0].thrower_steamid, Some(1));
+        assert_eq!(out.flashes[0].thrower_team_num, Some(2));
+        assert_eq!((out.flashes[0].enemies_blinded, out.flashes[0].teammates_blinded), (1, 0));
+        // No projectile record either, the blind can't be counted as enemy or teammate
+        assert_eq!(out.flashes[1].thrower_team_num, None);
+        assert_eq!((out.flashes[1].enemies_blinded, out.flashes[1].teammates_blinded), (0, 0));
+    }
+}
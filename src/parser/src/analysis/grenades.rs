@@ -0,0 +1,273 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field_f32;
+use crate::analysis::field_i32;
+use crate::analysis::sorted_events;
+use crate::analysis::RoundTimeline;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::collect_data::ProjectileRecord;
+use crate::second_pass::game_events::GameEvent;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const GRENADE_THROWS_EVENTS: &[&str] = &[
+    "decoy_detonate",
+    "flashbang_detonate",
+    "hegrenade_detonate",
+    "molotov_detonate",
+    "smokegrenade_detonate",
+    "round_end",
+    "round_start",
+];
+pub const GRENADE_THROWS_PLAYER_PROPS: &[&str] = &["team_num"];
+pub const GRENADE_THROWS_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// The projectile is usually removed in the same tick as the detonate event fires
+const DETONATE_AFTER_LAST_SEEN_TICKS: i32 = 4;
+
+pub fn grenade_throws_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(
+        GRENADE_THROWS_EVENTS,
+        GRENADE_THROWS_PLAYER_PROPS,
+        GRENADE_THROWS_OTHER_PROPS,
+        huffman_lookup_table,
+    )?;
+    inputs.parse_projectiles = true;
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone)]
+pub struct PathPoint {
+    pub tick: i32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GrenadeThrow {
+    pub unique_id: u64,
+    pub entity_id: i32,
+    pub grenade_type: Option<String>,
+    pub thrower_steamid: Option<u64>,
+    pub thrower_name: Option<String>,
+    pub round: i32,
+    pub throw_tick: i32,
+    pub throw_x: Option<f32>,
+    pub throw_y: Option<f32>,
+    pub throw_z: Option<f32>,
+    pub path: Vec<PathPoint>,
+    pub bounces: i32,
+    pub detonate_tick: Option<i32>,
+    pub detonate_x: Option<f32>,
+    pub detonate_y: Option<f32>,
+    pub detonate_z: Option<f32>,
+    // Zone the grenade was in when it detonated (or was last seen), needs zones to be given
+    pub detonate_zone: Option<String>,
+    pub detonate_event: Option<GameEvent>,
+}
+
+fn detonate_event_name(grenade_type: &str) -> Option<&'static str> {
+    match grenade_type {
+        "decoy" => Some("decoy_detonate"),
+        "flashbang" => Some("flashbang_detonate"),
+        "he_grenade" => Some("hegrenade_detonate"),
+        "molotov" | "incendiary_grenade" => Some("molotov_detonate"),
+        "smoke" => Some("smokegrenade_detonate"),
+        _ => None,
+    }
+}
+
+/// Groups the per tick projectile records into one row per thrown grenade and attaches the
+/// matching *_detonate event. Grenades that never detonated (e.g. the demo ended or the
+/// round restarted) have no detonate_tick.
+/// Expects the events and projectiles from grenade_throws_inputs().
+pub fn grenade_throws(events: &[GameEvent], projectiles: &[ProjectileRecord]) -> Vec<GrenadeThrow> {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+
+    let mut per_throw: BTreeMap<u64, Vec<&ProjectileRecord>> = BTreeMap::default();
+    for record in projectiles {
+        if let (Some(unique_id), Some(_)) = (record.unique_id, record.tick) {
+            per_throw.entry(unique_id).or_default().push(record);
+        }
+    }
+    let mut throws = vec![];
+    for (unique_id, mut records) in per_throw {
+        records.sort_by_key(|r| r.tick);
+        let first = records[0];
+        let last = records[records.len() - 1];
+        let (first_tick, last_tick) = (first.tick.unwrap_or(0), last.tick.unwrap_or(0));
+        let entity_id = first.entity_id.unwrap_or(-1);
+
+        let path: Vec<PathPoint> = records
+            .iter()
+            .filter_map(|r| match (r.tick, r.x, r.y, r.z) {
+                (Some(tick), Some(x), Some(y), Some(z)) => Some(PathPoint { tick, x, y, z }),
+                _ => None,
+            })
+            .collect();
+        let event_name = first.grenade_type.as_deref().and_then(detonate_event_name);
+        let detonate_event = events.iter().find(|e| {
+            Some(e.name.as_str()) == event_name
+                && field_i32(e, "entityid") == Some(entity_id)
+                && e.tick >= first_tick
+                && e.tick <= last_tick + DETONATE_AFTER_LAST_SEEN_TICKS
+        });
+        let (detonate_x, detonate_y, detonate_z) = match detonate_event {
+            Some(e) => (field_f32(e, "x"), field_f32(e, "y"), field_f32(e, "z")),
+            None => (None, None, None),
+        };
+        // Smokes and decoys stay around after detonating, cut the path there
+        let path: Vec<PathPoint> = match detonate_event {
+            Some(e) => path.into_iter().filter(|p| p.tick <= e.tick).collect(),
+            None => path,
+        };
+        let detonate_zone = records
+            .iter()
+            .rev()
+            .find(|r| detonate_event.is_none_or(|e| r.tick.is_some_and(|t| t <= e.tick)))
+            .and_then(|r| r.zone.clone());
+        let bounces = records.iter().filter_map(|r| r.bounces).max().unwrap_or(0) as i32;
+        // Use the last known thrower, the owner is not always set on the first tick
+        let thrower = records.iter().rev().find(|r| r.steamid.is_some());
+
+        throws.push(GrenadeThrow {
+            unique_id,
+            entity_id,
+            grenade_type: first.grenade_type.clone(),
+            thrower_steamid: thrower.and_then(|r| r.steamid),
+            thrower_name: thrower.and_then(|r| r.name.clone()),
+            round: rounds.round_at(first_tick),
+            throw_tick: first_tick,
+            throw_x: first.x,
+            throw_y: first.y,
+            throw_z: first.z,
+            path,
+            bounces,
+            detonate_tick: detonate_event.map(|e| e.tick),
+            detonate_x,
+            detonate_y,
+            detonate_z,
+            detonate_zone,
+            detonate_event: detonate_event.map(|e| (*e).clone()),
+        });
+    }
+    throws.sort_by_key(|t| (t.throw_tick, t.unique_id));
+    throws
+}
+
+impl Serialize for PathPoint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PathPoint", 4)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("x", &self.x)?;
+        state.serialize_field("y", &self.y)?;
+        state.serialize_field("z", &self.z)?;
+        state.end()
+    }
+}
+impl Serialize for GrenadeThrow {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("GrenadeThrow", 18)?;
+        state.serialize_field("unique_id", &self.unique_id)?;
+        state.serialize_field("entity_id", &self.entity_id)?;
+        state.serialize_field("grenade_type", &self.grenade_type)?;
+        state.serialize_field("thrower_steamid", &self.thrower_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("thrower_name", &self.thrower_name)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("throw_tick", &self.throw_tick)?;
+        state.serialize_field("throw_x", &self.throw_x)?;
+        state.serialize_field("throw_y", &self.throw_y)?;
+        state.serialize_field("throw_z", &self.throw_z)?;
+        state.serialize_field("path", &self.path)?;
+        state.serialize_field("bounces", &self.bounces)?;
+        state.serialize_field("detonate_tick", &self.detonate_tick)?;
+        state.serialize_field("detonate_x", &self.detonate_x)?;
+        state.serialize_field("detonate_y", &self.detonate_y)?;
+        state.serialize_field("detonate_z", &self.detonate_z)?;
+        state.serialize_field("detonate_zone", &self.detonate_zone)?;
+        state.serialize_field("detonate_event", &self.detonate_event)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::grenades::*;
+    use crate::analysis::test_event;
+    use crate::second_pass::variants::Variant;
+
+    fn record(unique_id: u64, tick: i32, bounces: u32, steamid: Option<u64>) -> ProjectileRecord {
+        ProjectileRecord {
+            steamid,
+            name: steamid.map(|s| format!("player{}", s)),
+            team_num: Some(2),
+            x: Some(tick as f32),
+            y: Some(0.0),
+            z: Some(0.0),
+            tick: Some(tick),
+            grenade_type: Some("smoke".to_string()),
+            entity_id: Some(80),
+            unique_id: Some(unique_id),
+            bounces: Some(bounces),
+            radar_x: None,
+            radar_y: None,
+            radar_level: None,
+            zone: None,
+        }
+    }
+
+    #[test]
+    fn test_grenade_throws() {
+        let events = vec![
+            test_event("round_start", 0, vec![("round", Variant::I32(1))]),
+            test_event(
+                "smokegrenade_detonate",
+                110,
+                vec![
+                    ("entityid", Variant::I32(80)),
+                    ("x", Variant::F32(110.0)),
+                    ("y", Variant::F32(0.0)),
+                    ("z", Variant::F32(0.0)),
+                ],
+            ),
+        ];
+        // Same entity id for both smokes, the second one got a new serial
+        let first_smoke = (1 << 14) | 80;
+        let second_smoke = (2 << 14) | 80;
+        let projectiles = vec![
+            record(first_smoke, 105, 1, Some(1)),
+            // The owner is not set yet on the first tick
+            record(first_smoke, 100, 0, None),
+            record(first_smoke, 110, 2, Some(1)),
+            // Smoke stays around after detonating
+            record(first_smoke, 115, 2, Some(1)),
+            record(second_smoke, 500, 0, Some(2)),
+            record(second_smoke, 505, 0, Some(2)),
+        ];
+        let throws = grenade_throws(&events, &projectiles);
+        assert_eq!(throws.len(), 2);
+
+        let first = &throws[0];
+        assert_eq!((first.unique_id, first.entity_id), (first_smoke, 80));
+        assert_eq!(first.thrower_steamid, Some(1));
+        assert_eq!(first.throw_tick, 100);
+        assert_eq!(first.path.iter().map(|p| p.tick).collect::<Vec<i32>>(), vec![100, 105, 110]);
+        assert_eq!(first.bounces, 2);
+        assert_eq!((first.detonate_tick, first.detonate_x), (Some(110), Some(110.0)));
+
+        let second = &throws[1];
+        assert_eq!(second.unique_id, second_smoke);
+        assert_eq!(second.thrower_steamid, Some(2));
+        assert_eq!(second.path.len(), 2);
+        assert_eq!(second.detonate_tick, None);
+    }
+}
@@ -0,0 +1,380 @@
+use crate::analysis::duels::Death;
+use crate::analysis::event_inputs;
+use crate::analysis::field_bool;
+use crate::analysis::field_i32;
+use crate::analysis::field_string;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::game_events::GameEvent;
+use ahash::AHashMap;
+use ahash::AHashSet;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const HIGHLIGHTS_EVENTS: &[&str] = &[
+    "player_death",
+    "player_spawn",
+    "player_disconnect",
+    "round_end",
+    "round_start",
+];
+pub const HIGHLIGHTS_PLAYER_PROPS: &[&str] = &["team_num"];
+pub const HIGHLIGHTS_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// Seconds added before and after the action so clips don't start mid-fight
+pub const DEFAULT_HIGHLIGHT_PADDING_SECONDS: f32 = 3.0;
+
+// These can kill multiple players in one tick without being a collateral
+const GRENADE_WEAPONS: &[&str] = &["hegrenade", "inferno", "molotov", "incgrenade", "planted_c4"];
+
+pub fn highlights_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    event_inputs(
+        HIGHLIGHTS_EVENTS,
+        HIGHLIGHTS_PLAYER_PROPS,
+        HIGHLIGHTS_OTHER_PROPS,
+        huffman_lookup_table,
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HighlightType {
+    ThreeK,
+    FourK,
+    Ace,
+    ClutchWon,
+    ClutchLost,
+    KnifeKill,
+    NoscopeKill,
+    Collateral,
+}
+
+impl HighlightType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HighlightType::ThreeK => "3k",
+            HighlightType::FourK => "4k",
+            HighlightType::Ace => "ace",
+            HighlightType::ClutchWon => "clutch_won",
+            HighlightType::ClutchLost => "clutch_lost",
+            HighlightType::KnifeKill => "knife_kill",
+            HighlightType::NoscopeKill => "noscope_kill",
+            HighlightType::Collateral => "collateral",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Highlight {
+    pub highlight_type: HighlightType,
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub round: i32,
+    pub start_tick: i32,
+    pub end_tick: i32,
+    // Kills by the player during the highlight
+    pub kills: i32,
+    // X in 1vX, only set for clutches
+    pub opponents: Option<i32>,
+    pub weapon: Option<String>,
+}
+
+struct Clutch {
+    player: PlayerKey,
+    team: i32,
+    opponents: i32,
+    start_tick: i32,
+    kills: i32,
+}
+
+/// Finds multikills, clutches, knife and noscope kills and collaterals. Every highlight has a
+/// [start_tick, end_tick] window padded by padding_seconds on both sides.
+/// Expects the events from highlights_inputs().
+pub fn highlights(events: &[GameEvent], padding_seconds: f32) -> Vec<Highlight> {
+    let rounds = RoundTimeline::from_events(events);
+    let padding = (padding_seconds * TICKRATE) as i32;
+    let mut highlights = vec![];
+
+    // round -> team -> players alive
+    let mut alive: BTreeMap<i32, BTreeMap<i32, AHashSet<PlayerKey>>> = BTreeMap::default();
+    let mut clutches: BTreeMap<i32, Vec<Clutch>> = BTreeMap::default();
+    // (round, attacker) -> kill events
+    let mut kills: BTreeMap<(i32, PlayerKey), Vec<&GameEvent>> = BTreeMap::default();
+
+    let mut names: AHashMap<PlayerKey, String> = AHashMap::default();
+
+    for event in sorted_events(events) {
+        let round = rounds.round_at(event.tick);
+        for prefix in ["user", "attacker"] {
+            if let (Some(player), Some(name)) = (
+                PlayerKey::from_event(event, prefix),
+                field_string(event, &(prefix.to_owned() + "_name")),
+            ) {
+                names.insert(player, name.to_string());
+            }
+        }
+        match event.name.as_str() {
+            "player_spawn" => {
+                let team = match field_i32(event, "user_team_num") {
+                    Some(t) if t == 2 || t == 3 => t,
+                    _ => continue,
+                };
+                if let Some(player) = PlayerKey::from_event(event, "user") {
+                    // Players spawn at the start of the freeze period of the next round
+                    let teams = alive.entry(rounds.buy_round_at(event.tick)).or_default();
+                    // Team switches
+                    for players in teams.values_mut() {
+                        players.remove(&player);
+                    }
+                    teams.entry(team).or_default().insert(player);
+                }
+            }
+            "player_disconnect" => {
+                if let (Some(player), Some(teams)) = (PlayerKey::from_event(event, "user"), alive.get_mut(&round)) {
+                    for players in teams.values_mut() {
+                        players.remove(&player);
+                    }
+                }
+            }
+            "player_death" => {
+                let death = match Death::from_event(event, round) {
+                    Some(d) => d,
+                    None => continue,
+                };
+                if let Some(teams) = alive.get_mut(&round) {
+                    for players in teams.values_mut() {
+                        players.remove(&death.victim);
+                    }
+                }
+                if let (Some(attacker), true) = (&death.attacker, death.is_enemy_kill()) {
+                    kills.entry((round, attacker.clone())).or_default().push(event);
+                    for clutch in clutches.entry(round).or_default() {
+                        if &clutch.player == attacker {
+                            clutch.kills += 1;
+                        }
+                    }
+                    highlights.extend(single_kill_highlights(event, attacker, round, padding));
+                }
+                if let Some(teams) = alive.get(&round) {
+                    let round_clutches = clutches.entry(round).or_default();
+                    for (team, players) in teams {
+                        let opponents = teams.iter().filter(|(t, _)| *t != team).map(|(_, p)| p.len() as i32).sum();
+                        // Both teams can end up in a clutch, the second one being a 1v1
+                        let in_clutch = round_clutches.iter().any(|c| c.team == *team);
+                        if players.len() != 1 || opponents == 0 || in_clutch {
+                            continue;
+                        }
+                        if let Some(player) = players.iter().next() {
+                            round_clutches.push(Clutch {
+                                player: player.clone(),
+                                team: *team,
+                                opponents,
+                                start_tick: event.tick,
+                                kills: 0,
+                            });
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    for ((round, player), round_kills) in &kills {
+        let highlight_type = match round_kills.len() {
+            3 => HighlightType::ThreeK,
+            4 => HighlightType::FourK,
+            n if n >= 5 => HighlightType::Ace,
+            _ => continue,
+        };
+        let first = round_kills[0];
+        let last = round_kills[round_kills.len() - 1];
+        highlights.push(Highlight {
+            highlight_type,
+            steamid: player.steamid,
+            name: field_string(last, "attacker_name").map(|s| s.to_string()),
+            round: *round,
+            start_tick: (first.tick - padding).max(0),
+            end_tick: last.tick + padding,
+            kills: round_kills.len() as i32,
+            opponents: None,
+            weapon: None,
+        });
+    }
+    for (round, round_clutches) in clutches {
+        // Can't know the outcome of a round that never ended
+        let end_tick = match rounds.round_end_tick(round) {
+            Some(tick) => tick,
+            None => continue,
+        };
+        for clutch in round_clutches {
+            let highlight_type = match rounds.winners.get(&round) {
+                Some(winner) if *winner == clutch.team => HighlightType::ClutchWon,
+                _ => HighlightType::ClutchLost,
+            };
+            highlights.push(Highlight {
+                highlight_type,
+                steamid: clutch.player.steamid,
+                name: names.get(&clutch.player).cloned(),
+                round,
+                start_tick: (clutch.start_tick - padding).max(0),
+                end_tick: end_tick + padding,
+                kills: clutch.kills,
+                opponents: Some(clutch.opponents),
+                weapon: None,
+            });
+        }
+    }
+    highlights.extend(collaterals(&kills, padding));
+    highlights.sort_by_key(|h| (h.round, h.start_tick));
+    highlights
+}
+
+fn single_kill_highlights(event: &GameEvent, attacker: &PlayerKey, round: i32, padding: i32) -> Vec<Highlight> {
+    let weapon = field_string(event, "weapon").unwrap_or("");
+    let mut types = vec![];
+    if weapon.contains("knife") || weapon.contains("bayonet") {
+        types.push(HighlightType::KnifeKill);
+    }
+    if field_bool(event, "noscope") == Some(true) {
+        types.push(HighlightType::NoscopeKill);
+    }
+    types
+        .into_iter()
+        .map(|highlight_type| Highlight {
+            highlight_type,
+            steamid: attacker.steamid,
+            name: field_string(event, "attacker_name").map(|s| s.to_string()),
+            round,
+            start_tick: (event.tick - padding).max(0),
+            end_tick: event.tick + padding,
+            kills: 1,
+            opponents: None,
+            weapon: Some(weapon.to_string()),
+        })
+        .collect()
+}
+
+// Multiple kills by one bullet end up as deaths on the same tick with the same weapon
+fn collaterals(kills: &BTreeMap<(i32, PlayerKey), Vec<&GameEvent>>, padding: i32) -> Vec<Highlight> {
+    let mut highlights = vec![];
+    for ((round, player), round_kills) in kills {
+        let mut same_tick: BTreeMap<(i32, &str), i32> = BTreeMap::default();
+        for kill in round_kills {
+            let weapon = field_string(kill, "weapon").unwrap_or("");
+            if GRENADE_WEAPONS.contains(&weapon) {
+                continue;
+            }
+            *same_tick.entry((kill.tick, weapon)).or_insert(0) += 1;
+        }
+        for ((tick, weapon), n_kills) in same_tick {
+            if n_kills < 2 {
+                continue;
+            }
+            highlights.push(Highlight {
+                highlight_type: HighlightType::Collateral,
+                steamid: player.steamid,
+                name: round_kills
+                    .iter()
+                    .find_map(|k| field_string(k, "attacker_name"))
+                    .map(|s| s.to_string()),
+                round: *round,
+                start_tick: (tick - padding).max(0),
+                end_tick: tick + padding,
+                kills: n_kills,
+                opponents: None,
+                weapon: Some(weapon.to_string()),
+            });
+        }
+    }
+    highlights
+}
+
+impl Serialize for Highlight {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Highlight", 9)?;
+        state.serialize_field("highlight_type", self.highlight_type.as_str())?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("start_tick", &self.start_tick)?;
+        state.serialize_field("end_tick", &self.end_tick)?;
+        state.serialize_field("kills", &self.kills)?;
+        state.serialize_field("opponents", &self.opponents)?;
+        state.serialize_field("weapon", &self.weapon)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::highlights::*;
+    use crate::analysis::test_event;
+    use crate::second_pass::variants::Variant;
+
+    fn spawn(steamid: &str, team: i32) -> GameEvent {
+        test_event(
+            "player_spawn",
+            10,
+            vec![
+                ("user_steamid", Variant::String(steamid.to_string())),
+                ("user_team_num", Variant::I32(team)),
+            ],
+        )
+    }
+
+    fn death(tick: i32, victim: (&str, i32), attacker: (&str, i32)) -> GameEvent {
+        test_event(
+            "player_death",
+            tick,
+            vec![
+                ("user_steamid", Variant::String(victim.0.to_string())),
+                ("user_team_num", Variant::I32(victim.1)),
+                ("attacker_steamid", Variant::String(attacker.0.to_string())),
+                ("attacker_team_num", Variant::I32(attacker.1)),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_clutch_per_team() {
+        let events = vec![
+            spawn("1", 2),
+            spawn("2", 2),
+            spawn("3", 3),
+            spawn("4", 3),
+            spawn("5", 3),
+            test_event("round_start", 20, vec![("round", Variant::I32(1))]),
+            death(100, ("1", 2), ("3", 3)),
+            death(200, ("3", 3), ("2", 2)),
+            death(300, ("4", 3), ("2", 2)),
+            death(400, ("2", 2), ("5", 3)),
+            test_event(
+                "round_end",
+                500,
+                vec![("round", Variant::I32(1)), ("winner", Variant::String("CT".to_string()))],
+            ),
+        ];
+        let clutches: Vec<Highlight> = highlights(&events, 0.0)
+            .into_iter()
+            .filter(|h| h.opponents.is_some())
+            .collect();
+        assert_eq!(clutches.len(), 2);
+        assert_eq!(clutches[0].highlight_type, HighlightType::ClutchLost);
+        assert_eq!(
+            (clutches[0].steamid, clutches[0].opponents, clutches[0].kills),
+            (2, Some(3), 2)
+        );
+        assert_eq!(clutches[1].highlight_type, HighlightType::ClutchWon);
+        assert_eq!(
+            (clutches[1].steamid, clutches[1].opponents, clutches[1].kills),
+            (5, Some(1), 1)
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field;
+use crate::analysis::field_bool;
+use crate::analysis::field_f32;
+use crate::analysis::field_steamid;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::collect_data::EYE_HEIGHT_DUCKED;
+use crate::second_pass::collect_data::EYE_HEIGHT_STANDING;
+use crate::second_pass::game_events::EventField;
+use crate::second_pass::game_events::GameEvent;
+use crate::second_pass::variants::Variant;
+
+pub const KILLS_EVENTS: &[&str] = &["player_death", "player_blind", "round_end", "round_start"];
+pub const KILLS_PLAYER_PROPS: &[&str] = &[
+    "team_num",
+    "X",
+    "Y",
+    "Z",
+    "pitch",
+    "yaw",
+    "X_before",
+    "Y_before",
+    "Z_before",
+    "pitch_before",
+    "yaw_before",
+    "velocity",
+    "is_airborne",
+    "ducked",
+    "approximate_spotted_by",
+];
+pub const KILLS_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+pub fn kills_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(KILLS_EVENTS, KILLS_PLAYER_PROPS, KILLS_OTHER_PROPS, huffman_lookup_table)?;
+    inputs.track_player_history = true;
+    Ok(inputs)
+}
+
+fn position(event: &GameEvent, prefix: &str, suffix: &str) -> Option<[f32; 3]> {
+    Some([
+        field_f32(event, &format!("{}_X{}", prefix, suffix))?,
+        field_f32(event, &format!("{}_Y{}", prefix, suffix))?,
+        field_f32(event, &format!("{}_Z{}", prefix, suffix))?,
+    ])
+}
+
+/// Angle in degrees between where the attacker was looking and the victim's head.
+pub fn view_angle_offset(attacker: [f32; 3], pitch: f32, yaw: f32, victim: [f32; 3]) -> Option<f32> {
+    let d = [victim[0] - attacker[0], victim[1] - attacker[1], victim[2] - attacker[2]];
+    let len = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+    if len == 0.0 {
+        return None;
+    }
+    // Positive pitch is looking down
+    let (pitch, yaw) = (pitch.to_radians(), yaw.to_radians());
+    let forward = [pitch.cos() * yaw.cos(), pitch.cos() * yaw.sin(), -pitch.sin()];
+    let dot = (forward[0] * d[0] + forward[1] * d[1] + forward[2] * d[2]) / len;
+    Some(dot.clamp(-1.0, 1.0).acos().to_degrees())
+}
+
+// Player origin is at the feet, aim goes from the eyes to the head. There is no "ducked" from
+// before the kill so the one at the kill is used for both.
+fn eye_height(event: &GameEvent, prefix: &str) -> f32 {
+    match field_bool(event, &format!("{}_ducked", prefix)) {
+        Some(true) => EYE_HEIGHT_DUCKED,
+        _ => EYE_HEIGHT_STANDING,
+    }
+}
+
+fn offset_from_event(event: &GameEvent, suffix: &str) -> Option<f32> {
+    let mut attacker = position(event, "attacker", suffix)?;
+    let mut victim = position(event, "user", suffix)?;
+    attacker[2] += eye_height(event, "attacker");
+    victim[2] += eye_height(event, "user");
+    let pitch = field_f32(event, &format!("attacker_pitch{}", suffix))?;
+    let yaw = field_f32(event, &format!("attacker_yaw{}", suffix))?;
+    view_angle_offset(attacker, pitch, yaw, victim)
+}
+
+/// Returns every player_death with computed fields added: round, distance between the players,
+/// how far off the attacker's crosshair was from the victim at the kill and
+/// PLAYER_HISTORY_LOOKBACK_TICKS before it, if the victim was flashed, if the attacker was in
+/// the air and if the victim had spotted the attacker. Positions, speed and the raw props are
+/// in the event as "attacker_X", "user_velocity" etc.
+/// Expects the events from kills_inputs().
+pub fn kills(events: &[GameEvent]) -> Vec<GameEvent> {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+
+    // (victim, start tick, end tick) of every blind
+    let blinds: Vec<(PlayerKey, i32, i32)> = events
+        .iter()
+        .filter(|e| e.name == "player_blind")
+        .filter_map(|e| {
+            let victim = PlayerKey::from_event(e, "user")?;
+            let duration = field_f32(e, "blind_duration")?;
+            Some((victim, e.tick, e.tick + (duration * TICKRATE) as i32))
+        })
+        .collect();
+
+    let mut kills = vec![];
+    for event in events.iter().filter(|e| e.name == "player_death") {
+        let victim = PlayerKey::from_event(event, "user");
+        let distance = match (position(event, "attacker", ""), position(event, "user", "")) {
+            (Some(a), Some(v)) => Some(((a[0] - v[0]).powi(2) + (a[1] - v[1]).powi(2) + (a[2] - v[2]).powi(2)).sqrt()),
+            _ => None,
+        };
+        let victim_flashed = victim.as_ref().map(|v| {
+            blinds
+                .iter()
+                .any(|(key, start, end)| key == v && (*start..=*end).contains(&event.tick))
+        });
+        let victim_spotted_attacker = match (field(event, "attacker_approximate_spotted_by"), field_steamid(event, "user")) {
+            (Some(Variant::U64Vec(spotted_by)), Some(victim)) => Some(spotted_by.contains(&victim)),
+            _ => None,
+        };
+
+        let mut fields = event.fields.clone();
+        fields.push(EventField {
+            name: "round".to_string(),
+            data: Some(Variant::I32(rounds.round_at(event.tick))),
+        });
+        fields.push(EventField {
+            name: "distance".to_string(),
+            data: distance.map(Variant::F32),
+        });
+        fields.push(EventField {
+            name: "view_angle_offset".to_string(),
+            data: offset_from_event(event, "").map(Variant::F32),
+        });
+        fields.push(EventField {
+            name: "view_angle_offset_before".to_string(),
+            data: offset_from_event(event, "_before").map(Variant::F32),
+        });
+        fields.push(EventField {
+            name: "victim_flashed".to_string(),
+            data: victim_flashed.map(Variant::Bool),
+        });
+        fields.push(EventField {
+            name: "attacker_airborne".to_string(),
+            data: field_bool(event, "attacker_is_airborne").map(Variant::Bool),
+        });
+        fields.push(EventField {
+            name: "victim_spotted_attacker".to_string(),
+            data: victim_spotted_attacker.map(Variant::Bool),
+        });
+        kills.push(GameEvent {
+            name: event.name.clone(),
+            fields,
+            tick: event.tick,
+        });
+    }
+    kills
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::field_f32;
+    use crate::analysis::kills::*;
+    use crate::analysis::test_event;
+    use crate::second_pass::variants::Variant;
+
+    fn close(a: Option<f32>, b: f32) -> bool {
+        a.is_some_and(|a| (a - b).abs() < 1e-3)
+    }
+
+    #[test]
+    fn test_view_angle_offset() {
+        let origin = [0.0, 0.0, 0.0];
+        assert!(close(view_angle_offset(origin, 0.0, 0.0, [100.0, 0.0, 0.0]), 0.0));
+        assert!(close(view_angle_offset(origin, 0.0, 0.0, [0.0, 100.0, 0.0]), 90.0));
+        assert!(close(view_angle_offset(origin, 0.0, 90.0, [0.0, 100.0, 0.0]), 0.0));
+        // Negative pitch is looking up
+        assert!(close(view_angle_offset(origin, -45.0, 0.0, [100.0, 0.0, 100.0]), 0.0));
+        assert!(close(view_angle_offset(origin, 45.0, 0.0, [100.0, 0.0, 100.0]), 90.0));
+        assert_eq!(view_angle_offset(origin, 0.0, 0.0, origin), None);
+    }
+
+    #[test]
+    fn test_kill_distance_and_offset() {
+        let down_to_crouched = ((EYE_HEIGHT_STANDING - EYE_HEIGHT_DUCKED) / 300.0).atan().to_degrees();
+        let fields = vec![
+            ("attacker_steamid", Variant::String("1".to_string())),
+            ("user_steamid", Variant::String("2".to_string())),
+            ("attacker_X", Variant::F32(0.0)),
+            ("attacker_Y", Variant::F32(0.0)),
+            ("attacker_Z", Variant::F32(0.0)),
+            ("user_X", Variant::F32(300.0)),
+            ("user_Y", Variant::F32(0.0)),
+            ("user_Z", Variant::F32(0.0)),
+            ("attacker_pitch", Variant::F32(down_to_crouched)),
+            ("attacker_yaw", Variant::F32(0.0)),
+            ("attacker_ducked", Variant::Bool(false)),
+            ("user_ducked", Variant::Bool(true)),
+        ];
+        let out = kills(&[test_event("player_death", 100, fields)]);
+        assert_eq!(out.len(), 1);
+        assert!(close(field_f32(&out[0], "distance"), 300.0));
+        // Aimed at the head of the crouched victim
+        assert!(close(field_f32(&out[0], "view_angle_offset"), 0.0));
+        assert_eq!(field_f32(&out[0], "view_angle_offset_before"), None);
+    }
+}
@@ -0,0 +1,284 @@
+pub mod aim;
+pub mod bomb;
+pub mod duels;
+pub mod economy;
+pub mod fires_and_smokes;
+pub mod flashes;
+pub mod grenades;
+pub mod highlights;
+pub mod kills;
+pub mod movement;
+pub mod player_stats;
+pub mod recoil;
+pub mod shots;
+pub mod stats_updates;
+pub mod user_cmds;
+pub mod visibility;
+pub mod voice_activity;
+pub mod weapon_skins;
+pub mod weapons;
+
+use crate::first_pass::parser_settings::rm_user_friendly_names;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::game_events::GameEvent;
+use crate::second_pass::variants::Variant;
+use ahash::AHashMap;
+
+// Demos are recorded at 64 ticks per second
+pub const TICKRATE: f32 = 64.0;
+
+/// Creates the parser inputs needed by the event based analyses. Props are given with their
+/// user friendly names (e.g. "team_num") and show up in the events as "user_team_num" etc.
+pub fn event_inputs<'a>(
+    wanted_events: &[&str],
+    wanted_player_props: &[&str],
+    wanted_other_props: &[&str],
+    huffman_lookup_table: &'a Vec<(u8, u8)>,
+) -> Result<ParserInputs<'a>, DemoParserError> {
+    let player_props: Vec<String> = wanted_player_props.iter().map(|x| x.to_string()).collect();
+    let other_props: Vec<String> = wanted_other_props.iter().map(|x| x.to_string()).collect();
+    let real_player_props = rm_user_friendly_names(&player_props)?;
+    let real_other_props = rm_user_friendly_names(&other_props)?;
+
+    let mut real_name_to_og_name = AHashMap::default();
+    for (real_name, friendly_name) in real_player_props.iter().zip(&player_props) {
+        real_name_to_og_name.insert(real_name.clone(), friendly_name.clone());
+    }
+    for (real_name, friendly_name) in real_other_props.iter().zip(&other_props) {
+        real_name_to_og_name.insert(real_name.clone(), friendly_name.clone());
+    }
+    Ok(ParserInputs {
+        real_name_to_og_name,
+        wanted_players: vec![],
+        wanted_player_props: real_player_props,
+        wanted_other_props: real_other_props,
+        wanted_events: wanted_events.iter().map(|x| x.to_string()).collect(),
+        parse_ents: true,
+        wanted_ticks: vec![],
+        parse_projectiles: false,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
+        only_header: true,
+        count_props: false,
+        only_convars: false,
+        huffman_lookup_table,
+    })
+}
+
+pub fn field<'a>(event: &'a GameEvent, name: &str) -> Option<&'a Variant> {
+    event.fields.iter().find(|f| f.name == name).and_then(|f| f.data.as_ref())
+}
+
+pub fn field_i32(event: &GameEvent, name: &str) -> Option<i32> {
+    match field(event, name)? {
+        Variant::I32(v) => Some(*v),
+        Variant::U32(v) => Some(*v as i32),
+        Variant::I16(v) => Some(*v as i32),
+        Variant::U8(v) => Some(*v as i32),
+        Variant::U64(v) => Some(*v as i32),
+        Variant::F32(v) => Some(*v as i32),
+        _ => None,
+    }
+}
+
+pub fn field_f32(event: &GameEvent, name: &str) -> Option<f32> {
+    match field(event, name)? {
+        Variant::F32(v) => Some(*v),
+        Variant::I32(v) => Some(*v as f32),
+        Variant::U32(v) => Some(*v as f32),
+        Variant::I16(v) => Some(*v as f32),
+        Variant::U8(v) => Some(*v as f32),
+        _ => None,
+    }
+}
+
+pub fn field_bool(event: &GameEvent, name: &str) -> Option<bool> {
+    match field(event, name)? {
+        Variant::Bool(b) => Some(*b),
+        _ => None,
+    }
+}
+
+pub fn field_string<'a>(event: &'a GameEvent, name: &str) -> Option<&'a str> {
+    match field(event, name)? {
+        Variant::String(s) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+/// Steamids are added to events as strings, prefix is "user", "attacker" or "assister".
+pub fn field_steamid(event: &GameEvent, prefix: &str) -> Option<u64> {
+    match field(event, &(prefix.to_owned() + "_steamid"))? {
+        Variant::String(s) => s.parse::<u64>().ok(),
+        Variant::U64(u) => Some(*u),
+        _ => None,
+    }
+}
+
+pub fn is_warmup_event(event: &GameEvent) -> bool {
+    field_bool(event, "is_warmup_period") == Some(true)
+}
+
+/// Players are identified by steamid so that reconnects end up in the same row. Bots all have
+/// steamid 0 so they are told apart by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PlayerKey {
+    pub steamid: u64,
+    pub bot_name: Option<String>,
+}
+
+impl PlayerKey {
+    pub fn from_event(event: &GameEvent, prefix: &str) -> Option<PlayerKey> {
+        let steamid = field_steamid(event, prefix)?;
+        let bot_name = match steamid {
+            0 => Some(field_string(event, &(prefix.to_owned() + "_name"))?.to_string()),
+            _ => None,
+        };
+        Some(PlayerKey { steamid, bot_name })
+    }
+    /// Same as from_event for the records collected during parsing.
+    pub fn from_record(steamid: Option<u64>, name: &Option<String>) -> Option<PlayerKey> {
+        let steamid = steamid?;
+        let bot_name = match steamid {
+            0 => Some(name.clone()?),
+            _ => None,
+        };
+        Some(PlayerKey { steamid, bot_name })
+    }
+}
+
+/// Maps ticks to round numbers using the "round_end" and "round_start" events. Round N lasts
+/// until the freeze period of round N + 1 ends, so kills after the round was decided still
+/// count towards it.
+#[derive(Debug, Clone, Default)]
+pub struct RoundTimeline {
+    // (tick, round) of every round end
+    pub round_ends: Vec<(i32, i32)>,
+    // (tick, round) of every freeze period end
+    pub round_starts: Vec<(i32, i32)>,
+    // round -> team number of the winner
+    pub winners: AHashMap<i32, i32>,
+}
+
+impl RoundTimeline {
+    pub fn from_events(events: &[GameEvent]) -> Self {
+        let mut round_ends = vec![];
+        let mut round_starts = vec![];
+        let mut winners = AHashMap::default();
+        for event in events {
+            if is_warmup_event(event) {
+                continue;
+            }
+            match event.name.as_str() {
+                "round_end" => {
+                    if let Some(round) = field_i32(event, "round") {
+                        round_ends.push((event.tick, round));
+                        match field_string(event, "winner") {
+                            Some("T") => winners.insert(round, 2),
+                            Some("CT") => winners.insert(round, 3),
+                            _ => None,
+                        };
+                    }
+                }
+                "round_start" => {
+                    if let Some(round) = field_i32(event, "round") {
+                        round_starts.push((event.tick, round));
+                    }
+                }
+                _ => {}
+            }
+        }
+        round_ends.sort();
+        round_starts.sort();
+        RoundTimeline {
+            round_ends,
+            round_starts,
+            winners,
+        }
+    }
+    pub fn round_at(&self, tick: i32) -> i32 {
+        if let Some((end_tick, round)) = self.round_ends.iter().rev().find(|(end_tick, _)| *end_tick < tick) {
+            let next_started = self.round_starts.iter().any(|(start, _)| start > end_tick && *start <= tick);
+            // Without round starts there is nothing to end the previous round at
+            if !self.round_starts.is_empty() && !next_started {
+                return *round;
+            }
+        }
+        self.buy_round_at(tick)
+    }
+    /// Round that money spent at the tick goes into, freezetime after round N is round N + 1.
+    pub fn buy_round_at(&self, tick: i32) -> i32 {
+        match self.round_ends.iter().find(|(end_tick, _)| *end_tick >= tick) {
+            Some((_, round)) => *round,
+            None => match self.round_ends.last() {
+                Some((_, round)) => round + 1,
+                None => 1,
+            },
+        }
+    }
+    /// Tick where the round was decided, None if the demo ends before that.
+    pub fn round_end_tick(&self, round: i32) -> Option<i32> {
+        self.round_ends.iter().find(|(_, r)| *r == round).map(|(tick, _)| *tick)
+    }
+    /// Tick where freezetime ended, falls back to the end of the previous round.
+    pub fn round_start_tick(&self, round: i32) -> Option<i32> {
+        match self.round_starts.iter().rev().find(|(_, r)| *r == round) {
+            Some((tick, _)) => Some(*tick),
+            None => self.round_end_tick(round - 1),
+        }
+    }
+}
+
+/// Events sorted by tick, without warmup.
+pub fn sorted_events(events: &[GameEvent]) -> Vec<&GameEvent> {
+    let mut events: Vec<&GameEvent> = events.iter().filter(|e| !is_warmup_event(e)).collect();
+    events.sort_by_key(|e| e.tick);
+    events
+}
+
+#[cfg(test)]
+pub(crate) fn test_event(name: &str, tick: i32, fields: Vec<(&str, Variant)>) -> GameEvent {
+    use crate::second_pass::game_events::EventField;
+    GameEvent {
+        name: name.to_string(),
+        tick,
+        fields: fields
+            .into_iter()
+            .map(|(name, data)| EventField {
+                name: name.to_string(),
+                data: Some(data),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::*;
+
+    #[test]
+    fn test_round_at() {
+        let rounds = RoundTimeline {
+            round_ends: vec![(100, 1), (300, 2)],
+            round_starts: vec![(10, 1), (150, 2), (350, 3)],
+            winners: AHashMap::default(),
+        };
+        assert_eq!(rounds.round_at(0), 1);
+        assert_eq!(rounds.round_at(100), 1);
+        // Between the end of round 1 and the end of the freeze period of round 2
+        assert_eq!(rounds.round_at(120), 1);
+        assert_eq!(rounds.round_at(150), 2);
+        assert_eq!(rounds.round_at(320), 2);
+        assert_eq!(rounds.round_at(400), 3);
+        assert_eq!(rounds.buy_round_at(120), 2);
+        assert_eq!(rounds.buy_round_at(320), 3);
+    }
+}
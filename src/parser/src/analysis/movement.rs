@@ -0,0 +1,355 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field_bool;
+use crate::analysis::field_f32;
+use crate::analysis::field_steamid;
+use crate::analysis::field_string;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::collect_data::MovementRecord;
+use crate::second_pass::game_events::GameEvent;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const MOVEMENT_EVENTS: &[&str] = &["weapon_fire", "player_jump", "round_end", "round_start"];
+// Asking for is_walking in events turns on collecting the movement segments
+pub const MOVEMENT_PLAYER_PROPS: &[&str] = &["team_num", "velocity", "is_airborne", "is_walking"];
+pub const MOVEMENT_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// A shot is accurate when the shooter moves slower than this fraction of the weapon's max speed
+pub const ACCURATE_SPEED_FRACTION: f32 = 0.34;
+
+// Max running speed of every gun, knives and grenades don't have an accurate speed
+const WEAPON_MAX_SPEEDS: &[(&str, f32)] = &[
+    ("ak47", 215.0),
+    ("aug", 220.0),
+    ("awp", 200.0),
+    ("bizon", 240.0),
+    ("cz75a", 240.0),
+    ("deagle", 230.0),
+    ("elite", 240.0),
+    ("famas", 220.0),
+    ("fiveseven", 240.0),
+    ("g3sg1", 215.0),
+    ("galilar", 215.0),
+    ("glock", 240.0),
+    ("hkp2000", 240.0),
+    ("m249", 195.0),
+    ("m4a1", 225.0),
+    ("m4a1_silencer", 225.0),
+    ("mac10", 240.0),
+    ("mag7", 225.0),
+    ("mp5sd", 235.0),
+    ("mp7", 220.0),
+    ("mp9", 240.0),
+    ("negev", 150.0),
+    ("nova", 220.0),
+    ("p250", 240.0),
+    ("p90", 230.0),
+    ("revolver", 220.0),
+    ("sawedoff", 210.0),
+    ("scar20", 215.0),
+    ("sg556", 210.0),
+    ("ssg08", 230.0),
+    ("taser", 220.0),
+    ("tec9", 240.0),
+    ("ump45", 230.0),
+    ("usp_silencer", 240.0),
+    ("xm1014", 215.0),
+];
+
+pub fn movement_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(
+        MOVEMENT_EVENTS,
+        MOVEMENT_PLAYER_PROPS,
+        MOVEMENT_OTHER_PROPS,
+        huffman_lookup_table,
+    )?;
+    inputs.track_player_history = true;
+    inputs.collect_movement = true;
+    Ok(inputs)
+}
+
+/// Max speed of a weapon given as in weapon_fire ("weapon_ak47") or without the prefix.
+pub fn weapon_max_speed(weapon: &str) -> Option<f32> {
+    let weapon = weapon.strip_prefix("weapon_").unwrap_or(weapon);
+    WEAPON_MAX_SPEEDS
+        .iter()
+        .find(|(name, _)| *name == weapon)
+        .map(|(_, speed)| *speed)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerRoundMovement {
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub round: i32,
+    // Horizontal units
+    pub distance: f32,
+    // Seconds
+    pub time_running: f32,
+    pub time_walking: f32,
+    pub time_crouched: f32,
+    pub time_airborne: f32,
+    pub time_still: f32,
+    pub jumps: i32,
+    pub shots: i32,
+    // Shots with a gun that has an accurate speed, the rest are knife swings and grenades
+    pub gun_shots: i32,
+    pub accurate_shots: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ShotMovement {
+    pub tick: i32,
+    pub round: i32,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    pub weapon: Option<String>,
+    // Horizontal units per second
+    pub speed: Option<f32>,
+    pub max_speed: Option<f32>,
+    pub airborne: Option<bool>,
+    // None for weapons without an accurate speed
+    pub accurate: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Movement {
+    pub players: Vec<PlayerRoundMovement>,
+    pub shots: Vec<ShotMovement>,
+}
+
+/// Sums the movement segments into distance and time spent running, walking, crouched, in the
+/// air and standing still per player per round, counting only from the end of freezetime to
+/// the end of the round. Every weapon_fire gets the shooter's speed and whether it was below
+/// ACCURATE_SPEED_FRACTION of the weapon's max speed on the ground, which tells how well the
+/// player counter-strafes. Expects the events and movement records from movement_inputs().
+pub fn movement(events: &[GameEvent], records: &[MovementRecord]) -> Movement {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+    let mut per_player: BTreeMap<(i32, PlayerKey), PlayerRoundMovement> = BTreeMap::default();
+
+    for (idx, (start, round)) in rounds.round_starts.iter().enumerate() {
+        // The next round starting also ends this one if the demo has no round_end for it
+        let next_start = rounds.round_starts.get(idx + 1).map(|(tick, _)| *tick - 1);
+        let end = match (rounds.round_end_tick(*round), next_start) {
+            (Some(end), Some(next)) => end.min(next),
+            (Some(end), None) => end,
+            (None, Some(next)) => next,
+            (None, None) => i32::MAX,
+        };
+        for record in records.iter().filter(|r| r.end_tick >= *start && r.start_tick <= end) {
+            let key = match PlayerKey::from_record(record.steamid, &record.name) {
+                Some(key) => key,
+                None => continue,
+            };
+            let total_ticks = (record.end_tick - record.start_tick + 1) as f32;
+            let ticks = (record.end_tick.min(end) - record.start_tick.max(*start) + 1) as f32;
+            let entry = per_player
+                .entry((*round, key.clone()))
+                .or_insert_with(|| PlayerRoundMovement {
+                    steamid: key.steamid,
+                    name: record.name.clone(),
+                    round: *round,
+                    ..Default::default()
+                });
+            // Segments that cross the round boundaries only count the part inside
+            entry.distance += record.distance * ticks / total_ticks;
+            let seconds = ticks / TICKRATE;
+            match record.state.as_str() {
+                "running" => entry.time_running += seconds,
+                "walking" => entry.time_walking += seconds,
+                "crouched" => entry.time_crouched += seconds,
+                "airborne" => entry.time_airborne += seconds,
+                _ => entry.time_still += seconds,
+            }
+        }
+    }
+
+    let first_tick = rounds.round_starts.first().map(|(tick, _)| *tick).unwrap_or(0);
+    let mut shots = vec![];
+    for event in events.iter().filter(|e| e.tick >= first_tick) {
+        let key = match PlayerKey::from_event(event, "user") {
+            Some(key) => key,
+            None => continue,
+        };
+        let round = rounds.round_at(event.tick);
+        let name = field_string(event, "user_name").map(|s| s.to_string());
+        let entry = per_player.entry((round, key.clone())).or_insert_with(|| PlayerRoundMovement {
+            steamid: key.steamid,
+            name: name.clone(),
+            round,
+            ..Default::default()
+        });
+        match event.name.as_str() {
+            "player_jump" => entry.jumps += 1,
+            "weapon_fire" => {
+                let weapon = field_string(event, "weapon").map(|s| s.to_string());
+                let max_speed = weapon.as_deref().and_then(weapon_max_speed);
+                let speed = field_f32(event, "user_velocity");
+                let airborne = field_bool(event, "user_is_airborne");
+                let accurate = match (max_speed, speed) {
+                    (Some(max_speed), Some(speed)) => {
+                        Some(airborne != Some(true) && speed <= max_speed * ACCURATE_SPEED_FRACTION)
+                    }
+                    _ => None,
+                };
+                entry.shots += 1;
+                if accurate.is_some() {
+                    entry.gun_shots += 1;
+                }
+                if accurate == Some(true) {
+                    entry.accurate_shots += 1;
+                }
+                shots.push(ShotMovement {
+                    tick: event.tick,
+                    round,
+                    steamid: field_steamid(event, "user"),
+                    name,
+                    weapon,
+                    speed,
+                    max_speed,
+                    airborne,
+                    accurate,
+                });
+            }
+            _ => {}
+        }
+    }
+    Movement {
+        players: per_player.into_values().collect(),
+        shots,
+    }
+}
+
+impl Serialize for PlayerRoundMovement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PlayerRoundMovement", 13)?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("distance", &self.distance)?;
+        state.serialize_field("time_running", &self.time_running)?;
+        state.serialize_field("time_walking", &self.time_walking)?;
+        state.serialize_field("time_crouched", &self.time_crouched)?;
+        state.serialize_field("time_airborne", &self.time_airborne)?;
+        state.serialize_field("time_still", &self.time_still)?;
+        state.serialize_field("jumps", &self.jumps)?;
+        state.serialize_field("shots", &self.shots)?;
+        state.serialize_field("gun_shots", &self.gun_shots)?;
+        state.serialize_field("accurate_shots", &self.accurate_shots)?;
+        state.end()
+    }
+}
+impl Serialize for ShotMovement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("ShotMovement", 9)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("weapon", &self.weapon)?;
+        state.serialize_field("speed", &self.speed)?;
+        state.serialize_field("max_speed", &self.max_speed)?;
+        state.serialize_field("airborne", &self.airborne)?;
+        state.serialize_field("accurate", &self.accurate)?;
+        state.end()
+    }
+}
+impl Serialize for Movement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Movement", 2)?;
+        state.serialize_field("players", &self.players)?;
+        state.serialize_field("shots", &self.shots)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::movement::*;
+    use crate::analysis::test_event;
+    use crate::second_pass::variants::Variant;
+
+    fn segment(state: &str, start_tick: i32, end_tick: i32, distance: f32) -> MovementRecord {
+        MovementRecord {
+            steamid: Some(1),
+            name: Some("player1".to_string()),
+            state: state.to_string(),
+            start_tick,
+            end_tick,
+            distance,
+        }
+    }
+    fn shot(tick: i32, weapon: &str, velocity: f32) -> GameEvent {
+        test_event(
+            "weapon_fire",
+            tick,
+            vec![
+                ("user_steamid", Variant::String("1".to_string())),
+                ("weapon", Variant::String(weapon.to_string())),
+                ("user_velocity", Variant::F32(velocity)),
+                ("user_is_airborne", Variant::Bool(false)),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_weapon_max_speed() {
+        assert_eq!(weapon_max_speed("weapon_ak47"), Some(215.0));
+        assert_eq!(weapon_max_speed("awp"), Some(200.0));
+        assert_eq!(weapon_max_speed("weapon_knife"), None);
+        assert_eq!(weapon_max_speed("weapon_hegrenade"), None);
+    }
+
+    #[test]
+    fn test_movement() {
+        let events = vec![
+            test_event("round_start", 0, vec![("round", Variant::I32(1))]),
+            shot(50, "weapon_ak47", 50.0),
+            shot(60, "weapon_ak47", 200.0),
+            shot(70, "weapon_knife", 250.0),
+            test_event("player_jump", 80, vec![("user_steamid", Variant::String("1".to_string()))]),
+            test_event("round_end", 1000, vec![("round", Variant::I32(1))]),
+            test_event("round_start", 1100, vec![("round", Variant::I32(2))]),
+        ];
+        let records = vec![
+            segment("running", 0, 63, 200.0),
+            segment("still", 64, 127, 0.0),
+            // Crosses the end of round 1 and the time before round 2
+            segment("crouched", 900, 1163, 264.0),
+        ];
+        let out = movement(&events, &records);
+        assert_eq!(out.players.len(), 2);
+        let first = &out.players[0];
+        assert_eq!(first.round, 1);
+        assert_eq!((first.time_running, first.time_still), (1.0, 1.0));
+        assert_eq!(first.time_crouched, 101.0 / 64.0);
+        assert_eq!(first.distance, 200.0 + 101.0);
+        assert_eq!(
+            (first.jumps, first.shots, first.gun_shots, first.accurate_shots),
+            (1, 3, 2, 1)
+        );
+        let second = &out.players[1];
+        assert_eq!(second.round, 2);
+        assert_eq!((second.time_crouched, second.distance), (1.0, 64.0));
+
+        let accurate: Vec<Option<bool>> = out.shots.iter().map(|s| s.accurate).collect();
+        assert_eq!(accurate, vec![Some(true), Some(false), None]);
+        assert_eq!(out.shots[0].max_speed, Some(215.0));
+    }
+}
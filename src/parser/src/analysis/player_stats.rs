@@ -0,0 +1,285 @@
+use crate::analysis::duels::find_trades;
+use crate::analysis::duels::is_enemy;
+use crate::analysis::duels::Death;
+use crate::analysis::duels::DEFAULT_TRADE_WINDOW_SECONDS;
+use crate::analysis::event_inputs;
+use crate::analysis::field_bool;
+use crate::analysis::field_i32;
+use crate::analysis::field_string;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::game_events::GameEvent;
+use ahash::AHashMap;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const PLAYER_STATS_EVENTS: &[&str] = &["player_death", "player_hurt", "player_spawn", "round_end", "round_start"];
+pub const PLAYER_STATS_PLAYER_PROPS: &[&str] = &["team_num"];
+pub const PLAYER_STATS_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+const MAX_HEALTH: i32 = 100;
+
+pub fn player_stats_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    event_inputs(
+        PLAYER_STATS_EVENTS,
+        PLAYER_STATS_PLAYER_PROPS,
+        PLAYER_STATS_OTHER_PROPS,
+        huffman_lookup_table,
+    )
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerRoundStats {
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub round: i32,
+    pub team_num: Option<i32>,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub flash_assists: i32,
+    pub headshot_kills: i32,
+    pub damage: i32,
+    pub survived: bool,
+    pub traded: bool,
+    pub kast: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerMatchStats {
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub team_num: Option<i32>,
+    pub rounds_played: i32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub flash_assists: i32,
+    pub headshot_kills: i32,
+    pub damage: i32,
+    pub adr: f32,
+    pub headshot_percentage: f32,
+    pub kast: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerStats {
+    pub per_round: Vec<PlayerRoundStats>,
+    pub per_match: Vec<PlayerMatchStats>,
+}
+
+/// Computes kills, deaths, assists, ADR, HS% and KAST per player per round and for the
+/// whole match. Expects the events from player_stats_inputs().
+pub fn player_stats(events: &[GameEvent]) -> PlayerStats {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+    let trade_window = (DEFAULT_TRADE_WINDOW_SECONDS * TICKRATE) as i32;
+
+    let mut per_round: BTreeMap<(i32, PlayerKey), PlayerRoundStats> = BTreeMap::default();
+    let mut health: AHashMap<PlayerKey, (i32, i32)> = AHashMap::default();
+    let mut deaths = vec![];
+
+    for event in events {
+        let round = rounds.round_at(event.tick);
+        match event.name.as_str() {
+            "player_spawn" => {
+                // Players spawn at the start of the freeze period of the next round
+                let round = rounds.buy_round_at(event.tick);
+                let team = field_i32(event, "user_team_num");
+                // Spectators also "spawn"
+                if team != Some(2) && team != Some(3) {
+                    continue;
+                }
+                if let Some(user) = PlayerKey::from_event(event, "user") {
+                    health.insert(user.clone(), (round, MAX_HEALTH));
+                    player_round(&mut per_round, &user, round, event, "user");
+                }
+            }
+            "player_hurt" => {
+                let (victim, attacker) = match (PlayerKey::from_event(event, "user"), PlayerKey::from_event(event, "attacker")) {
+                    (Some(v), Some(a)) => (v, a),
+                    (Some(v), None) => {
+                        track_health(&mut health, &v, round, event);
+                        continue;
+                    }
+                    _ => continue,
+                };
+                let damage = track_health(&mut health, &victim, round, event);
+                if victim == attacker || !is_enemy(field_i32(event, "user_team_num"), field_i32(event, "attacker_team_num")) {
+                    continue;
+                }
+                player_round(&mut per_round, &attacker, round, event, "attacker").damage += damage;
+            }
+            "player_death" => {
+                let death = match Death::from_event(event, round) {
+                    Some(d) => d,
+                    None => continue,
+                };
+                player_round(&mut per_round, &death.victim, round, event, "user").deaths += 1;
+                if let (Some(attacker), true) = (&death.attacker, death.is_enemy_kill()) {
+                    let stats = player_round(&mut per_round, attacker, round, event, "attacker");
+                    stats.kills += 1;
+                    if field_bool(event, "headshot") == Some(true) {
+                        stats.headshot_kills += 1;
+                    }
+                }
+                if let Some(assister) = PlayerKey::from_event(event, "assister") {
+                    if is_enemy(field_i32(event, "user_team_num"), field_i32(event, "assister_team_num")) {
+                        let stats = player_round(&mut per_round, &assister, round, event, "assister");
+                        match field_bool(event, "assistedflash") {
+                            Some(true) => stats.flash_assists += 1,
+                            _ => stats.assists += 1,
+                        }
+                    }
+                }
+                deaths.push(death);
+            }
+            _ => {}
+        }
+    }
+    // A death is traded if the killer died shortly after in the same round
+    for (death, trade) in deaths.iter().zip(find_trades(&deaths, trade_window)) {
+        if trade.is_some() {
+            if let Some(stats) = per_round.get_mut(&(death.round, death.victim.clone())) {
+                stats.traded = true;
+            }
+        }
+    }
+    for stats in per_round.values_mut() {
+        stats.survived = stats.deaths == 0;
+        stats.kast = stats.kills > 0 || stats.assists > 0 || stats.flash_assists > 0 || stats.survived || stats.traded;
+    }
+
+    let mut per_match: BTreeMap<PlayerKey, PlayerMatchStats> = BTreeMap::default();
+    let mut kast_rounds: AHashMap<PlayerKey, i32> = AHashMap::default();
+    for ((_, key), round_stats) in &per_round {
+        let stats = per_match.entry(key.clone()).or_insert_with(|| PlayerMatchStats {
+            steamid: key.steamid,
+            ..Default::default()
+        });
+        // Latest name and team win, players can change both mid match
+        if round_stats.name.is_some() {
+            stats.name = round_stats.name.clone();
+        }
+        if round_stats.team_num.is_some() {
+            stats.team_num = round_stats.team_num;
+        }
+        stats.rounds_played += 1;
+        stats.kills += round_stats.kills;
+        stats.deaths += round_stats.deaths;
+        stats.assists += round_stats.assists;
+        stats.flash_assists += round_stats.flash_assists;
+        stats.headshot_kills += round_stats.headshot_kills;
+        stats.damage += round_stats.damage;
+        if round_stats.kast {
+            *kast_rounds.entry(key.clone()).or_insert(0) += 1;
+        }
+    }
+    for (key, stats) in per_match.iter_mut() {
+        if stats.rounds_played > 0 {
+            stats.adr = stats.damage as f32 / stats.rounds_played as f32;
+            stats.kast = *kast_rounds.get(key).unwrap_or(&0) as f32 / stats.rounds_played as f32 * 100.0;
+        }
+        if stats.kills > 0 {
+            stats.headshot_percentage = stats.headshot_kills as f32 / stats.kills as f32 * 100.0;
+        }
+    }
+    PlayerStats {
+        per_round: per_round.into_values().collect(),
+        per_match: per_match.into_values().collect(),
+    }
+}
+
+fn player_round<'a>(
+    per_round: &'a mut BTreeMap<(i32, PlayerKey), PlayerRoundStats>,
+    key: &PlayerKey,
+    round: i32,
+    event: &GameEvent,
+    prefix: &str,
+) -> &'a mut PlayerRoundStats {
+    let stats = per_round.entry((round, key.clone())).or_insert_with(|| PlayerRoundStats {
+        steamid: key.steamid,
+        round,
+        ..Default::default()
+    });
+    if let Some(name) = field_string(event, &(prefix.to_owned() + "_name")) {
+        stats.name = Some(name.to_string());
+    }
+    if let Some(team) = field_i32(event, &(prefix.to_owned() + "_team_num")) {
+        stats.team_num = Some(team);
+    }
+    stats
+}
+
+// Returns the damage actually dealt, "dmg_health" is not capped at the health the player had left
+fn track_health(health: &mut AHashMap<PlayerKey, (i32, i32)>, victim: &PlayerKey, round: i32, event: &GameEvent) -> i32 {
+    let remaining = match health.get(victim) {
+        Some((r, hp)) if *r == round => *hp,
+        _ => MAX_HEALTH,
+    };
+    let damage = field_i32(event, "dmg_health").unwrap_or(0).min(remaining).max(0);
+    let new_health = field_i32(event, "health").unwrap_or(remaining - damage);
+    health.insert(victim.clone(), (round, new_health));
+    damage
+}
+
+impl Serialize for PlayerRoundStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PlayerRoundStats", 13)?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("team_num", &self.team_num)?;
+        state.serialize_field("kills", &self.kills)?;
+        state.serialize_field("deaths", &self.deaths)?;
+        state.serialize_field("assists", &self.assists)?;
+        state.serialize_field("flash_assists", &self.flash_assists)?;
+        state.serialize_field("headshot_kills", &self.headshot_kills)?;
+        state.serialize_field("damage", &self.damage)?;
+        state.serialize_field("survived", &self.survived)?;
+        state.serialize_field("traded", &self.traded)?;
+        state.serialize_field("kast", &self.kast)?;
+        state.end()
+    }
+}
+impl Serialize for PlayerMatchStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PlayerMatchStats", 13)?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("team_num", &self.team_num)?;
+        state.serialize_field("rounds_played", &self.rounds_played)?;
+        state.serialize_field("kills", &self.kills)?;
+        state.serialize_field("deaths", &self.deaths)?;
+        state.serialize_field("assists", &self.assists)?;
+        state.serialize_field("flash_assists", &self.flash_assists)?;
+        state.serialize_field("headshot_kills", &self.headshot_kills)?;
+        state.serialize_field("damage", &self.damage)?;
+        state.serialize_field("adr", &self.adr)?;
+        state.serialize_field("headshot_percentage", &self.headshot_percentage)?;
+        state.serialize_field("kast", &self.kast)?;
+        state.end()
+    }
+}
+impl Serialize for PlayerStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PlayerStats", 2)?;
+        state.serialize_field("per_round", &self.per_round)?;
+        state.serialize_field("per_match", &self.per_match)?;
+        state.end()
+    }
+}
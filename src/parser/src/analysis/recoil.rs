@@ -0,0 +1,250 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field;
+use crate::analysis::field_f32;
+use crate::analysis::shots::shots;
+use crate::analysis::shots::Shot;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::game_events::GameEvent;
+use crate::second_pass::variants::Variant;
+use ahash::AHashMap;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const RECOIL_EVENTS: &[&str] = &["weapon_fire", "player_hurt", "round_end", "round_start"];
+pub const RECOIL_PLAYER_PROPS: &[&str] = &["team_num", "pitch", "yaw", "aim_punch_angle", "aim_punch_angle_vel"];
+pub const RECOIL_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// weapon_recoil_scale, bullets go where the eyes look plus this times the aim punch
+pub const RECOIL_SCALE: f32 = 2.0;
+
+pub fn recoil_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    event_inputs(RECOIL_EVENTS, RECOIL_PLAYER_PROPS, RECOIL_OTHER_PROPS, huffman_lookup_table)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RecoilBullet {
+    pub tick: i32,
+    pub round: i32,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    pub weapon: String,
+    // Tick of the first bullet, identifies the spray
+    pub spray_start_tick: i32,
+    pub spray_index: i32,
+    pub pitch: Option<f32>,
+    pub yaw: Option<f32>,
+    pub punch_pitch: Option<f32>,
+    pub punch_yaw: Option<f32>,
+    pub punch_vel_pitch: Option<f32>,
+    pub punch_vel_yaw: Option<f32>,
+    // Where the bullet went: eye angles plus the scaled punch
+    pub aim_pitch: Option<f32>,
+    pub aim_yaw: Option<f32>,
+    // Degrees between where this bullet went and where the first bullet of the spray went
+    pub compensation_error: Option<f32>,
+    pub hit: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SprayControl {
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub weapon: String,
+    // Sprays of at least 2 bullets
+    pub sprays: i32,
+    // Bullets after the first one in those sprays
+    pub bullets: i32,
+    pub avg_compensation_error: Option<f32>,
+    // Hits / bullets of the same bullets
+    pub spray_accuracy: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Recoil {
+    pub bullets: Vec<RecoilBullet>,
+    pub players: Vec<SprayControl>,
+}
+
+fn angles(event: &GameEvent, name: &str) -> (Option<f32>, Option<f32>) {
+    match field(event, name) {
+        Some(Variant::VecXYZ(v)) => (Some(v[0]), Some(v[1])),
+        _ => (None, None),
+    }
+}
+
+// Yaw wraps around at +-180
+fn angle_diff(a: f32, b: f32) -> f32 {
+    let d = (a - b) % 360.0;
+    if d > 180.0 {
+        d - 360.0
+    } else if d < -180.0 {
+        d + 360.0
+    } else {
+        d
+    }
+}
+
+/// Returns the eye angles and aim punch of every bullet of every spray (spray and hits as in
+/// shots()). Bullets go to the eye angles plus RECOIL_SCALE times the punch, so perfect spray
+/// control keeps that point where the first bullet went: the compensation error is how far
+/// each bullet lands from it in degrees. Following a moving target also counts as error, so
+/// compare players on the same weapons. Sprays are summed per player per weapon into the
+/// average error and the accuracy after the first bullet.
+/// Expects the events from recoil_inputs().
+pub fn recoil(events: &[GameEvent]) -> Recoil {
+    let shots = shots(events);
+    let events = sorted_events(events);
+    let shot_at: AHashMap<(PlayerKey, i32), &Shot> = shots
+        .shots
+        .iter()
+        .filter_map(|s| Some(((PlayerKey::from_record(s.steamid, &s.name)?, s.tick), s)))
+        .collect();
+
+    let mut bullets: Vec<RecoilBullet> = vec![];
+    // Aim of the first bullet of the current spray of every player
+    let mut spray_start: AHashMap<PlayerKey, (i32, Option<(f32, f32)>)> = AHashMap::default();
+    for event in events.iter().filter(|e| e.name == "weapon_fire") {
+        let key = match PlayerKey::from_event(event, "user") {
+            Some(key) => key,
+            None => continue,
+        };
+        let shot = match shot_at.get(&(key.clone(), event.tick)) {
+            Some(shot) => shot,
+            None => continue,
+        };
+        let (punch_pitch, punch_yaw) = angles(event, "user_aim_punch_angle");
+        let (punch_vel_pitch, punch_vel_yaw) = angles(event, "user_aim_punch_angle_vel");
+        let (pitch, yaw) = (field_f32(event, "user_pitch"), field_f32(event, "user_yaw"));
+        let aim = match (pitch, yaw, punch_pitch, punch_yaw) {
+            (Some(pitch), Some(yaw), Some(punch_pitch), Some(punch_yaw)) => {
+                Some((pitch + RECOIL_SCALE * punch_pitch, yaw + RECOIL_SCALE * punch_yaw))
+            }
+            _ => None,
+        };
+        if shot.spray_index == 1 {
+            spray_start.insert(key.clone(), (event.tick, aim));
+        }
+        let (spray_start_tick, first_aim) = spray_start.get(&key).cloned().unwrap_or((event.tick, aim));
+        let compensation_error = match (aim, first_aim) {
+            (Some((pitch, yaw)), Some((first_pitch, first_yaw))) => {
+                Some((angle_diff(pitch, first_pitch).powi(2) + angle_diff(yaw, first_yaw).powi(2)).sqrt())
+            }
+            _ => None,
+        };
+        bullets.push(RecoilBullet {
+            tick: event.tick,
+            round: shot.round,
+            steamid: shot.steamid,
+            name: shot.name.clone(),
+            weapon: shot.weapon.clone(),
+            spray_start_tick,
+            spray_index: shot.spray_index,
+            pitch,
+            yaw,
+            punch_pitch,
+            punch_yaw,
+            punch_vel_pitch,
+            punch_vel_yaw,
+            aim_pitch: aim.map(|(pitch, _)| pitch),
+            aim_yaw: aim.map(|(_, yaw)| yaw),
+            compensation_error,
+            hit: shot.hit,
+        });
+    }
+
+    // (stats, summed error, bullets with an error, hits)
+    let mut per_weapon: BTreeMap<(PlayerKey, String), (SprayControl, f32, i32, i32)> = BTreeMap::default();
+    for bullet in bullets.iter().filter(|b| b.spray_index > 1) {
+        let key = match PlayerKey::from_record(bullet.steamid, &bullet.name) {
+            Some(key) => key,
+            None => continue,
+        };
+        let (entry, error_sum, error_count, hits) = per_weapon.entry((key.clone(), bullet.weapon.clone())).or_insert_with(|| {
+            let control = SprayControl {
+                steamid: key.steamid,
+                name: bullet.name.clone(),
+                weapon: bullet.weapon.clone(),
+                ..Default::default()
+            };
+            (control, 0.0, 0, 0)
+        });
+        if bullet.spray_index == 2 {
+            entry.sprays += 1;
+        }
+        entry.bullets += 1;
+        if let Some(error) = bullet.compensation_error {
+            *error_sum += error;
+            *error_count += 1;
+        }
+        *hits += bullet.hit as i32;
+    }
+    let players = per_weapon
+        .into_values()
+        .map(|(mut control, error_sum, error_count, hits)| {
+            if error_count > 0 {
+                control.avg_compensation_error = Some(error_sum / error_count as f32);
+            }
+            control.spray_accuracy = hits as f32 / control.bullets as f32;
+            control
+        })
+        .collect();
+    Recoil { bullets, players }
+}
+
+impl Serialize for RecoilBullet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("RecoilBullet", 17)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("weapon", &self.weapon)?;
+        state.serialize_field("spray_start_tick", &self.spray_start_tick)?;
+        state.serialize_field("spray_index", &self.spray_index)?;
+        state.serialize_field("pitch", &self.pitch)?;
+        state.serialize_field("yaw", &self.yaw)?;
+        state.serialize_field("punch_pitch", &self.punch_pitch)?;
+        state.serialize_field("punch_yaw", &self.punch_yaw)?;
+        state.serialize_field("punch_vel_pitch", &self.punch_vel_pitch)?;
+        state.serialize_field("punch_vel_yaw", &self.punch_vel_yaw)?;
+        state.serialize_field("aim_pitch", &self.aim_pitch)?;
+        state.serialize_field("aim_yaw", &self.aim_yaw)?;
+        state.serialize_field("compensation_error", &self.compensation_error)?;
+        state.serialize_field("hit", &self.hit)?;
+        state.end()
+    }
+}
+impl Serialize for SprayControl {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("SprayControl", 7)?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("weapon", &self.weapon)?;
+        state.serialize_field("sprays", &self.sprays)?;
+        state.serialize_field("bullets", &self.bullets)?;
+        state.serialize_field("avg_compensation_error", &self.avg_compensation_error)?;
+        state.serialize_field("spray_accuracy", &self.spray_accuracy)?;
+        state.end()
+    }
+}
+impl Serialize for Recoil {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Recoil", 2)?;
+        state.serialize_field("bullets", &self.bullets)?;
+        state.serialize_field("players", &self.players)?;
+        state.end()
+    }
+}
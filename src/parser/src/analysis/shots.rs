@@ -0,0 +1,265 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field_i32;
+use crate::analysis::field_steamid;
+use crate::analysis::field_string;
+use crate::analysis::sorted_events;
+use crate::analysis::weapons::normalize_weapon_name;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::game_events::GameEvent;
+use ahash::AHashMap;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const SHOTS_EVENTS: &[&str] = &["weapon_fire", "player_hurt", "round_end", "round_start"];
+pub const SHOTS_PLAYER_PROPS: &[&str] = &["team_num"];
+pub const SHOTS_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// player_hurt comes on the same tick as the shot, the window covers the odd late one
+pub const HIT_WINDOW_TICKS: i32 = 4;
+// Shots further apart than this start a new spray
+pub const SPRAY_RESET_TICKS: i32 = 20;
+
+// Thrown, not shot, they show up in weapon_fire too
+const GRENADES: &[&str] = &["hegrenade", "flashbang", "smokegrenade", "molotov", "incgrenade", "decoy"];
+
+pub fn shots_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    event_inputs(SHOTS_EVENTS, SHOTS_PLAYER_PROPS, SHOTS_OTHER_PROPS, huffman_lookup_table)
+}
+
+/// Name of the hitgroup in player_hurt.
+pub fn hitgroup_name(hitgroup: i32) -> &'static str {
+    match hitgroup {
+        1 => "head",
+        2 => "chest",
+        3 => "stomach",
+        4 => "left_arm",
+        5 => "right_arm",
+        6 => "left_leg",
+        7 => "right_leg",
+        8 => "neck",
+        10 => "gear",
+        _ => "generic",
+    }
+}
+
+// weapon_fire has "weapon_ak47" while player_hurt has "ak47"
+fn weapon_name(weapon: &str) -> &str {
+    weapon.strip_prefix("weapon_").unwrap_or(weapon)
+}
+
+// Names can differ in case and suffix ("m4a1_silencer_off") between the events. Prefixes are
+// not enough, "m4a1" (M4A4) and "m4a1_silencer" (M4A1-S) are different weapons
+fn same_weapon(shot: &str, hurt: &str) -> bool {
+    normalize_weapon_name(shot) == normalize_weapon_name(hurt)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Shot {
+    pub tick: i32,
+    pub round: i32,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    pub weapon: String,
+    // 1 for the first bullet of a spray
+    pub spray_index: i32,
+    pub hit: bool,
+    // Shotguns and wallbangs can hit more than once
+    pub hits: i32,
+    pub damage: i32,
+    // Of the first hit
+    pub hitgroup: Option<String>,
+    pub headshot: bool,
+    pub victim_steamid: Option<u64>,
+    pub victim_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WeaponAccuracy {
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub weapon: String,
+    pub shots: i32,
+    // Shots that hit at least once
+    pub hits: i32,
+    pub headshots: i32,
+    pub damage: i32,
+    pub accuracy: f32,
+    pub headshots_per_hit: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Shots {
+    pub shots: Vec<Shot>,
+    pub accuracy: Vec<WeaponAccuracy>,
+}
+
+/// Matches every player_hurt to the latest weapon_fire of the attacker with the same weapon at
+/// most HIT_WINDOW_TICKS before it, which makes every shot a hit (with the hitgroup and damage)
+/// or a miss. Shots get their index within the spray and are summed into accuracy and
+/// headshots per hit per player per weapon. Grenades are left out.
+/// Expects the events from shots_inputs().
+pub fn shots(events: &[GameEvent]) -> Shots {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+    let first_tick = rounds.round_starts.first().map(|(tick, _)| *tick).unwrap_or(0);
+
+    let mut shots: Vec<Shot> = vec![];
+    // Indicies of the shots of every player, in order
+    let mut player_shots: AHashMap<PlayerKey, Vec<usize>> = AHashMap::default();
+    for event in events.iter().filter(|e| e.tick >= first_tick) {
+        match event.name.as_str() {
+            "weapon_fire" => {
+                let weapon = match field_string(event, "weapon") {
+                    Some(w) if !GRENADES.contains(&weapon_name(w)) => weapon_name(w).to_string(),
+                    _ => continue,
+                };
+                let shooter = match PlayerKey::from_event(event, "user") {
+                    Some(key) => key,
+                    None => continue,
+                };
+                let previous_shots = player_shots.entry(shooter).or_default();
+                let spray_index = match previous_shots.last().map(|idx| &shots[*idx]) {
+                    Some(last) if last.weapon == weapon && event.tick - last.tick <= SPRAY_RESET_TICKS => last.spray_index + 1,
+                    _ => 1,
+                };
+                previous_shots.push(shots.len());
+                shots.push(Shot {
+                    tick: event.tick,
+                    round: rounds.round_at(event.tick),
+                    steamid: field_steamid(event, "user"),
+                    name: field_string(event, "user_name").map(|s| s.to_string()),
+                    weapon,
+                    spray_index,
+                    ..Default::default()
+                });
+            }
+            "player_hurt" => {
+                let (attacker, weapon) = match (PlayerKey::from_event(event, "attacker"), field_string(event, "weapon")) {
+                    (Some(attacker), Some(weapon)) => (attacker, weapon_name(weapon)),
+                    _ => continue,
+                };
+                let shot_idx = player_shots.get(&attacker).and_then(|indicies| {
+                    indicies
+                        .iter()
+                        .rev()
+                        .copied()
+                        .take_while(|idx| event.tick - shots[*idx].tick <= HIT_WINDOW_TICKS)
+                        .find(|idx| shots[*idx].tick <= event.tick && same_weapon(&shots[*idx].weapon, weapon))
+                });
+                let shot = match shot_idx {
+                    Some(idx) => &mut shots[idx],
+                    None => continue,
+                };
+                let hitgroup = field_i32(event, "hitgroup").map(hitgroup_name);
+                if !shot.hit {
+                    shot.hitgroup = hitgroup.map(|h| h.to_string());
+                    shot.victim_steamid = field_steamid(event, "user");
+                    shot.victim_name = field_string(event, "user_name").map(|s| s.to_string());
+                }
+                shot.hit = true;
+                shot.hits += 1;
+                shot.damage += field_i32(event, "dmg_health").unwrap_or(0);
+                shot.headshot |= hitgroup == Some("head");
+            }
+            _ => {}
+        }
+    }
+
+    let mut per_weapon: BTreeMap<(PlayerKey, String), WeaponAccuracy> = BTreeMap::default();
+    for shot in &shots {
+        let key = match PlayerKey::from_record(shot.steamid, &shot.name) {
+            Some(key) => key,
+            None => continue,
+        };
+        let entry = per_weapon
+            .entry((key.clone(), shot.weapon.clone()))
+            .or_insert_with(|| WeaponAccuracy {
+                steamid: key.steamid,
+                name: shot.name.clone(),
+                weapon: shot.weapon.clone(),
+                ..Default::default()
+            });
+        entry.shots += 1;
+        entry.hits += shot.hit as i32;
+        entry.headshots += shot.headshot as i32;
+        entry.damage += shot.damage;
+    }
+    let mut accuracy: Vec<WeaponAccuracy> = per_weapon.into_values().collect();
+    for entry in accuracy.iter_mut() {
+        entry.accuracy = entry.hits as f32 / entry.shots as f32;
+        if entry.hits > 0 {
+            entry.headshots_per_hit = entry.headshots as f32 / entry.hits as f32;
+        }
+    }
+    Shots { shots, accuracy }
+}
+
+impl Serialize for Shot {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Shot", 13)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("weapon", &self.weapon)?;
+        state.serialize_field("spray_index", &self.spray_index)?;
+        state.serialize_field("hit", &self.hit)?;
+        state.serialize_field("hits", &self.hits)?;
+        state.serialize_field("damage", &self.damage)?;
+        state.serialize_field("hitgroup", &self.hitgroup)?;
+        state.serialize_field("headshot", &self.headshot)?;
+        state.serialize_field("victim_steamid", &self.victim_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("victim_name", &self.victim_name)?;
+        state.end()
+    }
+}
+impl Serialize for WeaponAccuracy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("WeaponAccuracy", 9)?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("weapon", &self.weapon)?;
+        state.serialize_field("shots", &self.shots)?;
+        state.serialize_field("hits", &self.hits)?;
+        state.serialize_field("headshots", &self.headshots)?;
+        state.serialize_field("damage", &self.damage)?;
+        state.serialize_field("accuracy", &self.accuracy)?;
+        state.serialize_field("headshots_per_hit", &self.headshots_per_hit)?;
+        state.end()
+    }
+}
+impl Serialize for Shots {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Shots", 2)?;
+        state.serialize_field("shots", &self.shots)?;
+        state.serialize_field("accuracy", &self.accuracy)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::shots::*;
+
+    #[test]
+    fn test_same_weapon() {
+        assert!(same_weapon("m4a1_silencer", "m4a1_silencer"));
+        assert!(same_weapon("m4a1_silencer_off", "m4a1_silencer"));
+        assert!(!same_weapon("m4a1", "m4a1_silencer"));
+        assert!(!same_weapon("m4a1_silencer", "m4a1"));
+        assert!(!same_weapon("usp_silencer", "hkp2000"));
+    }
+}
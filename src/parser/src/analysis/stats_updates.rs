@@ -0,0 +1,112 @@
+use crate::analysis::event_inputs;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::game_events::GameEvent;
+use crate::second_pass::parser_settings::MatchStatsUpdateRecord;
+use crate::second_pass::parser_settings::PlayerStatsUpdateRecord;
+use ahash::AHashMap;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+
+// The updates are always collected, the events are only for the round numbers
+pub const STATS_UPDATES_EVENTS: &[&str] = &["round_start", "round_end"];
+pub const STATS_UPDATES_PLAYER_PROPS: &[&str] = &[];
+pub const STATS_UPDATES_OTHER_PROPS: &[&str] = &[];
+
+pub fn stats_updates_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    event_inputs(
+        STATS_UPDATES_EVENTS,
+        STATS_UPDATES_PLAYER_PROPS,
+        STATS_UPDATES_OTHER_PROPS,
+        huffman_lookup_table,
+    )
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatsUpdate {
+    pub tick: i32,
+    pub round: i32,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    pub stat_id: Option<i32>,
+    pub stat: Option<String>,
+    pub delta: i32,
+    // Sum of the deltas of the player for the stat so far
+    pub value: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatsUpdates {
+    pub player_stats: Vec<StatsUpdate>,
+    pub match_stats: Vec<MatchStatsUpdateRecord>,
+}
+
+/// The counters the server keeps for every player (CS_UM_PlayerStatsUpdate) as a timeline with
+/// the running value of every stat, and the raw CS_UM_MatchStatsUpdate strings. Updates of
+/// players that couldn't be resolved from the handle have no steamid and are summed together.
+/// Expects the events and stats updates from stats_updates_inputs().
+pub fn stats_updates(
+    events: &[GameEvent],
+    player_updates: &[PlayerStatsUpdateRecord],
+    match_updates: &[MatchStatsUpdateRecord],
+) -> StatsUpdates {
+    let rounds = RoundTimeline::from_events(events);
+    let mut player_updates = player_updates.to_vec();
+    player_updates.sort_by_key(|update| update.tick);
+
+    let mut totals: AHashMap<(Option<PlayerKey>, Option<i32>), i32> = AHashMap::default();
+    let mut player_stats = Vec::with_capacity(player_updates.len());
+    for update in player_updates {
+        let delta = update.delta.unwrap_or(0);
+        let key = (PlayerKey::from_record(update.steamid, &update.name), update.stat_id);
+        let value = totals.entry(key).or_default();
+        *value += delta;
+        player_stats.push(StatsUpdate {
+            tick: update.tick,
+            round: rounds.round_at(update.tick),
+            steamid: update.steamid,
+            name: update.name,
+            stat_id: update.stat_id,
+            stat: update.stat,
+            delta,
+            value: *value,
+        });
+    }
+    let mut match_stats = match_updates.to_vec();
+    match_stats.sort_by_key(|update| update.tick);
+    StatsUpdates {
+        player_stats,
+        match_stats,
+    }
+}
+
+impl Serialize for StatsUpdate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("StatsUpdate", 8)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("stat_id", &self.stat_id)?;
+        state.serialize_field("stat", &self.stat)?;
+        state.serialize_field("delta", &self.delta)?;
+        state.serialize_field("value", &self.value)?;
+        state.end()
+    }
+}
+impl Serialize for StatsUpdates {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("StatsUpdates", 2)?;
+        state.serialize_field("player_stats", &self.player_stats)?;
+        state.serialize_field("match_stats", &self.match_stats)?;
+        state.end()
+    }
+}
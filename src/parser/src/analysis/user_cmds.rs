@@ -0,0 +1,21 @@
+use crate::analysis::event_inputs;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+
+// Entities are only needed for the steamid of the player
+pub const USER_CMDS_EVENTS: &[&str] = &[];
+pub const USER_CMDS_PLAYER_PROPS: &[&str] = &[];
+pub const USER_CMDS_OTHER_PROPS: &[&str] = &[];
+
+/// Inputs for output.user_cmds: every DEM_UserCmd of a POV demo with the view angles, movement,
+/// buttons, subtick moves and the attack tick of the command. Other demos have none.
+pub fn user_cmds_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(
+        USER_CMDS_EVENTS,
+        USER_CMDS_PLAYER_PROPS,
+        USER_CMDS_OTHER_PROPS,
+        huffman_lookup_table,
+    )?;
+    inputs.parse_user_cmds = true;
+    Ok(inputs)
+}
@@ -0,0 +1,206 @@
+use crate::analysis::duels::Death;
+use crate::analysis::event_inputs;
+use crate::analysis::field_string;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::collect_data::SpottedRecord;
+use crate::second_pass::game_events::GameEvent;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const VISIBILITY_EVENTS: &[&str] = &["player_death", "round_end", "round_start"];
+// Asking for the spotted mask in events turns on collecting the intervals
+pub const VISIBILITY_PLAYER_PROPS: &[&str] = &["team_num", "approximate_spotted_by"];
+pub const VISIBILITY_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+pub fn visibility_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(
+        VISIBILITY_EVENTS,
+        VISIBILITY_PLAYER_PROPS,
+        VISIBILITY_OTHER_PROPS,
+        huffman_lookup_table,
+    )?;
+    inputs.collect_spotted = true;
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpottedInterval {
+    pub observer_steamid: Option<u64>,
+    pub observer_name: Option<String>,
+    pub target_steamid: Option<u64>,
+    pub target_name: Option<String>,
+    pub round: i32,
+    pub start_tick: i32,
+    pub end_tick: i32,
+    pub duration: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KillVisibility {
+    pub tick: i32,
+    pub round: i32,
+    pub attacker_steamid: Option<u64>,
+    pub attacker_name: Option<String>,
+    pub victim_steamid: Option<u64>,
+    pub victim_name: Option<String>,
+    // Start of the last time the attacker spotted the victim before the kill
+    pub attacker_saw_victim_tick: Option<i32>,
+    pub victim_saw_attacker_tick: Option<i32>,
+    // Seconds from the attacker spotting the victim to the kill
+    pub time_to_kill: Option<f32>,
+    // "attacker", "victim" or "same_tick"
+    pub spotted_first: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Visibility {
+    pub intervals: Vec<SpottedInterval>,
+    pub kills: Vec<KillVisibility>,
+}
+
+/// Turns the spotted records into "A saw B" intervals (merged where the parser split them up)
+/// and, for every kill between enemies, looks up when the attacker and the victim last started
+/// seeing each other to get who spotted who first and the time from sight to kill.
+/// Expects the events and spotted records from visibility_inputs().
+pub fn visibility(events: &[GameEvent], spotted: &[SpottedRecord]) -> Visibility {
+    let rounds = RoundTimeline::from_events(events);
+    let events = sorted_events(events);
+    let first_tick = rounds.round_starts.first().map(|(tick, _)| *tick).unwrap_or(0);
+
+    let mut per_pair: BTreeMap<(PlayerKey, PlayerKey), Vec<&SpottedRecord>> = BTreeMap::default();
+    for record in spotted.iter().filter(|r| r.end_tick >= first_tick) {
+        let observer = PlayerKey::from_record(record.observer_steamid, &record.observer_name);
+        let target = PlayerKey::from_record(record.target_steamid, &record.target_name);
+        if let (Some(observer), Some(target)) = (observer, target) {
+            if observer != target {
+                per_pair.entry((observer, target)).or_default().push(record);
+            }
+        }
+    }
+    let mut intervals: Vec<SpottedInterval> = vec![];
+    let mut pair_intervals: BTreeMap<(PlayerKey, PlayerKey), Vec<(i32, i32)>> = BTreeMap::default();
+    for (pair, mut records) in per_pair {
+        records.sort_by_key(|r| r.start_tick);
+        let mut merged: Vec<(i32, i32)> = vec![];
+        for record in &records {
+            match merged.last_mut() {
+                Some((_, end)) if record.start_tick <= *end + 1 => *end = (*end).max(record.end_tick),
+                _ => merged.push((record.start_tick.max(first_tick), record.end_tick)),
+            }
+        }
+        let first = records[0];
+        for (start_tick, end_tick) in &merged {
+            intervals.push(SpottedInterval {
+                observer_steamid: first.observer_steamid,
+                observer_name: first.observer_name.clone(),
+                target_steamid: first.target_steamid,
+                target_name: first.target_name.clone(),
+                round: rounds.round_at(*start_tick),
+                start_tick: *start_tick,
+                end_tick: *end_tick,
+                duration: (end_tick - start_tick + 1) as f32 / TICKRATE,
+            });
+        }
+        pair_intervals.insert(pair, merged);
+    }
+    intervals.sort_by_key(|i| (i.start_tick, i.end_tick));
+
+    let last_sight = |observer: &PlayerKey, target: &PlayerKey, tick: i32, round: i32| {
+        pair_intervals
+            .get(&(observer.clone(), target.clone()))
+            .and_then(|v| {
+                v.iter()
+                    .rev()
+                    .find(|(start, _)| *start <= tick && rounds.round_at(*start) == round)
+            })
+            .map(|(start, _)| *start)
+    };
+    let mut kills = vec![];
+    for event in events.iter().filter(|e| e.name == "player_death") {
+        let death = match Death::from_event(event, rounds.round_at(event.tick)) {
+            Some(d) if d.is_enemy_kill() => d,
+            _ => continue,
+        };
+        let attacker = match &death.attacker {
+            Some(a) => a,
+            None => continue,
+        };
+        let attacker_saw_victim_tick = last_sight(attacker, &death.victim, event.tick, death.round);
+        let victim_saw_attacker_tick = last_sight(&death.victim, attacker, event.tick, death.round);
+        let spotted_first = match (attacker_saw_victim_tick, victim_saw_attacker_tick) {
+            (Some(a), Some(v)) if a < v => Some("attacker"),
+            (Some(a), Some(v)) if a > v => Some("victim"),
+            (Some(_), Some(_)) => Some("same_tick"),
+            (Some(_), None) => Some("attacker"),
+            (None, Some(_)) => Some("victim"),
+            (None, None) => None,
+        };
+        kills.push(KillVisibility {
+            tick: event.tick,
+            round: death.round,
+            attacker_steamid: Some(attacker.steamid),
+            attacker_name: field_string(event, "attacker_name").map(|s| s.to_string()),
+            victim_steamid: Some(death.victim.steamid),
+            victim_name: field_string(event, "user_name").map(|s| s.to_string()),
+            attacker_saw_victim_tick,
+            victim_saw_attacker_tick,
+            time_to_kill: attacker_saw_victim_tick.map(|t| (event.tick - t) as f32 / TICKRATE),
+            spotted_first: spotted_first.map(|s| s.to_string()),
+        });
+    }
+    Visibility { intervals, kills }
+}
+
+impl Serialize for SpottedInterval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("SpottedInterval", 8)?;
+        state.serialize_field("observer_steamid", &self.observer_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("observer_name", &self.observer_name)?;
+        state.serialize_field("target_steamid", &self.target_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("target_name", &self.target_name)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("start_tick", &self.start_tick)?;
+        state.serialize_field("end_tick", &self.end_tick)?;
+        state.serialize_field("duration", &self.duration)?;
+        state.end()
+    }
+}
+impl Serialize for KillVisibility {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("KillVisibility", 10)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("attacker_steamid", &self.attacker_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("attacker_name", &self.attacker_name)?;
+        state.serialize_field("victim_steamid", &self.victim_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("victim_name", &self.victim_name)?;
+        state.serialize_field("attacker_saw_victim_tick", &self.attacker_saw_victim_tick)?;
+        state.serialize_field("victim_saw_attacker_tick", &self.victim_saw_attacker_tick)?;
+        state.serialize_field("time_to_kill", &self.time_to_kill)?;
+        state.serialize_field("spotted_first", &self.spotted_first)?;
+        state.end()
+    }
+}
+impl Serialize for Visibility {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Visibility", 2)?;
+        state.serialize_field("intervals", &self.intervals)?;
+        state.serialize_field("kills", &self.kills)?;
+        state.end()
+    }
+}
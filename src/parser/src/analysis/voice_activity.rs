@@ -0,0 +1,218 @@
+use crate::analysis::event_inputs;
+use crate::analysis::RoundTimeline;
+use crate::analysis::TICKRATE;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::game_events::GameEvent;
+use crate::second_pass::parser_settings::PlayerEndMetaData;
+use crate::voice::voice_key;
+use crate::voice::VoiceKey;
+use crate::voice::VoiceRecord;
+use ahash::AHashMap;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+
+// Voice data is always collected, the events are only for the round numbers
+pub const VOICE_ACTIVITY_EVENTS: &[&str] = &["round_start", "round_end"];
+pub const VOICE_ACTIVITY_PLAYER_PROPS: &[&str] = &[];
+pub const VOICE_ACTIVITY_OTHER_PROPS: &[&str] = &[];
+
+// A pause longer than this many seconds starts a new segment
+pub const DEFAULT_VOICE_GAP_SECONDS: f32 = 0.5;
+
+pub fn voice_activity_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    event_inputs(
+        VOICE_ACTIVITY_EVENTS,
+        VOICE_ACTIVITY_PLAYER_PROPS,
+        VOICE_ACTIVITY_OTHER_PROPS,
+        huffman_lookup_table,
+    )
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VoiceSegment {
+    pub round: i32,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    // Client slot, set when the demo has no steamid for the voice
+    pub client: Option<i32>,
+    pub start_tick: i32,
+    pub end_tick: i32,
+    pub duration: f32,
+    // Voice messages in the segment
+    pub packets: i32,
+    // Bytes of compressed voice, roughly how much was said
+    pub bytes: usize,
+}
+
+struct OpenSegment {
+    segment: VoiceSegment,
+    section: Option<u32>,
+}
+
+/// Who spoke when, as segments of continuous voice per player. Consecutive voice messages
+/// belong to the same segment unless the player was silent for more than gap_seconds or the
+/// voice section changes (the client starts a new transmission). Segments are split at the
+/// round ends so each belongs to one round. Nothing is decoded, so this is cheap compared to
+/// voice_to_wav. Expects the events and voice data from voice_activity_inputs().
+pub fn voice_activity(
+    events: &[GameEvent],
+    voice_data: &[VoiceRecord],
+    players: &[PlayerEndMetaData],
+    gap_seconds: f32,
+) -> Vec<VoiceSegment> {
+    let rounds = RoundTimeline::from_events(events);
+    let gap = (gap_seconds * TICKRATE) as i32;
+    let names: AHashMap<u64, &String> = players.iter().filter_map(|p| Some((p.steamid?, p.name.as_ref()?))).collect();
+
+    let mut records: Vec<&VoiceRecord> = voice_data.iter().filter(|r| r.msg.audio.is_some()).collect();
+    records.sort_by_key(|r| r.tick);
+
+    let mut open: AHashMap<VoiceKey, OpenSegment> = AHashMap::default();
+    let mut segments = vec![];
+    for record in records {
+        let msg = &record.msg;
+        let key = voice_key(msg);
+        let tick = record.tick;
+        let round = rounds.round_at(tick);
+        let section = msg.audio.section_number;
+        let bytes = msg.audio.voice_data().len();
+
+        if let Some(current) = open.get_mut(&key) {
+            let same_section = current.section.is_none() || section.is_none() || current.section == section;
+            if tick - current.segment.end_tick <= gap && same_section && current.segment.round == round {
+                current.segment.end_tick = tick;
+                current.segment.packets += 1;
+                current.segment.bytes += bytes;
+                continue;
+            }
+        }
+        let new = OpenSegment {
+            segment: VoiceSegment {
+                round,
+                steamid: key.0,
+                name: key.0.and_then(|steamid| names.get(&steamid).map(|name| name.to_string())),
+                client: key.1,
+                start_tick: tick,
+                end_tick: tick,
+                duration: 0.0,
+                packets: 1,
+                bytes,
+            },
+            section,
+        };
+        if let Some(finished) = open.insert(key, new) {
+            segments.push(finished.segment);
+        }
+    }
+    segments.extend(open.into_values().map(|open| open.segment));
+    for segment in segments.iter_mut() {
+        segment.duration = (segment.end_tick - segment.start_tick + 1) as f32 / TICKRATE;
+    }
+    segments.sort_by_key(|s| (s.start_tick, s.steamid, s.client));
+    segments
+}
+
+impl Serialize for VoiceSegment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("VoiceSegment", 9)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("client", &self.client)?;
+        state.serialize_field("start_tick", &self.start_tick)?;
+        state.serialize_field("end_tick", &self.end_tick)?;
+        state.serialize_field("duration", &self.duration)?;
+        state.serialize_field("packets", &self.packets)?;
+        state.serialize_field("bytes", &self.bytes)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::test_event;
+    use crate::analysis::voice_activity::*;
+    use crate::second_pass::variants::Variant;
+    use csgoproto::netmessages::CMsgVoiceAudio;
+    use csgoproto::netmessages::CSVCMsg_VoiceData;
+    use protobuf::MessageField;
+
+    fn record(tick: i32, xuid: u64, client: i32, section: Option<u32>, bytes: usize) -> VoiceRecord {
+        let audio = CMsgVoiceAudio {
+            voice_data: Some(vec![0; bytes]),
+            section_number: section,
+            ..Default::default()
+        };
+        VoiceRecord {
+            tick,
+            msg: CSVCMsg_VoiceData {
+                audio: MessageField::some(audio),
+                client: Some(client),
+                xuid: Some(xuid),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_voice_activity() {
+        let events = vec![
+            test_event("round_start", 0, vec![("round", Variant::I32(1))]),
+            test_event("round_end", 100, vec![("round", Variant::I32(1))]),
+            test_event("round_start", 120, vec![("round", Variant::I32(2))]),
+        ];
+        let voice_data = vec![
+            record(10, 1, 3, Some(1), 10),
+            record(20, 1, 3, Some(1), 10),
+            record(30, 1, 3, Some(1), 10),
+            // No audio, skipped
+            VoiceRecord {
+                tick: 40,
+                msg: CSVCMsg_VoiceData {
+                    xuid: Some(1),
+                    ..Default::default()
+                },
+            },
+            // Silent for longer than the gap
+            record(80, 1, 3, Some(1), 10),
+            // New transmission
+            record(85, 1, 3, Some(2), 20),
+            record(110, 1, 3, Some(2), 20),
+            // Round 2 starts at 120
+            record(125, 1, 3, Some(2), 5),
+            // No steamid, keyed by the client slot
+            record(20, 0, 4, None, 7),
+        ];
+        let players = vec![PlayerEndMetaData {
+            steamid: Some(1),
+            name: Some("player".to_string()),
+            team_number: Some(2),
+            accolade: None,
+        }];
+        let segments = voice_activity(&events, &voice_data, &players, DEFAULT_VOICE_GAP_SECONDS);
+
+        let spans: Vec<(i32, i32, i32, i32, usize)> = segments
+            .iter()
+            .map(|s| (s.round, s.start_tick, s.end_tick, s.packets, s.bytes))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (1, 10, 30, 3, 30),
+                (1, 20, 20, 1, 7),
+                (1, 80, 80, 1, 10),
+                (1, 85, 110, 2, 40),
+                (2, 125, 125, 1, 5),
+            ]
+        );
+        assert_eq!(segments[0].duration, 21.0 / 64.0);
+        assert_eq!(segments[0].name.as_deref(), Some("player"));
+        assert_eq!((segments[0].steamid, segments[0].client), (Some(1), None));
+        assert_eq!((segments[1].steamid, segments[1].client), (None, Some(4)));
+        assert_eq!(segments[1].name, None);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::analysis::event_inputs;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+
+// The weapons are read from the entities, any event works for turning on the parsing
+pub const WEAPON_SKINS_EVENTS: &[&str] = &["round_start"];
+// Asking for active_weapon_skin in events turns on collecting the skins of every weapon
+pub const WEAPON_SKINS_PLAYER_PROPS: &[&str] = &["active_weapon_skin"];
+pub const WEAPON_SKINS_OTHER_PROPS: &[&str] = &[];
+
+/// Inputs for output.weapon_skins: a row for every weapon entity every time it gets a new owner,
+/// with the paint kit, wear, seed, StatTrak count, stickers, charm and name tag of the weapon.
+pub fn weapon_skins_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    let mut inputs = event_inputs(
+        WEAPON_SKINS_EVENTS,
+        WEAPON_SKINS_PLAYER_PROPS,
+        WEAPON_SKINS_OTHER_PROPS,
+        huffman_lookup_table,
+    )?;
+    inputs.collect_weapon_skins = true;
+    Ok(inputs)
+}
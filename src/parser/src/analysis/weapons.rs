@@ -0,0 +1,218 @@
+use crate::analysis::event_inputs;
+use crate::analysis::field_bool;
+use crate::analysis::field_i32;
+use crate::analysis::field_string;
+use crate::analysis::shots::shots;
+use crate::analysis::sorted_events;
+use crate::analysis::PlayerKey;
+use crate::analysis::RoundTimeline;
+use crate::first_pass::parser_settings::ParserInputs;
+use crate::first_pass::read_bits::DemoParserError;
+use crate::second_pass::game_events::GameEvent;
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const WEAPONS_EVENTS: &[&str] = &["weapon_fire", "player_hurt", "player_death", "round_end", "round_start"];
+pub const WEAPONS_PLAYER_PROPS: &[&str] = &["team_num", "active_weapon_name"];
+pub const WEAPONS_OTHER_PROPS: &[&str] = &["is_warmup_period"];
+
+// Names in WEAPINDICIES (props and inventory) -> names in events without the "weapon_" prefix
+const WEAPON_DISPLAY_NAMES: &[(&str, &str)] = &[
+    ("Desert Eagle", "deagle"),
+    ("Dual Berettas", "elite"),
+    ("Five-SeveN", "fiveseven"),
+    ("Glock-18", "glock"),
+    ("AK-47", "ak47"),
+    ("Galil AR", "galilar"),
+    ("M4A4", "m4a1"),
+    ("MAC-10", "mac10"),
+    ("MP5-SD", "mp5sd"),
+    ("UMP-45", "ump45"),
+    ("PP-Bizon", "bizon"),
+    ("MAG-7", "mag7"),
+    ("Sawed-Off", "sawedoff"),
+    ("Tec-9", "tec9"),
+    ("Zeus x27", "taser"),
+    ("P2000", "hkp2000"),
+    ("Scar-20", "scar20"),
+    ("SG 553", "sg556"),
+    ("SSG 08", "ssg08"),
+    ("High Explosive Grenade", "hegrenade"),
+    ("Smoke Grenade", "smokegrenade"),
+    ("Decoy Grenade", "decoy"),
+    ("Incendiary Grenade", "incgrenade"),
+    ("M4A1-S", "m4a1_silencer"),
+    ("USP-S", "usp_silencer"),
+    ("CZ75-Auto", "cz75a"),
+    ("R8 Revolver", "revolver"),
+];
+
+pub fn weapons_inputs(huffman_lookup_table: &Vec<(u8, u8)>) -> Result<ParserInputs<'_>, DemoParserError> {
+    event_inputs(
+        WEAPONS_EVENTS,
+        WEAPONS_PLAYER_PROPS,
+        WEAPONS_OTHER_PROPS,
+        huffman_lookup_table,
+    )
+}
+
+/// One name per weapon no matter where it comes from: "weapon_m4a1_silencer" (weapon_fire),
+/// "m4a1_silencer" (player_hurt/player_death), "m4a1_silencer_off" and "M4A1-S" (WEAPINDICIES)
+/// all become "m4a1_silencer". Every knife is "knife". The fire of molotovs and incendiaries is
+/// "inferno" in damage and can't be told apart, so all three are "molotov".
+pub fn normalize_weapon_name(weapon: &str) -> String {
+    if let Some((_, name)) = WEAPON_DISPLAY_NAMES.iter().find(|(display, _)| *display == weapon) {
+        return normalize_weapon_name(name);
+    }
+    let lower = weapon.to_lowercase();
+    let name = lower.strip_prefix("weapon_").unwrap_or(&lower);
+    let name = name.strip_suffix("_off").unwrap_or(name);
+    match name {
+        "inferno" | "incgrenade" => "molotov".to_string(),
+        "planted_c4" => "c4".to_string(),
+        _ if name.starts_with("knife") || name.starts_with("bayonet") => "knife".to_string(),
+        _ => name.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerWeaponStats {
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub weapon: String,
+    pub kills: i32,
+    pub headshot_kills: i32,
+    // Times the player was killed with this weapon
+    pub deaths: i32,
+    pub damage: i32,
+    // Shots and hits as in shots(), grenades only get kills and damage
+    pub shots: i32,
+    pub hits: i32,
+    pub headshots: i32,
+    pub accuracy: Option<f32>,
+}
+
+fn is_enemy(event: &GameEvent) -> bool {
+    match (field_i32(event, "attacker_team_num"), field_i32(event, "user_team_num")) {
+        (Some(attacker), Some(victim)) => attacker != victim,
+        _ => true,
+    }
+}
+
+fn entry(
+    per_weapon: &mut BTreeMap<(PlayerKey, String), PlayerWeaponStats>,
+    key: PlayerKey,
+    name: Option<String>,
+    weapon: String,
+) -> &mut PlayerWeaponStats {
+    per_weapon
+        .entry((key.clone(), weapon.clone()))
+        .or_insert_with(|| PlayerWeaponStats {
+            steamid: key.steamid,
+            name,
+            weapon,
+            ..Default::default()
+        })
+}
+
+/// Sums kills, damage, shots, hits, headshots and deaths per player per weapon with the weapon
+/// names normalized by normalize_weapon_name(). Team kills, team damage and self damage are
+/// left out. Expects the events from weapons_inputs().
+pub fn weapons(events: &[GameEvent]) -> Vec<PlayerWeaponStats> {
+    let rounds = RoundTimeline::from_events(events);
+    let first_tick = rounds.round_starts.first().map(|(tick, _)| *tick).unwrap_or(0);
+    let shots = shots(events);
+    let events = sorted_events(events);
+
+    let mut per_weapon: BTreeMap<(PlayerKey, String), PlayerWeaponStats> = BTreeMap::default();
+    for shot in &shots.shots {
+        let key = match PlayerKey::from_record(shot.steamid, &shot.name) {
+            Some(key) => key,
+            None => continue,
+        };
+        let stats = entry(&mut per_weapon, key, shot.name.clone(), normalize_weapon_name(&shot.weapon));
+        stats.shots += 1;
+        stats.hits += shot.hit as i32;
+        stats.headshots += shot.headshot as i32;
+    }
+    for event in events.iter().filter(|e| e.tick >= first_tick) {
+        let weapon = match field_string(event, "weapon") {
+            Some(weapon) if !weapon.is_empty() => normalize_weapon_name(weapon),
+            _ => match field_string(event, "attacker_active_weapon_name") {
+                Some(weapon) => normalize_weapon_name(weapon),
+                None => continue,
+            },
+        };
+        let attacker = PlayerKey::from_event(event, "attacker");
+        let victim = PlayerKey::from_event(event, "user");
+        let by_enemy = attacker.is_some() && attacker != victim && is_enemy(event);
+        match event.name.as_str() {
+            "player_hurt" if by_enemy => {
+                if let Some(key) = attacker {
+                    let name = field_string(event, "attacker_name").map(|s| s.to_string());
+                    entry(&mut per_weapon, key, name, weapon).damage += field_i32(event, "dmg_health").unwrap_or(0);
+                }
+            }
+            "player_death" => {
+                if let Some(key) = victim {
+                    let name = field_string(event, "user_name").map(|s| s.to_string());
+                    entry(&mut per_weapon, key, name, weapon.clone()).deaths += 1;
+                }
+                if let (true, Some(key)) = (by_enemy, attacker) {
+                    let name = field_string(event, "attacker_name").map(|s| s.to_string());
+                    let stats = entry(&mut per_weapon, key, name, weapon);
+                    stats.kills += 1;
+                    stats.headshot_kills += field_bool(event, "headshot").unwrap_or(false) as i32;
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut stats: Vec<PlayerWeaponStats> = per_weapon.into_values().collect();
+    for entry in stats.iter_mut().filter(|s| s.shots > 0) {
+        entry.accuracy = Some(entry.hits as f32 / entry.shots as f32);
+    }
+    stats
+}
+
+impl Serialize for PlayerWeaponStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PlayerWeaponStats", 11)?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("weapon", &self.weapon)?;
+        state.serialize_field("kills", &self.kills)?;
+        state.serialize_field("headshot_kills", &self.headshot_kills)?;
+        state.serialize_field("deaths", &self.deaths)?;
+        state.serialize_field("damage", &self.damage)?;
+        state.serialize_field("shots", &self.shots)?;
+        state.serialize_field("hits", &self.hits)?;
+        state.serialize_field("headshots", &self.headshots)?;
+        state.serialize_field("accuracy", &self.accuracy)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::weapons::*;
+
+    #[test]
+    fn test_normalize_weapon_name() {
+        assert_eq!(normalize_weapon_name("weapon_m4a1_silencer"), "m4a1_silencer");
+        assert_eq!(normalize_weapon_name("m4a1_silencer_off"), "m4a1_silencer");
+        assert_eq!(normalize_weapon_name("M4A1-S"), "m4a1_silencer");
+        assert_eq!(normalize_weapon_name("m4a1"), "m4a1");
+        assert_eq!(normalize_weapon_name("USP-S"), "usp_silencer");
+        assert_eq!(normalize_weapon_name("weapon_AK47"), "ak47");
+        assert_eq!(normalize_weapon_name("weapon_knife_karambit"), "knife");
+        assert_eq!(normalize_weapon_name("bayonet"), "knife");
+        assert_eq!(normalize_weapon_name("inferno"), "molotov");
+        assert_eq!(normalize_weapon_name("Incendiary Grenade"), "molotov");
+        assert_eq!(normalize_weapon_name("planted_c4"), "c4");
+    }
+}
@@ -303,6 +303,15 @@ pub fn _create_ge_tests() {
         wanted_players: vec![],
         wanted_ticks: (0..5).into_iter().map(|x| x * 10000).collect_vec(),
         parse_projectiles: true,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: false,
         count_props: false,
         only_convars: false,
@@ -672,6 +681,15 @@ pub fn _create_tests() {
         wanted_players: vec![],
         wanted_ticks: (0..5).into_iter().map(|x| x * 10000).collect_vec(),
         parse_projectiles: true,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: false,
         count_props: false,
         only_convars: false,
@@ -1029,6 +1047,15 @@ fn create_data() -> (DemoOutput, PropController, BTreeMap<String, Vec<GameEvent>
         wanted_players: vec![],
         wanted_ticks: (0..5).into_iter().map(|x| x * 10000).collect_vec(),
         parse_projectiles: true,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: false,
         count_props: false,
         only_convars: false,
@@ -1054,6 +1081,15 @@ fn create_data() -> (DemoOutput, PropController, BTreeMap<String, Vec<GameEvent>
         wanted_players: vec![],
         wanted_ticks: (0..5).into_iter().map(|x| x * 10000).collect_vec(),
         parse_projectiles: true,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: false,
         count_props: false,
         only_convars: false,
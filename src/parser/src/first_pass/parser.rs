@@ -11,17 +11,21 @@ use crate::first_pass::stringtables::UserInfo;
 use crate::maps::demo_cmd_type_from_int;
 use crate::maps::netmessage_type_from_int;
 use crate::maps::NetmessageType::*;
+use crate::radar::MapOverview;
 use crate::second_pass::decoder::QfMapper;
 use crate::second_pass::other_netmessages::Class;
+use crate::zones::Zones;
 use ahash::AHashMap;
 use ahash::AHashSet;
 use csgoproto::demo::CDemoFullPacket;
 use csgoproto::demo::CDemoPacket;
 use csgoproto::demo::EDemoCommands;
 use csgoproto::demo::EDemoCommands::*;
-use csgoproto::demo::{CDemoClassInfo, CDemoFileHeader};
+use csgoproto::demo::{CDemoClassInfo, CDemoFileHeader, CDemoFileInfo};
 use csgoproto::netmessages::csvcmsg_game_event_list::Descriptor_t;
 use csgoproto::netmessages::CSVCMsg_GameEventList;
+use csgoproto::netmessages::CSVCMsg_ServerInfo;
+use csgoproto::networkbasetypes::CNETMsg_SetConVar;
 use protobuf::Message;
 use snap::raw::decompress_len;
 use snap::raw::Decoder as SnapDecoder;
@@ -64,7 +68,75 @@ pub struct FirstPassOutput<'a> {
     pub added_temp_props: Vec<String>,
     pub wanted_players: AHashSet<u64>,
     pub header: AHashMap<String, String>,
+    pub file_info: Option<FileInfo>,
+    pub overview: Option<MapOverview>,
+    pub zones: Option<Zones>,
+    // From the signon packets, the second pass only sees them in the first thread
+    pub convars: AHashMap<String, String>,
+    pub tick_interval: Option<f32>,
 }
+#[derive(Debug, Clone, Default)]
+pub struct FileInfo {
+    // Length of the demo in seconds
+    pub playback_time: Option<f32>,
+    pub playback_ticks: Option<i32>,
+    pub playback_frames: Option<i32>,
+    // Tick where each round started
+    pub round_start_ticks: Vec<i32>,
+}
+
+impl FileInfo {
+    pub fn from_bytes(bytes: &[u8]) -> Result<FileInfo, DemoParserError> {
+        let info: CDemoFileInfo = match Message::parse_from_bytes(bytes) {
+            Ok(info) => info,
+            Err(_) => return Err(DemoParserError::MalformedMessage),
+        };
+        let round_start_ticks = match info.game_info.cs.as_ref() {
+            Some(cs) => cs.round_start_ticks.clone(),
+            None => vec![],
+        };
+        Ok(FileInfo {
+            playback_time: info.playback_time,
+            playback_ticks: info.playback_ticks,
+            playback_frames: info.playback_frames,
+            round_start_ticks,
+        })
+    }
+}
+
+/// Reads DEM_FileInfo without parsing the rest of the demo. The frame is at the end of the demo
+/// and the header has the byte offset to it, demos that were cut short or are still being
+/// recorded don't have it.
+pub fn parse_file_info(demo_bytes: &[u8]) -> Result<FileInfo, DemoParserError> {
+    if demo_bytes.len() < HEADER_ENDS_AT_BYTE {
+        return Err(DemoParserError::OutOfBytesError);
+    }
+    let mut ptr = match demo_bytes[8..12].try_into() {
+        Ok(arr) => u32::from_le_bytes(arr) as usize,
+        Err(_) => return Err(DemoParserError::OutOfBytesError),
+    };
+    if ptr < HEADER_ENDS_AT_BYTE || ptr >= demo_bytes.len() {
+        return Err(DemoParserError::FileInfoNotFound);
+    }
+    let cmd = read_varint(demo_bytes, &mut ptr)?;
+    let _tick = read_varint(demo_bytes, &mut ptr)?;
+    let size = read_varint(demo_bytes, &mut ptr)? as usize;
+    if demo_cmd_type_from_int((cmd & !64) as i32)? != DEM_FileInfo {
+        return Err(DemoParserError::FileInfoNotFound);
+    }
+    let bytes = match demo_bytes.get(ptr..ptr + size) {
+        Some(bytes) => bytes,
+        None => return Err(DemoParserError::OutOfBytesError),
+    };
+    match (cmd & 64) == 64 {
+        true => match SnapDecoder::new().decompress_vec(bytes) {
+            Ok(decompressed) => FileInfo::from_bytes(&decompressed),
+            Err(e) => Err(DemoParserError::DecompressionFailure(format!("{}", e))),
+        },
+        false => FileInfo::from_bytes(bytes),
+    }
+}
+
 struct Frame {
     pub size: usize,
     pub frame_starts_at: usize,
@@ -75,6 +147,9 @@ struct Frame {
 impl<'a> FirstPassParser<'a> {
     pub fn parse_demo(&mut self, demo_bytes: &'a [u8]) -> Result<FirstPassOutput, DemoParserError> {
         self.handle_short_header(demo_bytes.len(), &demo_bytes[..HEADER_ENDS_AT_BYTE])?;
+        if let Ok(file_info) = parse_file_info(demo_bytes) {
+            self.set_file_info(file_info);
+        }
         let mut reuseable_buffer = vec![0_u8; 100_000];
         // Loop that goes trough the entire file
         loop {
@@ -93,6 +168,12 @@ impl<'a> FirstPassParser<'a> {
                 DEM_ClassInfo => self.parse_class_info(&bytes)?,
                 DEM_SignonPacket => self.parse_packet(&bytes)?,
                 DEM_FullPacket => self.parse_full_packet(&bytes, &frame)?,
+                // Only when the offset in the header didn't lead to it. It's optional so a bad one is ignored
+                DEM_FileInfo if self.file_info.is_none() => {
+                    if let Ok(file_info) = FileInfo::from_bytes(bytes) {
+                        self.set_file_info(file_info);
+                    }
+                }
                 DEM_Stop => break,
                 _ => {}
             };
@@ -101,6 +182,18 @@ impl<'a> FirstPassParser<'a> {
         self.create_first_pass_output()
     }
 
+    fn set_file_info(&mut self, file_info: FileInfo) {
+        if let Some(time) = file_info.playback_time {
+            self.header.insert("playback_time".to_string(), time.to_string());
+        }
+        if let Some(ticks) = file_info.playback_ticks {
+            self.header.insert("playback_ticks".to_string(), ticks.to_string());
+        }
+        if let Some(frames) = file_info.playback_frames {
+            self.header.insert("playback_frames".to_string(), frames.to_string());
+        }
+        self.file_info = Some(file_info);
+    }
     fn parse_sendtable_bytes(&mut self, bytes: &[u8]) -> Result<(), DemoParserError> {
         self.sendtable_message = match Message::parse_from_bytes(&bytes) {
             Ok(m) => Some(m),
@@ -180,6 +273,11 @@ impl<'a> FirstPassParser<'a> {
         };
         Ok(FirstPassOutput {
             header: self.header.clone(),
+            file_info: self.file_info.clone(),
+            overview: self.header.get("map_name").and_then(|name| MapOverview::from_map_name(name)),
+            zones: None,
+            convars: self.convars.clone(),
+            tick_interval: self.tick_interval,
             fullpacket_offsets: self.fullpacket_offsets.clone(),
             settings: &self.settings,
             baselines: self.baselines.clone(),
@@ -263,6 +361,8 @@ impl<'a> FirstPassParser<'a> {
                 svc_CreateStringTable => self.parse_create_stringtable(&msg_bytes),
                 svc_UpdateStringTable => self.update_string_table(&msg_bytes),
                 svc_ClearAllStringTables => self.clear_stringtables(),
+                svc_ServerInfo => self.parse_server_info(&msg_bytes),
+                net_SetConVar => self.parse_convars(&msg_bytes),
                 _ => Ok(()),
             };
             ok?
@@ -273,6 +373,21 @@ impl<'a> FirstPassParser<'a> {
         self.string_tables = vec![];
         Ok(())
     }
+    // Only needed for timing, a message that doesn't decode is skipped
+    fn parse_server_info(&mut self, bytes: &[u8]) -> Result<(), DemoParserError> {
+        if let Ok(server_info) = CSVCMsg_ServerInfo::parse_from_bytes(bytes) {
+            self.tick_interval = server_info.tick_interval.filter(|interval| *interval > 0.0);
+        }
+        Ok(())
+    }
+    fn parse_convars(&mut self, bytes: &[u8]) -> Result<(), DemoParserError> {
+        if let Ok(convar) = CNETMsg_SetConVar::parse_from_bytes(bytes) {
+            for var in convar.convars.iter().flat_map(|cv| &cv.cvars) {
+                self.convars.insert(var.name().to_owned(), var.value().to_owned());
+            }
+        }
+        Ok(())
+    }
     pub fn parse_header(&mut self, bytes: &[u8]) -> Result<(), DemoParserError> {
         let header: CDemoFileHeader = match Message::parse_from_bytes(&bytes) {
             Ok(list) => list,
@@ -324,6 +439,7 @@ impl<'a> FirstPassParser<'a> {
             },
             Err(_) => {}
         };
+        // Byte offset to DEM_FileInfo, the last frame of the demo
         // hmmmm not sure where the 18 comes from if the header is only 16?
         // can be used to check that file ends early
         let file_length_expected = match bytes[8..12].try_into() {
@@ -339,11 +455,6 @@ impl<'a> FirstPassParser<'a> {
                 100.0 - (file_len as f32 / file_length_expected as f32 * 100.0),
             )));
         }
-        // seems to be byte offset to where DEM_END command happens. After that comes Spawngroups and fileinfo. odd...
-        let _no_clue_what_this_is = match bytes[8..12].try_into() {
-            Err(_) => return Err(DemoParserError::OutOfBytesError),
-            Ok(arr) => i32::from_le_bytes(arr),
-        };
         self.ptr = HEADER_ENDS_AT_BYTE;
         Ok(())
     }
@@ -383,3 +494,59 @@ impl<'a> FirstPassParser<'a> {
         return Ok(());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::first_pass::parser::*;
+
+    // Header with the offset of the file info frame, some filler and the frame itself
+    fn demo_with_file_info(compressed: bool) -> Vec<u8> {
+        let mut info = CDemoFileInfo::new();
+        info.set_playback_time(123.5);
+        info.set_playback_ticks(7904);
+        info.set_playback_frames(7900);
+        let mut payload = info.write_to_bytes().unwrap();
+        if compressed {
+            payload = snap::raw::Encoder::new().compress_vec(&payload).unwrap();
+        }
+        let mut bytes = b"PBDEMS2\0".to_vec();
+        bytes.extend(20_u32.to_le_bytes());
+        bytes.extend([0_u8; 8]);
+        let cmd = DEM_FileInfo as u8 | if compressed { 64 } else { 0 };
+        bytes.extend([cmd, 0, payload.len() as u8]);
+        bytes.extend(payload);
+        bytes
+    }
+
+    #[test]
+    fn test_parse_file_info() {
+        for compressed in [false, true] {
+            let info = parse_file_info(&demo_with_file_info(compressed)).unwrap();
+            assert_eq!(info.playback_time, Some(123.5));
+            assert_eq!(info.playback_ticks, Some(7904));
+            assert_eq!(info.playback_frames, Some(7900));
+            assert!(info.round_start_ticks.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_parse_file_info_missing() {
+        // Still being recorded, the offset is not written yet
+        let mut bytes = demo_with_file_info(false);
+        bytes[8..12].copy_from_slice(&0_u32.to_le_bytes());
+        assert_eq!(parse_file_info(&bytes).err(), Some(DemoParserError::FileInfoNotFound));
+        // Cut short before the frame
+        let mut bytes = demo_with_file_info(false);
+        bytes.truncate(20);
+        assert_eq!(parse_file_info(&bytes).err(), Some(DemoParserError::FileInfoNotFound));
+        // Frame header is there but the message is cut
+        let mut bytes = demo_with_file_info(false);
+        bytes.truncate(bytes.len() - 2);
+        assert_eq!(parse_file_info(&bytes).err(), Some(DemoParserError::OutOfBytesError));
+        // Some other frame at the offset
+        let mut bytes = demo_with_file_info(false);
+        bytes[20] = DEM_Packet as u8;
+        assert_eq!(parse_file_info(&bytes).err(), Some(DemoParserError::FileInfoNotFound));
+        assert_eq!(parse_file_info(&[0; 10]).err(), Some(DemoParserError::OutOfBytesError));
+    }
+}
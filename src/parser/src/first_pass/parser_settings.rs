@@ -1,5 +1,6 @@
 use super::sendtables::Serializer;
 use super::stringtables::StringTable;
+use crate::first_pass::parser::FileInfo;
 use crate::first_pass::prop_controller::PropController;
 use crate::first_pass::prop_controller::PropInfo;
 use crate::first_pass::read_bits::DemoParserError;
@@ -7,6 +8,7 @@ use crate::first_pass::stringtables::UserInfo;
 use crate::maps::FRIENDLY_NAMES_MAPPING;
 use crate::maps::NON_MULTITHREADABLE_PROPS;
 use crate::second_pass::decoder::QfMapper;
+use crate::second_pass::game_events::INVENTORY_EVENTS;
 use crate::second_pass::other_netmessages::Class;
 use crate::second_pass::parser_settings::PlayerEndMetaData;
 use crate::second_pass::parser_settings::SpecialIDs;
@@ -31,6 +33,24 @@ pub struct ParserInputs<'a> {
     pub wanted_events: Vec<String>,
     pub parse_ents: bool,
     pub parse_projectiles: bool,
+    // Position and state of the bomb every tick
+    pub collect_bomb: bool,
+    // Fire positions of molotovs and incendiaries
+    pub collect_infernos: bool,
+    // Attribute lists of weapons for the weapon skins table
+    pub collect_weapon_skins: bool,
+    // Per round money and equipment for the economy analysis
+    pub collect_economy: bool,
+    // Flicks and tracking for the aim analysis
+    pub collect_aim: bool,
+    // Movement segments for the movement analysis
+    pub collect_movement: bool,
+    // Spotted intervals for the visibility analysis
+    pub collect_spotted: bool,
+    // Keep the recent positions/angles of players, velocity in events comes from them
+    pub track_player_history: bool,
+    // DEM_UserCmd, only in POV demos
+    pub parse_user_cmds: bool,
     pub only_header: bool,
     pub count_props: bool,
     pub only_convars: bool,
@@ -50,6 +70,7 @@ pub struct FirstPassParser<'a> {
     pub string_tables: Vec<StringTable>,
     pub baselines: AHashMap<u32, Vec<u8>, RandomState>,
     pub convars: AHashMap<String, String>,
+    pub tick_interval: Option<f32>,
     pub player_md: Vec<PlayerEndMetaData>,
     pub prop_controller: PropController,
     pub ge_list: AHashMap<i32, Descriptor_t>,
@@ -72,6 +93,7 @@ pub struct FirstPassParser<'a> {
     pub only_header: bool,
     pub prop_infos: Vec<PropInfo>,
     pub header: AHashMap<String, String>,
+    pub file_info: Option<FileInfo>,
     pub is_multithreadable: bool,
     pub needs_velocity: bool,
     pub sendtable_message: Option<CDemoSendTables>,
@@ -91,7 +113,7 @@ impl<'a> FirstPassParser<'a> {
             sendtable_message: None,
             needs_velocity: false,
             added_temp_props: vec![],
-            is_multithreadable: check_multithreadability(&inputs.wanted_player_props),
+            is_multithreadable: check_multithreadability(inputs),
             stringtable_players: BTreeMap::default(),
             only_header: inputs.only_header,
             ge_list_set: false,
@@ -108,6 +130,7 @@ impl<'a> FirstPassParser<'a> {
             player_md: vec![],
             name_to_id: AHashMap::default(),
             convars: AHashMap::default(),
+            tick_interval: None,
             string_tables: vec![],
             fullpacket_offsets: vec![],
             ptr: 0,
@@ -133,16 +156,26 @@ impl<'a> FirstPassParser<'a> {
             wanted_prop_ids: vec![],
             prop_infos: vec![],
             header: AHashMap::default(),
+            file_info: None,
         }
     }
 }
-pub fn check_multithreadability(player_props: &[String]) -> bool {
-    for name in player_props {
+impl<'a> ParserInputs<'a> {
+    pub fn wants_inventory_events(&self) -> bool {
+        self.wanted_events
+            .iter()
+            .any(|e| INVENTORY_EVENTS.contains(&e.as_str()) || e == "all")
+    }
+}
+// The second pass is split at fullpackets and every thread starts with empty state.
+// Anything that compares against earlier ticks has to run in one go.
+pub fn check_multithreadability(inputs: &ParserInputs) -> bool {
+    for name in &inputs.wanted_player_props {
         if NON_MULTITHREADABLE_PROPS.contains(name) {
             return false;
         }
     }
-    true
+    !(inputs.wants_inventory_events() || inputs.collect_aim || inputs.collect_weapon_skins || inputs.collect_movement)
 }
 
 pub fn rm_user_friendly_names(names: &Vec<String>) -> Result<Vec<String>, DemoParserError> {
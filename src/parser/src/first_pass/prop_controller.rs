@@ -12,8 +12,15 @@ pub const SPECTATOR_TEAM_NUM: u32 = 1;
 pub const BUTTONS_BASEID: u32 = 100000;
 pub const NORMAL_PROP_BASEID: u32 = 1000;
 pub const WEAPON_SKIN_ID: u32 = 420420420;
+pub const ITEM_ATTRIBUTE_DEF_ID: u32 = 420420421;
+// CEconItemAttribute lists, every (list, element) gets its own id in get_propinfo
+pub const ITEM_ATTRIBUTE_DEF_OFFSET: u32 = 800000;
+pub const ITEM_ATTRIBUTE_VALUE_OFFSET: u32 = 810000;
+pub const MAX_ITEM_ATTRIBUTES: u32 = 64;
 pub const WEAPON_ORIGINGAL_OWNER_ID: u32 = 6942000;
 pub const MY_WEAPONS_OFFSET: u32 = 500000;
+pub const FIRE_POSITIONS_OFFSET: u32 = 600000;
+pub const FIRE_IS_BURNING_OFFSET: u32 = 700000;
 pub const GRENADE_AMMO_ID: u32 = 1111111;
 pub const INVENTORY_ID: u32 = 100000000;
 pub const IS_ALIVE_ID: u32 = 100000001;
@@ -34,6 +41,27 @@ pub const NAME_ID: u32 = 100000015;
 pub const PLAYER_X_ID: u32 = 100000016;
 pub const PLAYER_Y_ID: u32 = 100000017;
 pub const PLAYER_Z_ID: u32 = 100000018;
+pub const X_BEFORE_ID: u32 = 100000019;
+pub const Y_BEFORE_ID: u32 = 100000020;
+pub const Z_BEFORE_ID: u32 = 100000021;
+pub const PITCH_BEFORE_ID: u32 = 100000022;
+pub const YAW_BEFORE_ID: u32 = 100000023;
+pub const IS_AIRBORNE_ID: u32 = 100000024;
+pub const RADAR_X_ID: u32 = 100000025;
+pub const RADAR_Y_ID: u32 = 100000026;
+pub const RADAR_LEVEL_ID: u32 = 100000027;
+pub const ZONE_ID: u32 = 100000028;
+pub const ANGULAR_VELOCITY_ID: u32 = 100000029;
+// Props that only exist with the recent positions/angles of players. Velocity can use them as
+// well but only when ParserInputs.track_player_history asks for it
+pub const PLAYER_HISTORY_PROPS: &[&str] = &[
+    "X_before",
+    "Y_before",
+    "Z_before",
+    "pitch_before",
+    "yaw_before",
+    "angular_velocity",
+];
 
 #[derive(Clone, Debug)]
 pub struct PropController {
@@ -280,6 +308,29 @@ impl PropController {
                 is_player_prop: true,
             });
         }
+        for (name, id) in [
+            ("X_before", X_BEFORE_ID),
+            ("Y_before", Y_BEFORE_ID),
+            ("Z_before", Z_BEFORE_ID),
+            ("pitch_before", PITCH_BEFORE_ID),
+            ("yaw_before", YAW_BEFORE_ID),
+            ("is_airborne", IS_AIRBORNE_ID),
+            ("radar_x", RADAR_X_ID),
+            ("radar_y", RADAR_Y_ID),
+            ("radar_level", RADAR_LEVEL_ID),
+            ("zone", ZONE_ID),
+            ("angular_velocity", ANGULAR_VELOCITY_ID),
+        ] {
+            if self.wanted_player_props.contains(&(name.to_string())) {
+                self.prop_infos.push(PropInfo {
+                    id,
+                    prop_type: PropType::Custom,
+                    prop_name: name.to_string(),
+                    prop_friendly_name: name.to_string(),
+                    is_player_prop: true,
+                });
+            }
+        }
         self.prop_infos.push(PropInfo {
             id: TICK_ID,
             prop_type: PropType::Tick,
@@ -396,9 +447,19 @@ impl PropController {
         if full_name == "CCSPlayerPawn.CCSPlayer_WeaponServices.m_hMyWeapons" {
             f.prop_id = MY_WEAPONS_OFFSET as u32;
         }
+        // Fixed size arrays, every element gets its own id in get_propinfo
+        if full_name == "CInferno.m_firePositions" {
+            f.prop_id = FIRE_POSITIONS_OFFSET;
+        }
+        if full_name == "CInferno.m_bFireIsBurning" {
+            f.prop_id = FIRE_IS_BURNING_OFFSET;
+        }
         if prop_name.contains("CEconItemAttribute.m_iRawValue32") {
             f.prop_id = WEAPON_SKIN_ID as u32;
         }
+        if prop_name.contains("CEconItemAttribute.m_iAttributeDefinitionIndex") {
+            f.prop_id = ITEM_ATTRIBUTE_DEF_ID;
+        }
         self.id += 1;
     }
 
@@ -407,6 +468,14 @@ impl PropController {
             match name {
                 "m_hOwnerEntity" => self.special_ids.h_owner_entity = Some(id),
                 "m_nOwnerId" => self.special_ids.grenade_owner_id = Some(id),
+                "m_nBounces" => self.special_ids.grenade_bounces = Some(id),
+                "m_fireCount" => self.special_ids.inferno_fire_count = Some(id),
+                "m_nBombSite" => self.special_ids.bomb_site = Some(id),
+                "m_flC4Blow" => self.special_ids.bomb_blow_time = Some(id),
+                "m_bBeingDefused" => self.special_ids.bomb_being_defused = Some(id),
+                "m_hBombDefuser" => self.special_ids.bomb_defuser = Some(id),
+                "m_flDefuseCountDown" => self.special_ids.bomb_defuse_countdown = Some(id),
+                "m_flDefuseLength" => self.special_ids.bomb_defuse_length = Some(id),
                 "CBodyComponentBaseAnimGraph.m_vecX" => self.special_ids.m_vec_x_grenade = Some(id),
                 "CBodyComponentBaseAnimGraph.m_vecY" => self.special_ids.m_vec_y_grenade = Some(id),
                 "CBodyComponentBaseAnimGraph.m_vecZ" => self.special_ids.m_vec_z_grenade = Some(id),
@@ -416,6 +485,10 @@ impl PropController {
                 "m_iItemDefinitionIndex" => self.special_ids.item_def = Some(id),
                 "m_OriginalOwnerXuidLow" => self.special_ids.orig_own_low = Some(id),
                 "m_OriginalOwnerXuidHigh" => self.special_ids.orig_own_high = Some(id),
+                "m_iItemIDHigh" => self.special_ids.item_id_high = Some(id),
+                "m_iItemIDLow" => self.special_ids.item_id_low = Some(id),
+                "m_iEntityQuality" => self.special_ids.item_quality = Some(id),
+                "m_szCustomName" => self.special_ids.item_custom_name = Some(id),
                 _ => {}
             };
         } else {
@@ -423,6 +496,9 @@ impl PropController {
                 "CCSGameRulesProxy.CCSGameRules.m_bFreezePeriod" => self.special_ids.is_freeze_period = Some(id),
                 "CCSGameRulesProxy.CCSGameRules.m_eRoundWinReason" => self.special_ids.round_win_reason = Some(id),
                 "CCSGameRulesProxy.CCSGameRules.m_totalRoundsPlayed" => self.special_ids.total_rounds_played = Some(id),
+                "CCSGameRulesProxy.CCSGameRules.m_fRoundStartTime" => self.special_ids.round_start_time = Some(id),
+                "CCSGameRulesProxy.CCSGameRules.m_iNumConsecutiveCTLoses" => self.special_ids.ct_losing_streak = Some(id),
+                "CCSGameRulesProxy.CCSGameRules.m_iNumConsecutiveTerroristLoses" => self.special_ids.t_losing_streak = Some(id),
                 "CCSTeam.m_iTeamNum" => self.special_ids.team_team_num = Some(id),
                 "CCSPlayerPawn.m_iTeamNum" => self.special_ids.player_team_pointer = Some(id),
                 "CBasePlayerWeapon.m_nOwnerId" => self.special_ids.weapon_owner_pointer = Some(id),
@@ -440,6 +516,23 @@ impl PropController {
                 "CCSPlayerPawn.CBodyComponentBaseAnimGraph.m_vecZ" => self.special_ids.cell_z_offset_player = Some(id),
                 "CCSPlayerPawn.CCSPlayer_WeaponServices.m_hActiveWeapon" => self.special_ids.active_weapon = Some(id),
                 "CCSPlayerPawn.m_lifeState" => self.special_ids.life_state = Some(id),
+                "CCSPlayerPawn.m_fFlags" => self.special_ids.player_flags = Some(id),
+                "CCSPlayerPawn.m_bSpottedByMask" => self.special_ids.spotted_by_mask = Some(id),
+                "CCSPlayerPawn.CCSPlayer_MovementServices.m_bDucked" => self.special_ids.ducked = Some(id),
+                "CCSPlayerPawn.m_bIsWalking" => self.special_ids.is_walking = Some(id),
+                "CCSPlayerPawn.m_bInBuyZone" => self.special_ids.in_buy_zone = Some(id),
+                "CCSPlayerPawn.m_unRoundStartEquipmentValue" => self.special_ids.round_start_equip_value = Some(id),
+                "CCSPlayerPawn.m_unCurrentEquipmentValue" => self.special_ids.current_equip_value = Some(id),
+                "CCSPlayerController.CCSPlayerController_InGameMoneyServices.m_iAccount" => self.special_ids.balance = Some(id),
+                "CCSPlayerController.CCSPlayerController_InGameMoneyServices.m_iStartAccount" => {
+                    self.special_ids.start_balance = Some(id)
+                }
+                "CCSPlayerController.CCSPlayerController_InGameMoneyServices.m_iCashSpentThisRound" => {
+                    self.special_ids.cash_spent_this_round = Some(id)
+                }
+                "CCSPlayerController.CCSPlayerController_ActionTrackingServices.CSPerRoundStats_t.m_iCashEarned" => {
+                    self.special_ids.cash_earned_this_round = Some(id)
+                }
                 "CCSPlayerController.m_nPawnCharacterDefIndex" => self.special_ids.agent_skin_idx = Some(id),
                 _ => {}
             };
@@ -244,6 +244,9 @@ pub enum DemoParserError {
     IllegalPathOp,
     VectorResizeFailure,
     ImpossibleCmd,
+    MalformedOverview(String),
+    MalformedZones(String),
+    FileInfoNotFound,
 }
 
 impl std::error::Error for DemoParserError {}
@@ -1,6 +1,10 @@
+pub mod analysis;
 #[cfg(test)]
 pub mod e2e_test;
 pub mod first_pass;
 pub mod maps;
 pub mod parse_demo;
+pub mod radar;
 pub mod second_pass;
+pub mod voice;
+pub mod zones;
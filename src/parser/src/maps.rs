@@ -41,6 +41,60 @@ pub static ROUND_WIN_REASON: phf::Map<i32, &'static str> = phf_map! {
     19_i32 => "t_planted",
     20_i32 => "ct_reached_hostage",
 };
+// eaccolade in CCSUsrMsg_EndOfMatchAllPlayersData, in the order of the "accolade_*" tokens of the game
+pub static ACCOLADES: phf::Map<i32, &'static str> = phf_map! {
+    0_i32 => "none",
+    1_i32 => "5k",
+    2_i32 => "4k",
+    3_i32 => "3k",
+    4_i32 => "mvps",
+    5_i32 => "hsp",
+    6_i32 => "kills",
+    7_i32 => "deaths",
+    8_i32 => "assists",
+    9_i32 => "firstkills",
+    10_i32 => "clutchkills",
+    11_i32 => "pistolkills",
+    12_i32 => "sniperkills",
+    13_i32 => "knifekills",
+    14_i32 => "bombcarrierkills",
+    15_i32 => "killswhileblind",
+    16_i32 => "uniqueweaponkills",
+    17_i32 => "burndamage",
+    18_i32 => "utilitydamage",
+    19_i32 => "damage",
+    20_i32 => "enemiesflashed",
+    21_i32 => "cashspent",
+    22_i32 => "livetime",
+    23_i32 => "nopurchasewins",
+    24_i32 => "quickcutter",
+    25_i32 => "bombplanted",
+    26_i32 => "hostagesrescued",
+    27_i32 => "gimme_01",
+    28_i32 => "gimme_02",
+    29_i32 => "gimme_03",
+    30_i32 => "gimme_04",
+};
+// idx in CCSUsrMsg_PlayerStatsUpdate, CSStatType_t of the game. Only the general stats, the per weapon
+// and per map ones that follow them are left as ids
+pub static CS_STATS: phf::Map<i32, &'static str> = phf_map! {
+    0_i32 => "shots_hit",
+    1_i32 => "shots_fired",
+    2_i32 => "kills",
+    3_i32 => "deaths",
+    4_i32 => "damage",
+    5_i32 => "bombs_planted",
+    6_i32 => "bombs_defused",
+    7_i32 => "playtime",
+    8_i32 => "rounds_won",
+    9_i32 => "t_rounds_won",
+    10_i32 => "ct_rounds_won",
+    11_i32 => "rounds_played",
+    12_i32 => "pistol_rounds_won",
+    13_i32 => "money_earned",
+    14_i32 => "objectives_completed",
+    15_i32 => "bombs_defused_with_kit",
+};
 pub static ROUND_WIN_REASON_TO_WINNER: phf::Map<i32, &'static str> = phf_map! {
     1_i32 => "T",
     4_i32 => "CT",
@@ -868,6 +922,50 @@ pub static WEAPINDICIES: phf::Map<u32, &'static str> = phf_map! {
     523_u32 => "knife_widowmaker",
     525_u32 => "knife_skeleton",
 };
+// Prices of the items in WEAPINDICIES that can be bought
+pub static ITEM_PRICES: phf::Map<&'static str, i32> = phf_map! {
+    "Desert Eagle" => 700,
+    "Dual Berettas" => 300,
+    "Five-SeveN" => 500,
+    "Glock-18" => 200,
+    "AK-47" => 2700,
+    "AUG" => 3300,
+    "AWP" => 4750,
+    "FAMAS" => 2050,
+    "G3SG1" => 5000,
+    "Galil AR" => 1800,
+    "M249" => 5200,
+    "M4A4" => 3100,
+    "MAC-10" => 1050,
+    "P90" => 2350,
+    "MP5-SD" => 1500,
+    "UMP-45" => 1200,
+    "XM1014" => 2000,
+    "PP-Bizon" => 1400,
+    "MAG-7" => 1300,
+    "Negev" => 1700,
+    "Sawed-Off" => 1100,
+    "Tec-9" => 500,
+    "Zeus x27" => 200,
+    "P2000" => 200,
+    "MP7" => 1500,
+    "MP9" => 1250,
+    "Nova" => 1050,
+    "P250" => 300,
+    "Scar-20" => 5000,
+    "SG 553" => 3000,
+    "SSG 08" => 1700,
+    "Flashbang" => 200,
+    "High Explosive Grenade" => 300,
+    "Smoke Grenade" => 300,
+    "Molotov" => 400,
+    "Decoy Grenade" => 50,
+    "Incendiary Grenade" => 500,
+    "M4A1-S" => 2900,
+    "USP-S" => 200,
+    "CZ75-Auto" => 500,
+    "R8 Revolver" => 600,
+};
 pub static PAINTKITS: phf::Map<u32, &'static str> = phf_map! {
     2_u32=>"Groundwater",
     3_u32=>"CandyApple",
@@ -2385,6 +2483,17 @@ pub static TYPEHM: phf::Map<&'static str, PropType> = phf_map! {
     "CCSPlayerPawn.m_bSpottedByMask" => PropType::Custom,
     "agent_skin" => PropType::Custom,
     "is_alive" => PropType::Custom,
+    "X_before" => PropType::Custom,
+    "Y_before" => PropType::Custom,
+    "Z_before" => PropType::Custom,
+    "pitch_before" => PropType::Custom,
+    "yaw_before" => PropType::Custom,
+    "is_airborne" => PropType::Custom,
+    "radar_x" => PropType::Custom,
+    "radar_y" => PropType::Custom,
+    "radar_level" => PropType::Custom,
+    "zone" => PropType::Custom,
+    "angular_velocity" => PropType::Custom,
     // Weapon
     "m_flAnimTime" => PropType::Weapon,
     "m_flSimulationTime"=> PropType::Weapon,
@@ -2590,6 +2699,17 @@ pub static FRIENDLY_NAMES_MAPPING: phf::Map<&'static str, &'static str> = phf_ma
     "inventory" => "inventory",
     "entity_id" => "entity_id",
     "is_alive"=>"is_alive",
+    "X_before" => "X_before",
+    "Y_before" => "Y_before",
+    "Z_before" => "Z_before",
+    "pitch_before" => "pitch_before",
+    "yaw_before" => "yaw_before",
+    "is_airborne" => "is_airborne",
+    "radar_x" => "radar_x",
+    "radar_y" => "radar_y",
+    "radar_level" => "radar_level",
+    "zone" => "zone",
+    "angular_velocity" => "angular_velocity",
     "agent_skin" => "agent_skin",
     "rank" => "CCSPlayerController.m_iCompetitiveRanking",
     "rank_if_win" => "CCSPlayerController.m_iCompetitiveRankingPredicted_Win",
@@ -2769,6 +2889,10 @@ pub static FRIENDLY_NAMES_MAPPING: phf::Map<&'static str, &'static str> = phf_ma
     "stamina" => "CCSPlayerPawn.m_flStamina",
     "direction" => "CCSPlayerPawn.m_iDirection",
     "shots_fired" => "CCSPlayerPawn.m_iShotsFired",
+    "aim_punch_angle" => "CCSPlayerPawn.m_aimPunchAngle",
+    "aim_punch_angle_vel" => "CCSPlayerPawn.m_aimPunchAngleVel",
+    "aim_punch_tick_base" => "CCSPlayerPawn.m_aimPunchTickBase",
+    "aim_punch_tick_fraction" => "CCSPlayerPawn.m_aimPunchTickFraction",
     "armor_value" => "CCSPlayerPawn.m_ArmorValue",
     "velo_modifier" => "CCSPlayerPawn.m_flVelocityModifier",
     "ground_accel_linear_frac_last_time" => "CCSPlayerPawn.m_flGroundAccelLinearFracLastTime",
@@ -1,17 +1,27 @@
+use crate::first_pass::parser::FileInfo;
 use crate::first_pass::parser::FirstPassOutput;
 use crate::first_pass::parser_settings::check_multithreadability;
 use crate::first_pass::parser_settings::{FirstPassParser, ParserInputs};
 use crate::first_pass::prop_controller::{PropController, TICK_ID};
 use crate::first_pass::read_bits::DemoParserError;
+use crate::radar::MapOverview;
+use crate::second_pass::collect_data::AimRecord;
+use crate::second_pass::collect_data::BombRecord;
+use crate::second_pass::collect_data::EconomyRecord;
+use crate::second_pass::collect_data::InfernoRecord;
+use crate::second_pass::collect_data::MovementRecord;
 use crate::second_pass::collect_data::ProjectileRecord;
+use crate::second_pass::collect_data::SpottedRecord;
+use crate::second_pass::collect_data::WeaponSkinRecord;
 use crate::second_pass::game_events::GameEvent;
 use crate::second_pass::parser::SecondPassOutput;
 use crate::second_pass::parser_settings::*;
 use crate::second_pass::variants::PropColumn;
 use crate::second_pass::variants::VarVec;
+use crate::voice::VoiceRecord;
+use crate::zones::Zones;
 use ahash::AHashMap;
 use ahash::AHashSet;
-use csgoproto::netmessages::CSVCMsg_VoiceData;
 use itertools::Itertools;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::prelude::ParallelIterator;
@@ -27,16 +37,31 @@ pub struct DemoOutput {
     pub chat_messages: Vec<ChatMessageRecord>,
     pub convars: AHashMap<String, String>,
     pub header: Option<AHashMap<String, String>>,
+    pub file_info: Option<FileInfo>,
     pub player_md: Vec<PlayerEndMetaData>,
+    pub player_stats_updates: Vec<PlayerStatsUpdateRecord>,
+    pub match_stats_updates: Vec<MatchStatsUpdateRecord>,
+    pub user_cmds: Vec<UserCmdRecord>,
     pub game_events_counter: AHashSet<String>,
     pub projectiles: Vec<ProjectileRecord>,
-    pub voice_data: Vec<CSVCMsg_VoiceData>,
+    pub infernos: Vec<InfernoRecord>,
+    pub bombs: Vec<BombRecord>,
+    pub spotted: Vec<SpottedRecord>,
+    pub movement: Vec<MovementRecord>,
+    pub aim: Vec<AimRecord>,
+    pub economy: Vec<EconomyRecord>,
+    pub weapon_skins: Vec<WeaponSkinRecord>,
+    pub voice_data: Vec<VoiceRecord>,
     pub prop_controller: PropController,
 }
 
 pub struct Parser<'a> {
     input: ParserInputs<'a>,
     pub force_singlethread: bool,
+    // Used for the radar props instead of the built-in overview of the map, for workshop maps
+    pub overview: Option<MapOverview>,
+    // Zones for the "zone" prop, only the ones of the map in the demo are used
+    pub zones: Option<Zones>,
 }
 
 impl<'a> Parser<'a> {
@@ -44,13 +69,22 @@ impl<'a> Parser<'a> {
         Parser {
             input: input,
             force_singlethread: force_singlethread,
+            overview: None,
+            zones: None,
         }
     }
     pub fn parse_demo(&mut self, demo_bytes: &[u8]) -> Result<DemoOutput, DemoParserError> {
         let mut first_pass_parser = FirstPassParser::new(&self.input);
-        let first_pass_output = first_pass_parser.parse_demo(&demo_bytes)?;
+        let mut first_pass_output = first_pass_parser.parse_demo(&demo_bytes)?;
+        if self.overview.is_some() {
+            first_pass_output.overview = self.overview.clone();
+        }
+        if let Some(zones) = &self.zones {
+            let map_name = first_pass_output.header.get("map_name").cloned().unwrap_or_default();
+            first_pass_output.zones = Some(zones.for_map(&map_name));
+        }
 
-        if check_multithreadability(&self.input.wanted_player_props) && !self.force_singlethread {
+        if check_multithreadability(&self.input) && !self.force_singlethread {
             self.second_pass_multi_threaded(demo_bytes, first_pass_output)
         } else {
             self.second_pass_single_threaded(demo_bytes, first_pass_output)
@@ -144,13 +178,30 @@ impl<'a> Parser<'a> {
             chat_messages: second_pass_outputs.iter().flat_map(|x| x.chat_messages.clone()).collect(),
             item_drops: second_pass_outputs.iter().flat_map(|x| x.item_drops.clone()).collect(),
             player_md: second_pass_outputs.iter().flat_map(|x| x.player_md.clone()).collect(),
+            player_stats_updates: second_pass_outputs
+                .iter()
+                .flat_map(|x| x.player_stats_updates.clone())
+                .collect(),
+            match_stats_updates: second_pass_outputs
+                .iter()
+                .flat_map(|x| x.match_stats_updates.clone())
+                .collect(),
+            user_cmds: second_pass_outputs.iter().flat_map(|x| x.user_cmds.clone()).collect(),
             game_events: second_pass_outputs.iter().flat_map(|x| x.game_events.clone()).collect(),
             skins: second_pass_outputs.iter().flat_map(|x| x.skins.clone()).collect(),
             convars: second_pass_outputs.iter().flat_map(|x| x.convars.clone()).collect(),
             df: all_dfs_combined,
             header: Some(first_pass_output.header),
+            file_info: first_pass_output.file_info,
             game_events_counter: all_game_events,
             projectiles: second_pass_outputs.iter().flat_map(|x| x.projectiles.clone()).collect(),
+            infernos: second_pass_outputs.iter().flat_map(|x| x.infernos.clone()).collect(),
+            bombs: second_pass_outputs.iter().flat_map(|x| x.bombs.clone()).collect(),
+            spotted: second_pass_outputs.iter().flat_map(|x| x.spotted.clone()).collect(),
+            movement: second_pass_outputs.iter().flat_map(|x| x.movement.clone()).collect(),
+            aim: second_pass_outputs.iter().flat_map(|x| x.aim.clone()).collect(),
+            economy: second_pass_outputs.iter().flat_map(|x| x.economy.clone()).collect(),
+            weapon_skins: second_pass_outputs.iter().flat_map(|x| x.weapon_skins.clone()).collect(),
             voice_data: second_pass_outputs.iter().flat_map(|x| x.voice_data.clone()).collect_vec(),
         }
     }
@@ -0,0 +1,255 @@
+use crate::first_pass::read_bits::DemoParserError;
+
+// pos_x, pos_y and scale from resource/overviews/<map>.txt of the official maps. The last value
+// is the altitude that splits the upper and lower radar on maps that have two.
+const OVERVIEWS: &[(&str, f32, f32, f32, Option<f32>)] = &[
+    ("de_ancient", -2953.0, 2164.0, 5.0, None),
+    ("de_anubis", -2796.0, 3328.0, 5.22, None),
+    ("de_dust2", -2476.0, 3239.0, 4.4, None),
+    ("de_inferno", -2087.0, 3870.0, 4.9, None),
+    ("de_mirage", -3230.0, 1713.0, 5.0, None),
+    ("de_nuke", -3453.0, 2887.0, 7.0, Some(-495.0)),
+    ("de_overpass", -4831.0, 1781.0, 5.2, None),
+    ("de_train", -2308.0, 2078.0, 4.082077, None),
+    ("de_vertigo", -3168.0, 1762.0, 4.0, Some(11700.0)),
+    ("cs_italy", -2647.0, 2592.0, 4.6, None),
+    ("cs_office", -1838.0, 1858.0, 4.1, None),
+];
+pub const DEFAULT_RADAR_LEVEL: &str = "default";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerticalSection {
+    pub name: String,
+    pub altitude_min: f32,
+    pub altitude_max: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapOverview {
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub scale: f32,
+    // Maps with more than one radar image, empty for the rest
+    pub vertical_sections: Vec<VerticalSection>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadarPosition {
+    pub x: f32,
+    pub y: f32,
+    pub level: String,
+}
+
+impl MapOverview {
+    /// Looks up an official map by the "map_name" of the header. Workshop maps come as
+    /// "workshop/<id>/<name>", those are looked up by the name.
+    pub fn from_map_name(map_name: &str) -> Option<MapOverview> {
+        let map_name = map_name.rsplit('/').next().unwrap_or(map_name);
+        let (_, pos_x, pos_y, scale, split) = OVERVIEWS.iter().find(|(name, ..)| name.eq_ignore_ascii_case(map_name))?;
+        let vertical_sections = match split {
+            Some(split) => vec![
+                VerticalSection {
+                    name: DEFAULT_RADAR_LEVEL.to_string(),
+                    altitude_min: *split,
+                    altitude_max: f32::MAX,
+                },
+                VerticalSection {
+                    name: "lower".to_string(),
+                    altitude_min: f32::MIN,
+                    altitude_max: *split,
+                },
+            ],
+            None => vec![],
+        };
+        Some(MapOverview {
+            pos_x: *pos_x,
+            pos_y: *pos_y,
+            scale: *scale,
+            vertical_sections,
+        })
+    }
+    pub fn from_file(path: &str) -> Result<MapOverview, DemoParserError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => MapOverview::from_overview_txt(&text),
+            Err(e) => Err(DemoParserError::FileNotFound(format!("{}. File name: {}", e, path))),
+        }
+    }
+    /// Parses an overview file (the KeyValues text next to the radar image), for example:
+    /// "de_nuke" { "pos_x" "-3453" "pos_y" "2887" "scale" "7" "verticalsections" { ... } }
+    pub fn from_overview_txt(text: &str) -> Result<MapOverview, DemoParserError> {
+        let tokens = tokenize(text);
+        let mut idx = 0;
+        let root = parse_block(&tokens, &mut idx)?;
+        // Top level is the map name with everything inside it
+        let body = match root.first() {
+            Some((_, KeyValue::Block(body))) => body,
+            _ => return Err(DemoParserError::MalformedOverview("missing map block".to_string())),
+        };
+        let number = |key: &str| -> Result<f32, DemoParserError> {
+            match find_key(body, key) {
+                Some(KeyValue::Value(v)) => v
+                    .parse::<f32>()
+                    .map_err(|_| DemoParserError::MalformedOverview(format!("{} is not a number: {}", key, v))),
+                _ => Err(DemoParserError::MalformedOverview(format!("missing {}", key))),
+            }
+        };
+        let mut vertical_sections = vec![];
+        if let Some(KeyValue::Block(sections)) = find_key(body, "verticalsections") {
+            for (name, section) in sections {
+                if let KeyValue::Block(section) = section {
+                    let altitude = |key: &str, default: f32| match find_key(section, key) {
+                        Some(KeyValue::Value(v)) => v.parse::<f32>().unwrap_or(default),
+                        _ => default,
+                    };
+                    vertical_sections.push(VerticalSection {
+                        name: name.clone(),
+                        altitude_min: altitude("AltitudeMin", f32::MIN),
+                        altitude_max: altitude("AltitudeMax", f32::MAX),
+                    });
+                }
+            }
+        }
+        Ok(MapOverview {
+            pos_x: number("pos_x")?,
+            pos_y: number("pos_y")?,
+            scale: number("scale")?,
+            vertical_sections,
+        })
+    }
+    /// World coordinates to pixels on the 1024x1024 radar image, (0, 0) being the top left corner.
+    pub fn to_radar(&self, x: f32, y: f32, z: f32) -> RadarPosition {
+        RadarPosition {
+            x: (x - self.pos_x) / self.scale,
+            y: (self.pos_y - y) / self.scale,
+            level: self.level(z).to_string(),
+        }
+    }
+    pub fn level(&self, z: f32) -> &str {
+        self.vertical_sections
+            .iter()
+            .find(|s| z >= s.altitude_min && z < s.altitude_max)
+            .map(|s| s.name.as_str())
+            .unwrap_or(DEFAULT_RADAR_LEVEL)
+    }
+}
+
+enum KeyValue {
+    Value(String),
+    Block(Vec<(String, KeyValue)>),
+}
+
+fn find_key<'a>(block: &'a [(String, KeyValue)], key: &str) -> Option<&'a KeyValue> {
+    block.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v)
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => tokens.push(c.to_string()),
+            '"' => {
+                let mut token = String::new();
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                    token.push(c);
+                }
+                tokens.push(token);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut token = c.to_string();
+                while let Some(next) = chars.peek() {
+                    if next.is_whitespace() || *next == '{' || *next == '}' || *next == '"' {
+                        break;
+                    }
+                    token.push(*next);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+        }
+    }
+    tokens
+}
+
+fn parse_block(tokens: &[String], idx: &mut usize) -> Result<Vec<(String, KeyValue)>, DemoParserError> {
+    let mut block = vec![];
+    while *idx < tokens.len() {
+        let key = tokens[*idx].clone();
+        *idx += 1;
+        if key == "}" {
+            return Ok(block);
+        }
+        let value = match tokens.get(*idx).map(|t| t.as_str()) {
+            Some("{") => {
+                *idx += 1;
+                KeyValue::Block(parse_block(tokens, idx)?)
+            }
+            Some(v) => {
+                *idx += 1;
+                KeyValue::Value(v.to_string())
+            }
+            None => return Err(DemoParserError::MalformedOverview(format!("no value for {}", key))),
+        };
+        block.push((key, value));
+    }
+    Ok(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::radar::*;
+
+    #[test]
+    fn test_to_radar() {
+        let overview = MapOverview::from_map_name("de_dust2").unwrap();
+        assert_eq!(overview.to_radar(-2476.0, 3239.0, 0.0).x, 0.0);
+        assert_eq!(overview.to_radar(-2476.0, 3239.0, 0.0).y, 0.0);
+        let pos = overview.to_radar(-2476.0 + 440.0, 3239.0 - 880.0, 0.0);
+        assert!((pos.x - 100.0).abs() < 0.001);
+        assert!((pos.y - 200.0).abs() < 0.001);
+        assert_eq!(pos.level, DEFAULT_RADAR_LEVEL);
+    }
+
+    #[test]
+    fn test_radar_levels() {
+        let overview = MapOverview::from_map_name("workshop/123456/DE_NUKE").unwrap();
+        assert_eq!(overview.level(0.0), DEFAULT_RADAR_LEVEL);
+        assert_eq!(overview.level(-600.0), "lower");
+        assert!(MapOverview::from_map_name("de_unknown").is_none());
+    }
+
+    #[test]
+    fn test_from_overview_txt() {
+        let text = r#"
+            // Comment
+            "de_test"
+            {
+                "material" "overviews/de_test"
+                "pos_x" "-1000"
+                "pos_y" "2000"
+                "scale" "2.5"
+                "verticalsections"
+                {
+                    "default" { "AltitudeMax" "10000" "AltitudeMin" "-100" }
+                    "lower" { "AltitudeMax" "-100" "AltitudeMin" "-10000" }
+                }
+            }
+        "#;
+        let overview = MapOverview::from_overview_txt(text).unwrap();
+        assert_eq!((overview.pos_x, overview.pos_y, overview.scale), (-1000.0, 2000.0, 2.5));
+        let pos = overview.to_radar(-500.0, 1000.0, -200.0);
+        assert_eq!((pos.x, pos.y, pos.level.as_str()), (200.0, 400.0, "lower"));
+        assert!(MapOverview::from_overview_txt(r#""de_test" { "pos_x" "-1000" }"#).is_err());
+    }
+}
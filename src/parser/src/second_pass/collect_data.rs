@@ -1,14 +1,18 @@
 use super::entities::PlayerMetaData;
 use super::variants::Variant;
+use crate::analysis::kills::view_angle_offset;
 use crate::first_pass::prop_controller::*;
 use crate::maps::AGENTSMAP;
 use crate::maps::BUTTONMAP;
 use crate::maps::GRENADE_FRIENDLY_NAMES;
 use crate::maps::PAINTKITS;
 use crate::maps::WEAPINDICIES;
+use crate::radar::RadarPosition;
 use crate::second_pass::parser_settings::SecondPassParser;
 use crate::second_pass::variants::PropColumn;
 use crate::second_pass::variants::VarVec;
+use ahash::AHashSet;
+use std::collections::VecDeque;
 use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -28,18 +32,194 @@ pub enum PropType {
 
 // DONT KNOW IF THESE ARE CORRECT. SEEMS TO GIVE CORRECT VALUES
 const CELL_BITS: i32 = 9;
+// Entity handles are (serial << 14) | entity index
+const ENTITY_INDEX_BITS: u32 = 14;
 const MAX_COORD: f32 = (1 << 14) as f32;
+// Attribute definition indicies of CEconItemAttribute
+const ATTRIBUTE_PAINT_KIT: u32 = 6;
+const ATTRIBUTE_PAINT_SEED: u32 = 7;
+const ATTRIBUTE_PAINT_WEAR: u32 = 8;
+const ATTRIBUTE_STATTRAK: u32 = 80;
+// Every sticker slot has 4 attributes starting from here: id, wear, scale and rotation
+const ATTRIBUTE_STICKER_BASE: u32 = 113;
+const MAX_STICKER_SLOTS: u32 = 5;
+const ATTRIBUTE_CHARM_ID: u32 = 299;
 
 #[derive(Debug, Clone)]
 pub struct ProjectileRecord {
     pub steamid: Option<u64>,
     pub name: Option<String>,
+    // Team of the thrower at the time of the record
+    pub team_num: Option<i32>,
     pub x: Option<f32>,
     pub y: Option<f32>,
     pub z: Option<f32>,
     pub tick: Option<i32>,
     pub grenade_type: Option<String>,
     pub entity_id: Option<i32>,
+    // Entity ids get reused, this one is unique per thrown grenade
+    pub unique_id: Option<u64>,
+    pub bounces: Option<u32>,
+    // Only set when the overview of the map is known
+    pub radar_x: Option<f32>,
+    pub radar_y: Option<f32>,
+    pub radar_level: Option<String>,
+    // Only set when zones were given
+    pub zone: Option<String>,
+}
+#[derive(Debug, Clone)]
+pub struct InfernoRecord {
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    pub tick: i32,
+    pub entity_id: i32,
+    pub unique_id: Option<u64>,
+    pub fire_count: Option<i32>,
+    // Only the points that are currently burning
+    pub fire_positions: Vec<[f32; 3]>,
+}
+#[derive(Debug, Clone, Default)]
+pub struct BombRecord {
+    pub tick: i32,
+    pub entity_id: i32,
+    // CC4 while carried or dropped, CPlantedC4 once planted
+    pub is_planted: bool,
+    pub carrier_steamid: Option<u64>,
+    pub carrier_name: Option<String>,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub z: Option<f32>,
+    // 0 = A, 1 = B
+    pub site: Option<i32>,
+    // Seconds until the bomb explodes
+    pub time_left: Option<f32>,
+    pub being_defused: Option<bool>,
+    pub defuser_steamid: Option<u64>,
+    // 0.0 - 1.0 of the current defuse
+    pub defuse_progress: Option<f32>,
+}
+#[derive(Debug, Clone)]
+pub struct SpottedRecord {
+    pub observer_steamid: Option<u64>,
+    pub observer_name: Option<String>,
+    pub target_steamid: Option<u64>,
+    pub target_name: Option<String>,
+    pub start_tick: i32,
+    // Last tick the target was spotted
+    pub end_tick: i32,
+}
+#[derive(Debug, Clone)]
+pub struct MovementRecord {
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    // "running", "walking", "crouched", "airborne" or "still"
+    pub state: String,
+    pub start_tick: i32,
+    pub end_tick: i32,
+    // Horizontal units moved during the segment
+    pub distance: f32,
+}
+#[derive(Debug, Clone)]
+pub struct AimRecord {
+    // "flick" or "occluded_tracking"
+    pub kind: String,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    // Flick: the enemy closest to the crosshair where the flick ended
+    pub target_steamid: Option<u64>,
+    pub target_name: Option<String>,
+    pub start_tick: i32,
+    pub end_tick: i32,
+    // Degrees the view moved
+    pub angle: f32,
+    // Degrees per second
+    pub peak_speed: f32,
+    // Degrees per second squared
+    pub peak_acceleration: f32,
+    // Degrees from the target's head, at the end of a flick and on average while tracking
+    pub target_offset: Option<f32>,
+    // Flick only: if the target was spotted by the player when the flick ended
+    pub target_spotted: Option<bool>,
+}
+#[derive(Debug, Clone)]
+pub struct EconomyRecord {
+    pub steamid: u64,
+    pub name: Option<String>,
+    pub team: Option<u32>,
+    pub round: i32,
+    // Tick when buy time ended, the money and equipment are from then
+    pub tick: i32,
+    pub start_balance: Option<i32>,
+    pub balance: Option<i32>,
+    pub spent: Option<i32>,
+    pub start_equip_value: Option<i32>,
+    pub equip_value: Option<i32>,
+    // Highest m_iCashEarned seen before the next round started
+    pub earned: Option<i32>,
+    // Rounds the team had lost in a row when the round started
+    pub loss_streak: Option<i32>,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeaponSticker {
+    pub slot: u32,
+    pub id: u32,
+    // 0 = new, goes up as the sticker gets scraped
+    pub wear: f32,
+}
+#[derive(Debug, Clone)]
+pub struct WeaponSkinRecord {
+    // First tick the weapon was seen with this owner
+    pub tick: i32,
+    pub entity_id: i32,
+    // Inventory item id, None for default weapons
+    pub item_id: Option<u64>,
+    pub def_index: Option<u32>,
+    pub weapon: Option<String>,
+    pub paint_index: Option<u32>,
+    pub skin_name: Option<String>,
+    pub paint_wear: Option<f32>,
+    pub paint_seed: Option<u32>,
+    pub stattrak: Option<u32>,
+    pub quality: Option<u32>,
+    pub custom_name: Option<String>,
+    pub stickers: Vec<WeaponSticker>,
+    pub charm_id: Option<u32>,
+    // None when the weapon is on the ground
+    pub owner_steamid: Option<u64>,
+    pub owner_name: Option<String>,
+    pub original_owner_steamid: Option<u64>,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventoryItem {
+    pub entity_id: i32,
+    // Entity ids get reused, this one is unique per item
+    pub unique_id: u64,
+    pub name: String,
+}
+// The "*_before" props are this many ticks before the current tick
+pub const PLAYER_HISTORY_LOOKBACK_TICKS: i32 = 8;
+const PLAYER_HISTORY_MAX_LEN: usize = 16;
+const FL_ONGROUND: u32 = 1;
+// Horizontal units per second below which a player on the ground counts as standing still
+const MOVING_SPEED: f32 = 10.0;
+// Anything further than this in one tick is a teleport (spawns etc.), not movement
+const MAX_MOVEMENT_STEP: f32 = 64.0;
+// Eye height from the feet
+pub const EYE_HEIGHT_STANDING: f32 = 64.0;
+pub const EYE_HEIGHT_DUCKED: f32 = 46.0;
+// Degrees per second the view has to turn for it to be part of a flick
+const FLICK_SPEED: f32 = 600.0;
+const FLICK_MIN_ANGLE: f32 = 20.0;
+// Crosshair this close to the head of an enemy that is not spotted counts as tracking
+const TRACKING_MAX_OFFSET: f32 = 3.0;
+const TRACKING_MIN_TICKS: i32 = 16;
+
+#[derive(Debug, Clone)]
+pub struct PlayerSnapshot {
+    pub tick: i32,
+    pub pos: [f32; 3],
+    pub pitch: f32,
+    pub yaw: f32,
 }
 pub enum CoordinateAxis {
     X,
@@ -51,14 +231,43 @@ pub enum CoordinateAxis {
 
 impl<'a> SecondPassParser<'a> {
     pub fn collect_entities(&mut self) {
+        if self.track_player_history {
+            self.collect_player_history();
+        }
+        if self.collect_spotted {
+            self.collect_spotted_intervals();
+        }
+        if self.collect_movement {
+            self.collect_movement_segments();
+        }
+        if self.collect_aim {
+            self.collect_aim_records();
+        }
+        if self.collect_inventory_events {
+            self.create_custom_events_inventory();
+        }
+        if self.collect_economy {
+            self.collect_economy_records();
+        }
+        if self.collect_weapon_skins {
+            self.collect_weapon_skins();
+        }
+        // Projectiles don't care about wanted events so that they can be combined with events
+        let is_wanted_tick = self.wanted_ticks.is_empty() || self.wanted_ticks.contains(&self.tick);
+        if self.parse_projectiles && is_wanted_tick {
+            self.collect_projectiles();
+        }
+        if self.collect_infernos && is_wanted_tick {
+            self.collect_infernos();
+        }
+        if self.collect_bomb && is_wanted_tick {
+            self.collect_bomb();
+        }
         if !self.prop_controller.event_with_velocity {
             if !self.wanted_ticks.contains(&self.tick) && self.wanted_ticks.len() != 0 || self.wanted_events.len() != 0 {
                 return;
             }
         }
-        if self.parse_projectiles {
-            self.collect_projectiles();
-        }
         // iterate every player and every wanted prop name
         // if either one is missing then push None to output
         for (entity_id, player) in &self.players {
@@ -188,6 +397,15 @@ impl<'a> SecondPassParser<'a> {
         }
     }
 
+    fn find_thrower_team(&self, entity_id: &i32) -> Option<i32> {
+        let team_id = self.prop_controller.special_ids.player_team_pointer?;
+        let owner_entid = self.find_owner_entid(entity_id).ok()?;
+        match self.get_prop_from_ent(&team_id, &(owner_entid as i32)) {
+            Ok(Variant::U32(team)) => Some(team as i32),
+            _ => None,
+        }
+    }
+
     fn find_grenade_type(&self, entity_id: &i32) -> Option<String> {
         if let Some(Some(ent)) = self.entities.get(*entity_id as usize) {
             if let Some(cls) = self.cls_by_id.get(ent.cls_id as usize) {
@@ -202,6 +420,21 @@ impl<'a> SecondPassParser<'a> {
         None
     }
 
+    fn find_entity_unique_id(&self, entity_id: &i32) -> Option<u64> {
+        match self.entities.get(*entity_id as usize) {
+            Some(Some(ent)) => Some(((ent.serial as u64) << ENTITY_INDEX_BITS) | *entity_id as u64),
+            _ => None,
+        }
+    }
+    fn find_grenade_bounces(&self, entity_id: &i32) -> Option<u32> {
+        let prop_id = self.prop_controller.special_ids.grenade_bounces?;
+        match self.get_prop_from_ent(&prop_id, entity_id) {
+            Ok(Variant::U32(bounces)) => Some(bounces),
+            Ok(Variant::I32(bounces)) => Some(bounces as u32),
+            _ => None,
+        }
+    }
+
     pub fn collect_projectiles(&mut self) {
         for projectile_entid in &self.projectiles {
             let grenade_type = match self.find_grenade_type(projectile_entid) {
@@ -238,19 +471,173 @@ impl<'a> SecondPassParser<'a> {
                 Ok(p) => Some(p),
                 Err(_) => None,
             };
+            let radar = match (&self.overview, float_x, float_y, float_z) {
+                (Some(overview), Some(x), Some(y), Some(z)) => Some(overview.to_radar(x, y, z)),
+                _ => None,
+            };
+
+            let zone = match (&self.zones, float_x, float_y, float_z) {
+                (Some(zones), Some(x), Some(y), Some(z)) => zones.zone_at(x, y, z).map(|s| s.to_string()),
+                _ => None,
+            };
 
             self.projectile_records.push(ProjectileRecord {
                 steamid: steamid,
                 name: name,
+                team_num: self.find_thrower_team(projectile_entid),
                 x: float_x,
                 y: float_y,
                 z: float_z,
                 tick: Some(self.tick),
                 grenade_type: Some(grenade_type),
                 entity_id: Some(*projectile_entid),
+                unique_id: self.find_entity_unique_id(projectile_entid),
+                bounces: self.find_grenade_bounces(projectile_entid),
+                radar_x: radar.as_ref().map(|r| r.x),
+                radar_y: radar.as_ref().map(|r| r.y),
+                radar_level: radar.map(|r| r.level),
+                zone,
+            });
+        }
+    }
+
+    pub fn collect_infernos(&mut self) {
+        for inferno_entid in &self.infernos {
+            let fire_count = match self.prop_controller.special_ids.inferno_fire_count {
+                Some(id) => match self.get_prop_from_ent(&id, inferno_entid) {
+                    Ok(Variant::I32(count)) => Some(count),
+                    Ok(Variant::U32(count)) => Some(count as i32),
+                    _ => None,
+                },
+                None => None,
+            };
+            let mut fire_positions = vec![];
+            for idx in 0..fire_count.unwrap_or(0).max(0) as u32 {
+                // Fires that burned out keep their position but are no longer burning
+                if let Ok(Variant::Bool(false)) = self.get_prop_from_ent(&(FIRE_IS_BURNING_OFFSET + idx), inferno_entid) {
+                    continue;
+                }
+                if let Ok(Variant::VecXYZ(pos)) = self.get_prop_from_ent(&(FIRE_POSITIONS_OFFSET + idx), inferno_entid) {
+                    fire_positions.push(pos);
+                }
+            }
+            self.inferno_records.push(InfernoRecord {
+                steamid: self.find_thrower_steamid(inferno_entid).ok(),
+                name: self.find_thrower_name(inferno_entid).ok(),
+                tick: self.tick,
+                entity_id: *inferno_entid,
+                unique_id: self.find_entity_unique_id(inferno_entid),
+                fire_count,
+                fire_positions,
+            });
+        }
+    }
+
+    pub fn collect_bomb(&mut self) {
+        if let Some(planted_entid) = self.planted_c4_entity_id {
+            let record = self.collect_planted_bomb(&planted_entid);
+            self.bomb_records.push(record);
+        } else if let Some(c4_entid) = self.c4_entity_id {
+            let carrier = self
+                .find_c4_owner()
+                .and_then(|owner| self.players.get(&owner).map(|md| (owner, md)));
+            // A carried bomb has no position of its own, use the carrier's
+            let (position_entid, is_player) = match carrier {
+                Some((owner, _)) => (owner, true),
+                None => (c4_entid, false),
+            };
+            let [x, y, z] = [CoordinateAxis::X, CoordinateAxis::Y, CoordinateAxis::Z].map(|axis| {
+                let coordinate = match is_player {
+                    true => self.collect_cell_coordinate_player(axis, &position_entid),
+                    false => self.collect_cell_coordinate_grenade(axis, &position_entid),
+                };
+                match coordinate {
+                    Ok(Variant::F32(f)) => Some(f),
+                    _ => None,
+                }
+            });
+            self.bomb_records.push(BombRecord {
+                tick: self.tick,
+                entity_id: c4_entid,
+                carrier_steamid: carrier.and_then(|(_, md)| md.steamid),
+                carrier_name: carrier.and_then(|(_, md)| md.name.clone()),
+                x,
+                y,
+                z,
+                ..Default::default()
             });
         }
     }
+    fn collect_planted_bomb(&self, entity_id: &i32) -> BombRecord {
+        let get_f32 = |id: Option<u32>| match id.map(|id| self.get_prop_from_ent(&id, entity_id)) {
+            Some(Ok(Variant::F32(f))) => Some(f),
+            _ => None,
+        };
+        let [x, y, z] = [CoordinateAxis::X, CoordinateAxis::Y, CoordinateAxis::Z].map(|axis| {
+            match self.collect_cell_coordinate_grenade(axis, entity_id) {
+                Ok(Variant::F32(f)) => Some(f),
+                _ => None,
+            }
+        });
+        let site = match self
+            .prop_controller
+            .special_ids
+            .bomb_site
+            .map(|id| self.get_prop_from_ent(&id, entity_id))
+        {
+            Some(Ok(Variant::I32(site))) => Some(site),
+            Some(Ok(Variant::U32(site))) => Some(site as i32),
+            _ => None,
+        };
+        let being_defused = match self
+            .prop_controller
+            .special_ids
+            .bomb_being_defused
+            .map(|id| self.get_prop_from_ent(&id, entity_id))
+        {
+            Some(Ok(Variant::Bool(b))) => Some(b),
+            _ => None,
+        };
+        let defuser_steamid = match self
+            .prop_controller
+            .special_ids
+            .bomb_defuser
+            .map(|id| self.get_prop_from_ent(&id, entity_id))
+        {
+            Some(Ok(Variant::U32(handle))) if being_defused == Some(true) => {
+                self.players.get(&((handle & 0x7FF) as i32)).and_then(|md| md.steamid)
+            }
+            _ => None,
+        };
+        let game_time = self.net_tick as f32 / 64.0;
+        let time_left = get_f32(self.prop_controller.special_ids.bomb_blow_time).map(|blow| (blow - game_time).max(0.0));
+        // The countdown is the game time when the defuse finishes
+        let defuse_progress = match (
+            being_defused,
+            get_f32(self.prop_controller.special_ids.bomb_defuse_countdown),
+            get_f32(self.prop_controller.special_ids.bomb_defuse_length),
+        ) {
+            (Some(true), Some(countdown), Some(length)) if length > 0.0 => {
+                Some((1.0 - (countdown - game_time) / length).clamp(0.0, 1.0))
+            }
+            _ => None,
+        };
+        BombRecord {
+            tick: self.tick,
+            entity_id: *entity_id,
+            is_planted: true,
+            carrier_steamid: None,
+            carrier_name: None,
+            x,
+            y,
+            z,
+            site,
+            time_left,
+            being_defused,
+            defuser_steamid,
+            defuse_progress,
+        }
+    }
 
     fn find_weapon_name(&self, entity_id: &i32) -> Result<Variant, PropCollectionError> {
         let item_def_id = match self.prop_controller.special_ids.item_def {
@@ -380,6 +767,10 @@ impl<'a> SecondPassParser<'a> {
             "X" => self.collect_cell_coordinate_player(CoordinateAxis::X, entity_id),
             "Y" => self.collect_cell_coordinate_player(CoordinateAxis::Y, entity_id),
             "Z" => self.collect_cell_coordinate_player(CoordinateAxis::Z, entity_id),
+            "velocity" if self.track_player_history => self.velocity_from_history(entity_id),
+            "velocity_X" if self.track_player_history => self.velocity_axis_from_history(entity_id, 0),
+            "velocity_Y" if self.track_player_history => self.velocity_axis_from_history(entity_id, 1),
+            "velocity_Z" if self.track_player_history => self.velocity_axis_from_history(entity_id, 2),
             "velocity" => self.collect_velocity(player),
             "velocity_X" => self.collect_velocity_axis(player, CoordinateAxis::X),
             "velocity_Y" => self.collect_velocity_axis(player, CoordinateAxis::Y),
@@ -393,6 +784,17 @@ impl<'a> SecondPassParser<'a> {
             "CCSPlayerPawn.m_bSpottedByMask" => self.find_spotted(entity_id, prop_info),
             "entity_id" => return Ok(Variant::I32(*entity_id)),
             "is_alive" => return self.find_is_alive(entity_id),
+            "is_airborne" => self.find_is_airborne(entity_id),
+            "radar_x" => Ok(Variant::F32(self.find_radar_position(entity_id)?.x)),
+            "radar_y" => Ok(Variant::F32(self.find_radar_position(entity_id)?.y)),
+            "radar_level" => Ok(Variant::String(self.find_radar_position(entity_id)?.level)),
+            "zone" => self.find_zone(entity_id),
+            "X_before" => self.find_value_before(entity_id, |s| s.pos[0]),
+            "Y_before" => self.find_value_before(entity_id, |s| s.pos[1]),
+            "Z_before" => self.find_value_before(entity_id, |s| s.pos[2]),
+            "pitch_before" => self.find_value_before(entity_id, |s| s.pitch),
+            "yaw_before" => self.find_value_before(entity_id, |s| s.yaw),
+            "angular_velocity" => self.angular_velocity_from_history(entity_id),
             "user_id" => return self.get_userid(player),
             "agent_skin" => return self.find_agent_skin(player),
             _ => Err(PropCollectionError::UnknownCustomPropName),
@@ -442,6 +844,145 @@ impl<'a> SecondPassParser<'a> {
         }
         return Err(PropCollectionError::PlayerNotFound);
     }
+    // Events don't have the previous ticks in the output so use the history instead
+    fn velocity_from_history(&self, entity_id: &i32) -> Result<Variant, PropCollectionError> {
+        let x = self.velocity_axis_from_history(entity_id, 0)?;
+        let y = self.velocity_axis_from_history(entity_id, 1)?;
+        if let (Variant::F32(x), Variant::F32(y)) = (x, y) {
+            return Ok(Variant::F32((f32::powi(x, 2) + f32::powi(y, 2)).sqrt() * 64.0));
+        }
+        Err(PropCollectionError::VelocityNotFound)
+    }
+    fn velocity_axis_from_history(&self, entity_id: &i32, idx: usize) -> Result<Variant, PropCollectionError> {
+        let axis = match idx {
+            0 => CoordinateAxis::X,
+            1 => CoordinateAxis::Y,
+            _ => CoordinateAxis::Z,
+        };
+        let current = match self.collect_cell_coordinate_player(axis, entity_id)? {
+            Variant::F32(f) => f,
+            _ => return Err(PropCollectionError::VelocityNotFound),
+        };
+        let previous = self
+            .player_history
+            .get(entity_id)
+            .and_then(|history| history.iter().rev().find(|s| s.tick < self.tick));
+        match previous {
+            // Same unit as the velocity_X in ticks: units moved per tick
+            Some(s) => Ok(Variant::F32((current - s.pos[idx]) / (self.tick - s.tick) as f32)),
+            None => Err(PropCollectionError::VelocityNotFound),
+        }
+    }
+    fn angular_velocity_from_history(&self, entity_id: &i32) -> Result<Variant, PropCollectionError> {
+        let (pitch, yaw) = match (self.find_pitch_or_yaw(entity_id, 0)?, self.find_pitch_or_yaw(entity_id, 1)?) {
+            (Variant::F32(pitch), Variant::F32(yaw)) => (pitch, yaw),
+            _ => return Err(PropCollectionError::EyeAnglesWrongVariant),
+        };
+        let previous = self
+            .player_history
+            .get(entity_id)
+            .and_then(|history| history.iter().rev().find(|s| s.tick < self.tick));
+        match previous {
+            Some(s) => Ok(Variant::F32(
+                angle_between(s.pitch, s.yaw, pitch, yaw) / ((self.tick - s.tick) as f32 * self.tick_interval),
+            )),
+            None => Err(PropCollectionError::PlayerHistoryNotFound),
+        }
+    }
+    fn find_value_before(&self, entity_id: &i32, f: fn(&PlayerSnapshot) -> f32) -> Result<Variant, PropCollectionError> {
+        let snapshot = self.player_history.get(entity_id).and_then(|history| {
+            history
+                .iter()
+                .rev()
+                .find(|s| s.tick <= self.tick - PLAYER_HISTORY_LOOKBACK_TICKS)
+        });
+        match snapshot {
+            Some(s) => Ok(Variant::F32(f(s))),
+            None => Err(PropCollectionError::PlayerHistoryNotFound),
+        }
+    }
+    pub fn collect_player_history(&mut self) {
+        let mut snapshots = vec![];
+        for entity_id in self.players.keys() {
+            let coordinates = [CoordinateAxis::X, CoordinateAxis::Y, CoordinateAxis::Z].map(|axis| {
+                match self.collect_cell_coordinate_player(axis, entity_id) {
+                    Ok(Variant::F32(f)) => Some(f),
+                    _ => None,
+                }
+            });
+            let (pitch, yaw) = (self.find_pitch_or_yaw(entity_id, 0), self.find_pitch_or_yaw(entity_id, 1));
+            if let ([Some(x), Some(y), Some(z)], Ok(Variant::F32(pitch)), Ok(Variant::F32(yaw))) = (coordinates, pitch, yaw) {
+                snapshots.push((
+                    *entity_id,
+                    PlayerSnapshot {
+                        tick: self.tick,
+                        pos: [x, y, z],
+                        pitch,
+                        yaw,
+                    },
+                ));
+            }
+        }
+        for (entity_id, snapshot) in snapshots {
+            let history = self.player_history.entry(entity_id).or_default();
+            if history.back().is_some_and(|s| s.tick == snapshot.tick) {
+                history.pop_back();
+            }
+            history.push_back(snapshot);
+            while history.len() > PLAYER_HISTORY_MAX_LEN {
+                history.pop_front();
+            }
+        }
+    }
+    pub fn find_is_airborne(&self, entity_id: &i32) -> Result<Variant, PropCollectionError> {
+        let id = match self.prop_controller.special_ids.player_flags {
+            Some(id) => id,
+            None => return Err(PropCollectionError::PlayerFlagsSpecialIdNotSet),
+        };
+        match self.get_prop_from_ent(&id, entity_id) {
+            Ok(Variant::U32(flags)) => Ok(Variant::Bool(flags & FL_ONGROUND == 0)),
+            Ok(Variant::U64(flags)) => Ok(Variant::Bool(flags & FL_ONGROUND as u64 == 0)),
+            Ok(_) => Err(PropCollectionError::PlayerFlagsIncorrectVariant),
+            Err(e) => Err(e),
+        }
+    }
+    pub fn find_radar_position(&self, entity_id: &i32) -> Result<RadarPosition, PropCollectionError> {
+        let overview = match &self.overview {
+            Some(overview) => overview,
+            None => return Err(PropCollectionError::RadarOverviewNotFound),
+        };
+        let mut pos = [0.0; 3];
+        for (idx, axis) in [CoordinateAxis::X, CoordinateAxis::Y, CoordinateAxis::Z]
+            .into_iter()
+            .enumerate()
+        {
+            match self.collect_cell_coordinate_player(axis, entity_id)? {
+                Variant::F32(f) => pos[idx] = f,
+                _ => return Err(PropCollectionError::RadarCoordinateIncorrectVariant),
+            }
+        }
+        Ok(overview.to_radar(pos[0], pos[1], pos[2]))
+    }
+    pub fn find_zone(&self, entity_id: &i32) -> Result<Variant, PropCollectionError> {
+        let zones = match &self.zones {
+            Some(zones) => zones,
+            None => return Err(PropCollectionError::ZonesNotLoaded),
+        };
+        let mut pos = [0.0; 3];
+        for (idx, axis) in [CoordinateAxis::X, CoordinateAxis::Y, CoordinateAxis::Z]
+            .into_iter()
+            .enumerate()
+        {
+            match self.collect_cell_coordinate_player(axis, entity_id)? {
+                Variant::F32(f) => pos[idx] = f,
+                _ => return Err(PropCollectionError::ZoneCoordinateIncorrectVariant),
+            }
+        }
+        match zones.zone_at(pos[0], pos[1], pos[2]) {
+            Some(zone) => Ok(Variant::String(zone.to_string())),
+            None => Err(PropCollectionError::NotInAnyZone),
+        }
+    }
     fn find_most_recent_coordinate_idx(&self, optv: Option<&PropColumn>, wanted_steamid: u64) -> Option<usize> {
         if let Some(v) = optv {
             if let Some(VarVec::U64(steamid_vec)) = &v.data {
@@ -521,6 +1062,309 @@ impl<'a> SecondPassParser<'a> {
             Err(e) => return Err(e),
         }
     }
+    pub fn collect_spotted_intervals(&mut self) {
+        let prop_id = match self.prop_controller.special_ids.spotted_by_mask {
+            Some(id) => id,
+            None => return,
+        };
+        let mut spotted_now = vec![];
+        for entity_id in self.players.keys() {
+            if let Ok(Variant::U32(mask)) = self.get_prop_from_ent(&prop_id, entity_id) {
+                for i in 0..16 {
+                    if (mask & (1 << i)) != 0 {
+                        spotted_now.push((i + 1, *entity_id));
+                    }
+                }
+            }
+        }
+        for (observer_controller_id, target_entity_id) in spotted_now {
+            if let Some(open) = self.spotted_open.get_mut(&(observer_controller_id, target_entity_id)) {
+                open.end_tick = self.tick;
+                continue;
+            }
+            let observer = self.find_user_by_controller_id(observer_controller_id);
+            let target = self.players.get(&target_entity_id);
+            let record = SpottedRecord {
+                observer_steamid: observer.and_then(|p| p.steamid),
+                observer_name: observer.and_then(|p| p.name.clone()),
+                target_steamid: target.and_then(|p| p.steamid),
+                target_name: target.and_then(|p| p.name.clone()),
+                start_tick: self.tick,
+                end_tick: self.tick,
+            };
+            self.spotted_open.insert((observer_controller_id, target_entity_id), record);
+        }
+        // Anything that was not updated this tick is no longer spotted
+        let tick = self.tick;
+        let ended: Vec<(i32, i32)> = self
+            .spotted_open
+            .iter()
+            .filter(|(_, record)| record.end_tick != tick)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in ended {
+            if let Some(record) = self.spotted_open.remove(&key) {
+                self.spotted_records.push(record);
+            }
+        }
+    }
+    fn find_special_bool(&self, prop_id: Option<u32>, entity_id: &i32) -> bool {
+        match prop_id.map(|id| self.get_prop_from_ent(&id, entity_id)) {
+            Some(Ok(Variant::Bool(b))) => b,
+            _ => false,
+        }
+    }
+    pub fn collect_movement_segments(&mut self) {
+        let mut moving_now = vec![];
+        for entity_id in self.players.keys() {
+            if !matches!(self.find_is_alive(entity_id), Ok(Variant::Bool(true))) {
+                continue;
+            }
+            let pos = match (
+                self.collect_cell_coordinate_player(CoordinateAxis::X, entity_id),
+                self.collect_cell_coordinate_player(CoordinateAxis::Y, entity_id),
+            ) {
+                (Ok(Variant::F32(x)), Ok(Variant::F32(y))) => [x, y],
+                _ => continue,
+            };
+            let airborne = matches!(self.find_is_airborne(entity_id), Ok(Variant::Bool(true)));
+            let ducked = self.find_special_bool(self.prop_controller.special_ids.ducked, entity_id);
+            let walking = self.find_special_bool(self.prop_controller.special_ids.is_walking, entity_id);
+            moving_now.push((*entity_id, pos, airborne, ducked, walking));
+        }
+        let tick = self.tick;
+        for (entity_id, pos, airborne, ducked, walking) in moving_now {
+            let (step, speed) = match self.movement_open.get(&entity_id) {
+                Some((record, last_pos)) => {
+                    let ticks = (tick - record.end_tick).max(1) as f32;
+                    let step = ((pos[0] - last_pos[0]).powi(2) + (pos[1] - last_pos[1]).powi(2)).sqrt();
+                    match step > MAX_MOVEMENT_STEP * ticks {
+                        true => (0.0, 0.0),
+                        false => (step, step / (ticks * self.tick_interval)),
+                    }
+                }
+                None => (0.0, 0.0),
+            };
+            let state = if airborne {
+                "airborne"
+            } else if ducked {
+                "crouched"
+            } else if speed < MOVING_SPEED {
+                "still"
+            } else if walking {
+                "walking"
+            } else {
+                "running"
+            };
+            if let Some((record, last_pos)) = self.movement_open.get_mut(&entity_id) {
+                if record.state == state {
+                    record.end_tick = tick;
+                    record.distance += step;
+                    *last_pos = pos;
+                    continue;
+                }
+            }
+            let player = self.players.get(&entity_id);
+            let record = MovementRecord {
+                steamid: player.and_then(|p| p.steamid),
+                name: player.and_then(|p| p.name.clone()),
+                state: state.to_string(),
+                start_tick: tick,
+                end_tick: tick,
+                distance: step,
+            };
+            if let Some((previous, _)) = self.movement_open.insert(entity_id, (record, pos)) {
+                self.movement_records.push(previous);
+            }
+        }
+        // Dead and disconnected players were not updated this tick
+        let ended: Vec<i32> = self
+            .movement_open
+            .iter()
+            .filter(|(_, (record, _))| record.end_tick != tick)
+            .map(|(entity_id, _)| *entity_id)
+            .collect();
+        for entity_id in ended {
+            if let Some((record, _)) = self.movement_open.remove(&entity_id) {
+                self.movement_records.push(record);
+            }
+        }
+    }
+    pub fn collect_aim_records(&mut self) {
+        struct AimState {
+            entity_id: i32,
+            controller_id: Option<i32>,
+            team: Option<u32>,
+            eyes: [f32; 3],
+            pitch: f32,
+            yaw: f32,
+            // Degrees moved since the previous snapshot, degrees per second and per second squared
+            step: Option<(f32, f32, f32)>,
+            spotted_mask: u32,
+        }
+        let tick = self.tick;
+        let mut states = vec![];
+        for (entity_id, player) in &self.players {
+            if !matches!(self.find_is_alive(entity_id), Ok(Variant::Bool(true))) {
+                continue;
+            }
+            let history = match self.player_history.get(entity_id) {
+                Some(history) => history,
+                None => continue,
+            };
+            let snapshot = match history.back() {
+                Some(s) if s.tick == tick => s,
+                _ => continue,
+            };
+            let step = view_motion(history, self.tick_interval);
+            let ducked = self.find_special_bool(self.prop_controller.special_ids.ducked, entity_id);
+            let eye_height = if ducked { EYE_HEIGHT_DUCKED } else { EYE_HEIGHT_STANDING };
+            let team = match self
+                .prop_controller
+                .special_ids
+                .player_team_pointer
+                .map(|id| self.get_prop_from_ent(&id, entity_id))
+            {
+                Some(Ok(Variant::U32(team))) => Some(team),
+                _ => None,
+            };
+            let spotted_mask = match self
+                .prop_controller
+                .special_ids
+                .spotted_by_mask
+                .map(|id| self.get_prop_from_ent(&id, entity_id))
+            {
+                Some(Ok(Variant::U32(mask))) => mask,
+                _ => 0,
+            };
+            states.push(AimState {
+                entity_id: *entity_id,
+                controller_id: player.controller_entid,
+                team,
+                eyes: [snapshot.pos[0], snapshot.pos[1], snapshot.pos[2] + eye_height],
+                pitch: snapshot.pitch,
+                yaw: snapshot.yaw,
+                step,
+                spotted_mask,
+            });
+        }
+        let is_spotted_by = |target: &AimState, observer: &AimState| match observer.controller_id {
+            Some(id) if (1..=32).contains(&id) => target.spotted_mask & (1 << (id - 1)) != 0,
+            _ => false,
+        };
+        let is_enemy = |a: &AimState, b: &AimState| matches!((a.team, b.team), (Some(a), Some(b)) if a != b && a > 1 && b > 1);
+
+        // Flicks: the view turning faster than FLICK_SPEED, ends when it slows down
+        let mut new_records = vec![];
+        for state in &states {
+            let (step, speed, acceleration) = match state.step {
+                Some(step) => step,
+                None => continue,
+            };
+            if speed >= FLICK_SPEED {
+                match self.aim_flicks_open.get_mut(&state.entity_id) {
+                    Some(open) => {
+                        open.end_tick = tick;
+                        open.angle += step;
+                        open.peak_speed = open.peak_speed.max(speed);
+                        open.peak_acceleration = open.peak_acceleration.max(acceleration);
+                    }
+                    None => {
+                        let player = self.players.get(&state.entity_id);
+                        let record = AimRecord {
+                            kind: "flick".to_string(),
+                            steamid: player.and_then(|p| p.steamid),
+                            name: player.and_then(|p| p.name.clone()),
+                            target_steamid: None,
+                            target_name: None,
+                            start_tick: tick,
+                            end_tick: tick,
+                            angle: step,
+                            peak_speed: speed,
+                            peak_acceleration: acceleration,
+                            target_offset: None,
+                            target_spotted: None,
+                        };
+                        self.aim_flicks_open.insert(state.entity_id, record);
+                    }
+                }
+                continue;
+            }
+            let mut record = match self.aim_flicks_open.remove(&state.entity_id).and_then(finish_flick) {
+                Some(record) => record,
+                None => continue,
+            };
+            // Where the view stopped, compared to every enemy head
+            let closest = states
+                .iter()
+                .filter(|other| is_enemy(state, other))
+                .filter_map(|other| Some((other, view_angle_offset(state.eyes, state.pitch, state.yaw, other.eyes)?)))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((target, offset)) = closest {
+                let target_player = self.players.get(&target.entity_id);
+                record.target_steamid = target_player.and_then(|p| p.steamid);
+                record.target_name = target_player.and_then(|p| p.name.clone());
+                record.target_offset = Some(offset);
+                record.target_spotted = Some(is_spotted_by(target, state));
+            }
+            new_records.push(record);
+        }
+        // Died mid-flick
+        let alive: AHashSet<i32> = states.iter().map(|s| s.entity_id).collect();
+        self.aim_flicks_open.retain(|entity_id, _| alive.contains(entity_id));
+
+        // Crosshair following the head of an enemy that the player has not spotted
+        let mut tracking_now = AHashSet::default();
+        for observer in &states {
+            for target in states.iter().filter(|t| is_enemy(observer, t) && !is_spotted_by(t, observer)) {
+                let offset = match view_angle_offset(observer.eyes, observer.pitch, observer.yaw, target.eyes) {
+                    Some(offset) if offset <= TRACKING_MAX_OFFSET => offset,
+                    _ => continue,
+                };
+                let (step, speed, acceleration) = observer.step.unwrap_or((0.0, 0.0, 0.0));
+                let key = (observer.entity_id, target.entity_id);
+                tracking_now.insert(key);
+                if let Some((open, offset_sum, count)) = self.aim_tracking_open.get_mut(&key) {
+                    open.end_tick = tick;
+                    open.angle += step;
+                    open.peak_speed = open.peak_speed.max(speed);
+                    open.peak_acceleration = open.peak_acceleration.max(acceleration);
+                    *offset_sum += offset;
+                    *count += 1;
+                    continue;
+                }
+                let observer_player = self.players.get(&observer.entity_id);
+                let target_player = self.players.get(&target.entity_id);
+                let record = AimRecord {
+                    kind: "occluded_tracking".to_string(),
+                    steamid: observer_player.and_then(|p| p.steamid),
+                    name: observer_player.and_then(|p| p.name.clone()),
+                    target_steamid: target_player.and_then(|p| p.steamid),
+                    target_name: target_player.and_then(|p| p.name.clone()),
+                    start_tick: tick,
+                    end_tick: tick,
+                    angle: 0.0,
+                    peak_speed: 0.0,
+                    peak_acceleration: 0.0,
+                    target_offset: None,
+                    target_spotted: None,
+                };
+                self.aim_tracking_open.insert(key, (record, offset, 1));
+            }
+        }
+        let ended: Vec<(i32, i32)> = self
+            .aim_tracking_open
+            .keys()
+            .filter(|key| !tracking_now.contains(*key))
+            .copied()
+            .collect();
+        for key in ended {
+            if let Some((record, offset_sum, count)) = self.aim_tracking_open.remove(&key) {
+                new_records.extend(finish_tracking(record, offset_sum, count));
+            }
+        }
+        self.aim_records.extend(new_records);
+    }
     fn steamids_from_mask(&self, uid: u32) -> Vec<u64> {
         let mut steamids = vec![];
         for i in 0..16 {
@@ -598,6 +1442,160 @@ impl<'a> SecondPassParser<'a> {
             };
         }
     }
+    fn find_special_i32(&self, prop_id: Option<u32>, entity_id: &i32) -> Option<i32> {
+        match prop_id.map(|id| self.get_prop_from_ent(&id, entity_id)) {
+            Some(Ok(Variant::I32(i))) => Some(i),
+            Some(Ok(Variant::U32(u))) => Some(u as i32),
+            _ => None,
+        }
+    }
+    pub fn collect_economy_records(&mut self) {
+        let rules_entid = match self.rules_entity_id {
+            Some(entid) => entid,
+            None => return,
+        };
+        let is_freeze_period = self.find_special_bool(self.prop_controller.special_ids.is_freeze_period, &rules_entid);
+        let is_buy_time = self.is_buy_time();
+        // A new freeze period starts the next round, the records of the previous one are done
+        if is_freeze_period && !self.economy_was_freeze_period {
+            self.economy_records
+                .extend(self.economy_open.drain().map(|(_, record)| record));
+        }
+        let special_ids = &self.prop_controller.special_ids;
+        if self.economy_was_buy_time && !is_buy_time {
+            let round = match self.find_current_round() {
+                Some(Variant::I32(round)) => round,
+                _ => 0,
+            };
+            let mut new_records = vec![];
+            for (entity_id, player) in &self.players {
+                let steamid = match player.steamid {
+                    Some(steamid) => steamid,
+                    None => continue,
+                };
+                if economy_key(steamid, &player.name).is_none() {
+                    continue;
+                }
+                let controller_id = match player.controller_entid {
+                    Some(id) => id,
+                    None => continue,
+                };
+                let loss_streak = match player.team_num {
+                    Some(2) => self.find_special_i32(special_ids.t_losing_streak, &rules_entid),
+                    Some(3) => self.find_special_i32(special_ids.ct_losing_streak, &rules_entid),
+                    _ => None,
+                };
+                new_records.push(EconomyRecord {
+                    steamid,
+                    name: player.name.clone(),
+                    team: player.team_num,
+                    round,
+                    tick: self.tick,
+                    start_balance: self.find_special_i32(special_ids.start_balance, &controller_id),
+                    balance: self.find_special_i32(special_ids.balance, &controller_id),
+                    spent: self.find_special_i32(special_ids.cash_spent_this_round, &controller_id),
+                    start_equip_value: self.find_special_i32(special_ids.round_start_equip_value, entity_id),
+                    equip_value: self.find_special_i32(special_ids.current_equip_value, entity_id),
+                    earned: self.find_special_i32(special_ids.cash_earned_this_round, &controller_id),
+                    loss_streak,
+                });
+            }
+            for record in new_records {
+                if let Some(key) = economy_key(record.steamid, &record.name) {
+                    if let Some(previous) = self.economy_open.insert(key, record) {
+                        self.economy_records.push(previous);
+                    }
+                }
+            }
+        }
+        let mut earned_now = vec![];
+        for player in self.players.values() {
+            if let (Some(steamid), Some(controller_id)) = (player.steamid, player.controller_entid) {
+                if let (Some(key), Some(earned)) = (
+                    economy_key(steamid, &player.name),
+                    self.find_special_i32(self.prop_controller.special_ids.cash_earned_this_round, &controller_id),
+                ) {
+                    earned_now.push((key, earned));
+                }
+            }
+        }
+        for (key, earned) in earned_now {
+            if let Some(record) = self.economy_open.get_mut(&key) {
+                record.earned = Some(record.earned.map_or(earned, |e| e.max(earned)));
+            }
+        }
+        self.economy_was_freeze_period = is_freeze_period;
+        self.economy_was_buy_time = is_buy_time;
+    }
+    /// Items in m_hMyWeapons, one per item entity. Armor and defuse kits are not items.
+    pub fn find_inventory_items(&self, entity_id: &i32) -> Vec<InventoryItem> {
+        let mut items: Vec<InventoryItem> = vec![];
+        let inventory_max_len = match self.get_prop_from_ent(&MY_WEAPONS_OFFSET, entity_id) {
+            Ok(Variant::U32(p)) => p,
+            _ => return items,
+        };
+        for i in 1..inventory_max_len + 1 {
+            let handle = match self.get_prop_from_ent(&(MY_WEAPONS_OFFSET + i), entity_id) {
+                Ok(Variant::U32(handle)) => handle,
+                _ => continue,
+            };
+            let item_entity_id = (handle & ((1 << ENTITY_INDEX_BITS) - 1)) as i32;
+            if items.iter().any(|item| item.entity_id == item_entity_id) {
+                continue;
+            }
+            let item = match self.find_item(&item_entity_id) {
+                Some(item) => item,
+                None => continue,
+            };
+            // The c4 handle can stay behind after the bomb changes hands, the bomb knows its owner
+            if item.name == "C4" && self.find_c4_owner() != Some(*entity_id) {
+                continue;
+            }
+            items.push(item);
+        }
+        items
+    }
+    pub fn find_active_item(&self, entity_id: &i32) -> Option<InventoryItem> {
+        let prop_id = self.prop_controller.special_ids.active_weapon?;
+        match self.get_prop_from_ent(&prop_id, entity_id) {
+            Ok(Variant::U32(handle)) => self.find_item(&((handle & 0x7FF) as i32)),
+            _ => None,
+        }
+    }
+    fn find_item(&self, item_entity_id: &i32) -> Option<InventoryItem> {
+        let item_def_id = self.prop_controller.special_ids.item_def?;
+        let name = match self.get_prop_from_ent(&item_def_id, item_entity_id) {
+            Ok(Variant::U32(def_idx)) => WEAPINDICIES.get(&def_idx)?.to_string(),
+            _ => return None,
+        };
+        Some(InventoryItem {
+            entity_id: *item_entity_id,
+            unique_id: self.find_entity_unique_id(item_entity_id)?,
+            name,
+        })
+    }
+    /// Pawn entity id of whoever holds the item, None if it is on the ground.
+    pub fn find_item_holder(&self, item_entity_id: &i32) -> Option<i32> {
+        let prop_id = self.prop_controller.special_ids.h_owner_entity?;
+        match self.get_prop_from_ent(&prop_id, item_entity_id) {
+            Ok(Variant::U32(handle)) if (handle & 0x7FF) != 0x7FF => Some((handle & 0x7FF) as i32),
+            _ => None,
+        }
+    }
+    /// Steamid of the player that bought the item (same as active_weapon_original_owner).
+    pub fn find_item_original_owner(&self, item_entity_id: &i32) -> Option<u64> {
+        let low_id = self.prop_controller.special_ids.orig_own_low?;
+        let high_id = self.prop_controller.special_ids.orig_own_high?;
+        match (
+            self.get_prop_from_ent(&low_id, item_entity_id),
+            self.get_prop_from_ent(&high_id, item_entity_id),
+        ) {
+            (Ok(Variant::U32(low_bits)), Ok(Variant::U32(high_bits))) if low_bits != 0 || high_bits != 0 => {
+                Some((high_bits as u64) << 32 | (low_bits as u64))
+            }
+            _ => None,
+        }
+    }
     fn find_c4_owner(&self) -> Option<i32> {
         if let Some(c4ent) = self.c4_entity_id {
             if let Some(id) = self.prop_controller.special_ids.h_owner_entity {
@@ -642,13 +1640,126 @@ impl<'a> SecondPassParser<'a> {
                         None => Err(PropCollectionError::WeaponSkinNoSkinMapping),
                     }
                 } else {
-                    return Err(PropCollectionError::WeaponSkinFloatConvertionError);
+                    Err(PropCollectionError::WeaponSkinFloatConvertionError)
                 }
             }
-            Ok(_) => return Err(PropCollectionError::WeaponSkinIdxIncorrectVariant),
-            Err(e) => return Err(e),
+            Ok(_) => Err(PropCollectionError::WeaponSkinIdxIncorrectVariant),
+            Err(e) => Err(e),
+        }
+    }
+    /// (definition index, value) of every CEconItemAttribute of the item. Values are floats,
+    /// attributes that are integers (sticker ids, StatTrak) have the integer in the float's bits.
+    pub fn find_item_attributes(&self, item_entity_id: &i32) -> Vec<(u32, f32)> {
+        let entity = match self.entities.get(*item_entity_id as usize) {
+            Some(Some(entity)) => entity,
+            _ => return vec![],
+        };
+        let max_slot = MAX_ITEM_ATTRIBUTES * MAX_ITEM_ATTRIBUTES;
+        let mut attributes: Vec<(u32, f32)> = entity
+            .props
+            .iter()
+            .filter(|(id, _)| (ITEM_ATTRIBUTE_DEF_OFFSET..ITEM_ATTRIBUTE_DEF_OFFSET + max_slot).contains(*id))
+            .filter_map(|(id, def)| {
+                let def = match def {
+                    Variant::U32(def) => *def,
+                    Variant::I32(def) => *def as u32,
+                    _ => return None,
+                };
+                match entity
+                    .props
+                    .get(&(id - ITEM_ATTRIBUTE_DEF_OFFSET + ITEM_ATTRIBUTE_VALUE_OFFSET))
+                {
+                    Some(Variant::F32(value)) => Some((def, *value)),
+                    _ => None,
+                }
+            })
+            .collect();
+        attributes.sort_by_key(|(def, _)| *def);
+        attributes.dedup_by_key(|(def, _)| *def);
+        attributes
+    }
+    fn find_item_id(&self, item_entity_id: &i32) -> Option<u64> {
+        let high_id = self.prop_controller.special_ids.item_id_high?;
+        let low_id = self.prop_controller.special_ids.item_id_low?;
+        match (
+            self.get_prop_from_ent(&high_id, item_entity_id),
+            self.get_prop_from_ent(&low_id, item_entity_id),
+        ) {
+            (Ok(Variant::U32(high_bits)), Ok(Variant::U32(low_bits))) if high_bits != 0 || low_bits != 0 => {
+                Some((high_bits as u64) << 32 | (low_bits as u64))
+            }
+            _ => None,
         }
     }
+    pub fn collect_weapon_skins(&mut self) {
+        let item_def_id = match self.prop_controller.special_ids.item_def {
+            Some(id) => id,
+            None => return,
+        };
+        // Only created and updated entities can have a new owner or new attributes
+        let mut entity_ids: Vec<i32> = std::mem::take(&mut self.weapon_skins_updated).into_iter().collect();
+        entity_ids.sort();
+        let mut new_records = vec![];
+        for entity_id in entity_ids {
+            let def_index = match self.get_prop_from_ent(&item_def_id, &entity_id) {
+                Ok(Variant::U32(def_index)) => def_index,
+                _ => continue,
+            };
+            let unique_id = match self.find_entity_unique_id(&entity_id) {
+                Some(id) => id,
+                None => continue,
+            };
+            let owner = self.find_item_holder(&entity_id).and_then(|pawn| self.players.get(&pawn));
+            let owner_steamid = owner.and_then(|player| player.steamid);
+            if self.weapon_skins_seen.contains(&(unique_id, owner_steamid)) {
+                continue;
+            }
+            let attributes = self.find_item_attributes(&entity_id);
+            let attribute = |def: u32| item_attribute(&attributes, def);
+            let paint_index = attribute(ATTRIBUTE_PAINT_KIT).map(|f| f as u32);
+            let stickers = stickers_from_attributes(&attributes);
+            let quality = match self
+                .prop_controller
+                .special_ids
+                .item_quality
+                .map(|id| self.get_prop_from_ent(&id, &entity_id))
+            {
+                Some(Ok(Variant::U32(quality))) => Some(quality),
+                Some(Ok(Variant::I32(quality))) => Some(quality as u32),
+                _ => None,
+            };
+            let custom_name = match self
+                .prop_controller
+                .special_ids
+                .item_custom_name
+                .map(|id| self.get_prop_from_ent(&id, &entity_id))
+            {
+                Some(Ok(Variant::String(name))) if !name.is_empty() => Some(name),
+                _ => None,
+            };
+            new_records.push(WeaponSkinRecord {
+                tick: self.tick,
+                entity_id,
+                item_id: self.find_item_id(&entity_id),
+                def_index: Some(def_index),
+                weapon: WEAPINDICIES.get(&def_index).map(|name| name.to_string()),
+                paint_index,
+                skin_name: paint_index.and_then(|idx| PAINTKITS.get(&idx)).map(|name| name.to_string()),
+                paint_wear: attribute(ATTRIBUTE_PAINT_WEAR),
+                paint_seed: attribute(ATTRIBUTE_PAINT_SEED).map(|f| f as u32),
+                stattrak: attribute(ATTRIBUTE_STATTRAK).map(|f| f.to_bits()),
+                quality,
+                custom_name,
+                stickers,
+                charm_id: attribute(ATTRIBUTE_CHARM_ID).map(|f| f.to_bits()),
+                owner_steamid,
+                owner_name: owner.and_then(|player| player.name.clone()),
+                original_owner_steamid: self.find_item_original_owner(&entity_id),
+            });
+            self.weapon_skins_seen.insert((unique_id, owner_steamid));
+        }
+        self.weapon_skins.extend(new_records);
+    }
     pub fn find_weapon_prop(&self, prop: &u32, player_entid: &i32) -> Result<Variant, PropCollectionError> {
         let p = match self.prop_controller.special_ids.active_weapon {
             Some(p) => p,
@@ -772,6 +1883,14 @@ pub enum PropCollectionError {
     OriginalOwnerXuidHighIncorrectVariant,
     SpottedIncorrectVariant,
     VelocityNotFound,
+    PlayerHistoryNotFound,
+    PlayerFlagsSpecialIdNotSet,
+    PlayerFlagsIncorrectVariant,
+    RadarOverviewNotFound,
+    RadarCoordinateIncorrectVariant,
+    ZonesNotLoaded,
+    ZoneCoordinateIncorrectVariant,
+    NotInAnyZone,
     AgentIdNotFound,
     AgentIncorrectVariant,
     AgentPropNotFound,
@@ -785,3 +1904,152 @@ impl fmt::Display for PropCollectionError {
         write!(f, "{:?}", self)
     }
 }
+
+// Degrees between two view angles, yaw wraps around at +-180
+fn angle_between(pitch_a: f32, yaw_a: f32, pitch_b: f32, yaw_b: f32) -> f32 {
+    let yaw = (yaw_b - yaw_a).rem_euclid(360.0);
+    let yaw = if yaw > 180.0 { 360.0 - yaw } else { yaw };
+    ((pitch_b - pitch_a).powi(2) + yaw.powi(2)).sqrt()
+}
+// Degrees moved since the previous snapshot, degrees per second and degrees per second squared,
+// for the newest snapshot in the history. Acceleration is 0 until there are three snapshots.
+pub fn view_motion(history: &VecDeque<PlayerSnapshot>, tick_interval: f32) -> Option<(f32, f32, f32)> {
+    let mut snapshots = history.iter().rev();
+    let speed = |from: &PlayerSnapshot, to: &PlayerSnapshot| {
+        let angle = angle_between(from.pitch, from.yaw, to.pitch, to.yaw);
+        (angle, angle / ((to.tick - from.tick) as f32 * tick_interval))
+    };
+    let current = snapshots.next()?;
+    let previous = snapshots.find(|s| s.tick < current.tick)?;
+    let (step, current_speed) = speed(previous, current);
+    let acceleration = match snapshots.find(|s| s.tick < previous.tick) {
+        Some(before) => (current_speed - speed(before, previous).1) / ((current.tick - previous.tick) as f32 * tick_interval),
+        None => 0.0,
+    };
+    Some((step, current_speed, acceleration))
+}
+// Flicks that stop before turning FLICK_MIN_ANGLE are just looking around
+pub fn finish_flick(record: AimRecord) -> Option<AimRecord> {
+    match record.angle >= FLICK_MIN_ANGLE {
+        true => Some(record),
+        false => None,
+    }
+}
+pub fn finish_tracking(mut record: AimRecord, offset_sum: f32, count: i32) -> Option<AimRecord> {
+    if record.end_tick - record.start_tick + 1 < TRACKING_MIN_TICKS {
+        return None;
+    }
+    record.target_offset = Some(offset_sum / count as f32);
+    Some(record)
+}
+
+fn item_attribute(attributes: &[(u32, f32)], def: u32) -> Option<f32> {
+    attributes.iter().find(|(d, _)| *d == def).map(|(_, value)| *value)
+}
+fn stickers_from_attributes(attributes: &[(u32, f32)]) -> Vec<WeaponSticker> {
+    (0..MAX_STICKER_SLOTS)
+        .filter_map(|slot| {
+            let id = item_attribute(attributes, ATTRIBUTE_STICKER_BASE + slot * 4)?.to_bits();
+            Some(WeaponSticker {
+                slot,
+                id,
+                wear: item_attribute(attributes, ATTRIBUTE_STICKER_BASE + slot * 4 + 1).unwrap_or(0.0),
+            })
+        })
+        .collect()
+}
+// Bots all have steamid 0 so they are told apart by name, same as PlayerKey in the analysis
+fn economy_key(steamid: u64, name: &Option<String>) -> Option<(u64, Option<String>)> {
+    match steamid {
+        0 => Some((0, Some(name.clone()?))),
+        _ => Some((steamid, None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::second_pass::collect_data::*;
+
+    fn snapshot(tick: i32, yaw: f32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            tick,
+            pos: [0.0, 0.0, 0.0],
+            pitch: 0.0,
+            yaw,
+        }
+    }
+    fn aim_record(kind: &str, start_tick: i32, end_tick: i32, angle: f32) -> AimRecord {
+        AimRecord {
+            kind: kind.to_string(),
+            steamid: Some(1),
+            name: None,
+            target_steamid: None,
+            target_name: None,
+            start_tick,
+            end_tick,
+            angle,
+            peak_speed: 0.0,
+            peak_acceleration: 0.0,
+            target_offset: None,
+            target_spotted: None,
+        }
+    }
+
+    #[test]
+    fn test_view_motion() {
+        let interval = 1.0 / 64.0;
+        let mut history = VecDeque::from(vec![snapshot(1, 0.0)]);
+        assert_eq!(view_motion(&history, interval), None);
+        history.push_back(snapshot(2, 10.0));
+        assert_eq!(view_motion(&history, interval), Some((10.0, 640.0, 0.0)));
+        history.push_back(snapshot(3, 30.0));
+        assert_eq!(view_motion(&history, interval), Some((20.0, 1280.0, 40960.0)));
+        // Turning the short way around and a skipped tick
+        history.push_back(snapshot(5, -10.0));
+        assert_eq!(view_motion(&history, interval), Some((40.0, 1280.0, 0.0)));
+        history.push_back(snapshot(6, 170.0));
+        assert_eq!(view_motion(&history, interval).map(|m| m.0), Some(180.0));
+    }
+
+    #[test]
+    fn test_finish_aim_records() {
+        assert!(finish_flick(aim_record("flick", 0, 3, FLICK_MIN_ANGLE - 1.0)).is_none());
+        assert!(finish_flick(aim_record("flick", 0, 3, FLICK_MIN_ANGLE)).is_some());
+        let short = aim_record("occluded_tracking", 0, TRACKING_MIN_TICKS - 2, 10.0);
+        assert!(finish_tracking(short, 1.0, 1).is_none());
+        let long = aim_record("occluded_tracking", 0, TRACKING_MIN_TICKS - 1, 10.0);
+        let record = finish_tracking(long, 32.0, 16).unwrap();
+        assert_eq!(record.target_offset, Some(2.0));
+    }
+
+    #[test]
+    fn test_stickers_from_attributes() {
+        // Integer attributes are stored in the bits of the float
+        let attributes = vec![
+            (ATTRIBUTE_PAINT_KIT, 44.0),
+            (ATTRIBUTE_STICKER_BASE, f32::from_bits(4567)),
+            (ATTRIBUTE_STICKER_BASE + 1, 0.25),
+            (ATTRIBUTE_STICKER_BASE + 8, f32::from_bits(12)),
+            (ATTRIBUTE_CHARM_ID, f32::from_bits(35)),
+        ];
+        assert_eq!(
+            stickers_from_attributes(&attributes),
+            vec![
+                WeaponSticker {
+                    slot: 0,
+                    id: 4567,
+                    wear: 0.25,
+                },
+                WeaponSticker {
+                    slot: 2,
+                    id: 12,
+                    wear: 0.0,
+                },
+            ]
+        );
+        assert_eq!(item_attribute(&attributes, ATTRIBUTE_CHARM_ID).map(f32::to_bits), Some(35));
+        assert_eq!(item_attribute(&attributes, ATTRIBUTE_PAINT_KIT), Some(44.0));
+        assert_eq!(item_attribute(&attributes, ATTRIBUTE_STATTRAK), None);
+        assert!(stickers_from_attributes(&[]).is_empty());
+    }
+}
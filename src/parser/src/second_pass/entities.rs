@@ -1,4 +1,10 @@
 use crate::first_pass::prop_controller::PropController;
+use crate::first_pass::prop_controller::FIRE_IS_BURNING_OFFSET;
+use crate::first_pass::prop_controller::FIRE_POSITIONS_OFFSET;
+use crate::first_pass::prop_controller::ITEM_ATTRIBUTE_DEF_ID;
+use crate::first_pass::prop_controller::ITEM_ATTRIBUTE_DEF_OFFSET;
+use crate::first_pass::prop_controller::ITEM_ATTRIBUTE_VALUE_OFFSET;
+use crate::first_pass::prop_controller::MAX_ITEM_ATTRIBUTES;
 use crate::first_pass::prop_controller::MY_WEAPONS_OFFSET;
 use crate::first_pass::prop_controller::PLAYER_ENTITY_HANDLE_MISSING;
 use crate::first_pass::prop_controller::SPECTATOR_TEAM_NUM;
@@ -24,6 +30,8 @@ const HUFFMAN_CODE_MAXLEN: u32 = 17;
 pub struct Entity {
     pub cls_id: u32,
     pub entity_id: i32,
+    // Incremented every time the entity index is reused, entity_id + serial identifies an entity
+    pub serial: u32,
     pub props: AHashMap<u32, Variant>,
     pub entity_type: EntityType,
 }
@@ -50,6 +58,8 @@ pub enum EntityType {
     Team,
     Normal,
     C4,
+    PlantedC4,
+    Inferno,
 }
 enum EntityCmd {
     Delete,
@@ -99,6 +109,13 @@ impl<'a> SecondPassParser<'a> {
             match cmd {
                 EntityCmd::Delete => {
                     self.projectiles.remove(&entity_id);
+                    self.infernos.remove(&entity_id);
+                    if self.c4_entity_id == Some(entity_id) {
+                        self.c4_entity_id = None;
+                    }
+                    if self.planted_c4_entity_id == Some(entity_id) {
+                        self.planted_c4_entity_id = None;
+                    }
                     if let Some(entry) = self.entities.get_mut(entity_id as usize) {
                         *entry = None;
                     }
@@ -137,6 +154,9 @@ impl<'a> SecondPassParser<'a> {
             self.decode_entity_update(bitreader, entity_id, n_updates, is_fullpacket, is_baseline, events_to_emit)?;
         if n_updated_values > 0 {
             self.gather_extra_info(&entity_id, is_baseline)?;
+            if self.collect_weapon_skins {
+                self.weapon_skins_updated.insert(entity_id);
+            }
         }
         Ok(())
     }
@@ -293,6 +313,10 @@ impl<'a> SecondPassParser<'a> {
     pub fn insert_field(entity: &mut Entity, result: Variant, field_info: Option<FieldInfo>) {
         if let Some(fi) = field_info {
             if fi.should_parse {
+                // The weapon_skin prop has always been the first attribute of either list
+                if is_first_item_attribute_value(fi.prop_id) {
+                    entity.props.insert(WEAPON_SKIN_ID, result.clone());
+                }
                 entity.props.insert(fi.prop_id, result);
             }
         }
@@ -321,13 +345,25 @@ impl<'a> SecondPassParser<'a> {
                     fi.prop_id = MY_WEAPONS_OFFSET + path.path[2] as u32 + 1;
                 }
             }
-            if fi.prop_id == WEAPON_SKIN_ID {
-                if let Some(entry) = path.path.get(path.last - 1) {
-                    if *entry != 0 {
-                        // Fill with impossible id
-                        fi.prop_id = u32::MAX;
+            if fi.prop_id == FIRE_POSITIONS_OFFSET || fi.prop_id == FIRE_IS_BURNING_OFFSET {
+                fi.prop_id += path.path[path.last] as u32;
+            }
+            // [.., attribute list, m_Attributes, element, field], the item has two attribute lists
+            if fi.prop_id == WEAPON_SKIN_ID || fi.prop_id == ITEM_ATTRIBUTE_DEF_ID {
+                let offset = match fi.prop_id {
+                    WEAPON_SKIN_ID => ITEM_ATTRIBUTE_VALUE_OFFSET,
+                    _ => ITEM_ATTRIBUTE_DEF_OFFSET,
+                };
+                fi.prop_id = match (path.last.checked_sub(3), path.last.checked_sub(1)) {
+                    (Some(list), Some(element))
+                        if (0..MAX_ITEM_ATTRIBUTES as i32).contains(&path.path[list])
+                            && (0..MAX_ITEM_ATTRIBUTES as i32).contains(&path.path[element]) =>
+                    {
+                        offset + path.path[list] as u32 * MAX_ITEM_ATTRIBUTES + path.path[element] as u32
                     }
-                }
+                    // Fill with impossible id
+                    _ => u32::MAX,
+                };
             }
             return Some(fi);
         }
@@ -511,8 +547,8 @@ impl<'a> SecondPassParser<'a> {
 
     fn create_new_entity(&mut self, bitreader: &mut Bitreader, entity_id: &i32) -> Result<(), DemoParserError> {
         let cls_id: u32 = bitreader.read_nbits(8)?;
-        // Both of these are not used. Don't think they are interesting for the parser
-        let _serial = bitreader.read_nbits(NSERIALBITS)?;
+        let serial = bitreader.read_nbits(NSERIALBITS)?;
+        // Not used. Don't think it is interesting for the parser
         let _unknown = bitreader.read_varint();
 
         let entity_type = self.check_entity_type(&cls_id)?;
@@ -520,11 +556,15 @@ impl<'a> SecondPassParser<'a> {
             EntityType::Projectile => {
                 self.projectiles.insert(*entity_id);
             }
+            EntityType::Inferno => {
+                self.infernos.insert(*entity_id);
+            }
             EntityType::Rules => self.rules_entity_id = Some(*entity_id),
             EntityType::C4 => self.c4_entity_id = Some(*entity_id),
+            EntityType::PlantedC4 => self.planted_c4_entity_id = Some(*entity_id),
             _ => {}
         };
-        let entity = SecondPassParser::make_ent(entity_id, cls_id, entity_type);
+        let entity = SecondPassParser::make_ent(entity_id, cls_id, serial, entity_type);
         if self.entities.len() as i32 <= *entity_id {
             self.entities.resize(*entity_id as usize + 1, None);
         }
@@ -540,10 +580,11 @@ impl<'a> SecondPassParser<'a> {
         }
         Ok(())
     }
-    fn make_ent(entity_id: &i32, cls_id: u32, entity_type: EntityType) -> Entity {
+    fn make_ent(entity_id: &i32, cls_id: u32, serial: u32, entity_type: EntityType) -> Entity {
         Entity {
             entity_id: *entity_id,
             cls_id: cls_id,
+            serial,
             props: AHashMap::with_capacity(0),
             entity_type: entity_type,
         }
@@ -560,6 +601,8 @@ impl<'a> SecondPassParser<'a> {
             "CCSGameRulesProxy" => return Ok(EntityType::Rules),
             "CCSTeam" => return Ok(EntityType::Team),
             "CC4" => return Ok(EntityType::C4),
+            "CPlantedC4" => return Ok(EntityType::PlantedC4),
+            "CInferno" => return Ok(EntityType::Inferno),
             _ => {}
         }
         if class.name.contains("Projectile") || class.name == "CIncendiaryGrenade" {
@@ -575,3 +618,8 @@ fn generate_fp() -> FieldPath {
         last: 0,
     }
 }
+
+fn is_first_item_attribute_value(prop_id: u32) -> bool {
+    (ITEM_ATTRIBUTE_VALUE_OFFSET..ITEM_ATTRIBUTE_VALUE_OFFSET + MAX_ITEM_ATTRIBUTES * MAX_ITEM_ATTRIBUTES).contains(&prop_id)
+        && (prop_id - ITEM_ATTRIBUTE_VALUE_OFFSET).is_multiple_of(MAX_ITEM_ATTRIBUTES)
+}
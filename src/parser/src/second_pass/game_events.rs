@@ -2,13 +2,18 @@ use super::entities::RoundEnd;
 use crate::first_pass::prop_controller::PropInfo;
 use crate::first_pass::read_bits::DemoParserError;
 use crate::first_pass::stringtables::UserInfo;
+use crate::maps::ITEM_PRICES;
 use crate::maps::ROUND_WIN_REASON;
 use crate::maps::ROUND_WIN_REASON_TO_WINNER;
+use crate::second_pass::collect_data::CoordinateAxis;
+use crate::second_pass::collect_data::InventoryItem;
 use crate::second_pass::collect_data::PropType;
 use crate::second_pass::entities::GameEventInfo;
 use crate::second_pass::entities::PlayerMetaData;
 use crate::second_pass::parser_settings::SecondPassParser;
 use crate::second_pass::variants::*;
+use ahash::AHashMap;
+use csgoproto::cstrike15_usermessages::CCSUsrMsg_AdjustMoney;
 use csgoproto::cstrike15_usermessages::CCSUsrMsg_ServerRankUpdate;
 use csgoproto::networkbasetypes::csvcmsg_game_event::Key_t;
 use csgoproto::networkbasetypes::CNETMsg_SetConVar;
@@ -28,8 +33,13 @@ static INTERNALEVENTFIELDS: &'static [&str] = &[
 
 static ENTITIES_FIRST_EVENTS: &'static [&str] = &["inferno_startburn", "decoy_started", "inferno_expire"];
 static REMOVEDEVENTS: &'static [&str] = &["server_cvar", "round_end", "round_start"];
+// Made from the changes in m_hMyWeapons and m_hActiveWeapon
+pub static INVENTORY_EVENTS: &[&str] = &["item_bought", "item_picked_up", "item_dropped", "weapon_switched"];
 
 const ENTITYIDNONE: i32 = 2047;
+// Defaults for when the demo doesn't have mp_buytime or the tick interval
+pub const DEFAULT_BUY_TIME_SECONDS: f32 = 20.0;
+pub const DEFAULT_TICK_INTERVAL: f32 = 1.0 / 64.0;
 // https://developer.valvesoftware.com/wiki/SteamID
 const STEAMID64INDIVIDUALIDENTIFIER: u64 = 0x0110000100000000;
 
@@ -601,6 +611,234 @@ impl<'a> SecondPassParser<'a> {
         }
         Ok(())
     }
+    // The message is only sent to the player receiving the money, so it only shows up in POV
+    // demos and the player is the one recording
+    pub fn create_custom_event_adjust_money(&mut self, msg_bytes: &[u8]) -> Result<(), DemoParserError> {
+        self.game_events_counter.insert("money_adjusted".to_string());
+        if !self.wanted_events.contains(&"money_adjusted".to_string()) && self.wanted_events.first() != Some(&"all".to_string()) {
+            return Ok(());
+        }
+        let adjust_msg: CCSUsrMsg_AdjustMoney = match Message::parse_from_bytes(msg_bytes) {
+            Ok(m) => m,
+            Err(_e) => return Err(DemoParserError::MalformedMessage),
+        };
+        let mut fields = vec![EventField {
+            data: Some(Variant::I32(self.tick)),
+            name: "tick".to_string(),
+        }];
+        let client_name = self.header.get("client_name");
+        let entity_id = self
+            .players
+            .values()
+            .find(|player| player.name.is_some() && player.name.as_ref() == client_name)
+            .and_then(|player| player.player_entity_id);
+        if let Some(entity_id) = entity_id {
+            fields.push(self.create_player_name_field(entity_id, "user"));
+            fields.push(self.create_player_steamid_field(entity_id, "user"));
+            fields.extend(self.find_extra_props_events(entity_id, "user"));
+        }
+        fields.push(EventField {
+            data: Some(Variant::I32(adjust_msg.amount())),
+            name: "amount".to_string(),
+        });
+        fields.extend(self.find_non_player_props());
+        self.game_events.push(GameEvent {
+            name: "money_adjusted".to_string(),
+            fields,
+            tick: self.tick,
+        });
+        Ok(())
+    }
+    pub fn is_buy_time(&self) -> bool {
+        let rules_entid = match self.rules_entity_id {
+            Some(entid) => entid,
+            None => return false,
+        };
+        if let Some(id) = self.prop_controller.special_ids.is_freeze_period {
+            if let Ok(Variant::Bool(true)) = self.get_prop_from_ent(&id, &rules_entid) {
+                return true;
+            }
+        }
+        match self.prop_controller.special_ids.round_start_time {
+            Some(id) => match self.get_prop_from_ent(&id, &rules_entid) {
+                Ok(Variant::F32(round_start_time)) => {
+                    self.net_tick as f32 * self.tick_interval - round_start_time <= self.buy_time_seconds
+                }
+                _ => false,
+            },
+            None => false,
+        }
+    }
+    fn create_inventory_event(&mut self, name: &str, entity_id: i32, item: &InventoryItem, extra_fields: Vec<EventField>) {
+        self.game_events_counter.insert(name.to_string());
+        if !self.wanted_events.contains(&name.to_string()) && self.wanted_events.first() != Some(&"all".to_string()) {
+            return;
+        }
+        let mut fields = vec![EventField {
+            data: Some(Variant::I32(self.tick)),
+            name: "tick".to_string(),
+        }];
+        fields.push(self.create_player_name_field(entity_id, "user"));
+        fields.push(self.create_player_steamid_field(entity_id, "user"));
+        fields.extend(self.find_extra_props_events(entity_id, "user"));
+        fields.extend(self.find_non_player_props());
+        fields.push(EventField {
+            data: Some(Variant::String(item.name.clone())),
+            name: "item".to_string(),
+        });
+        fields.push(EventField {
+            data: Some(Variant::U64(item.unique_id)),
+            name: "item_id".to_string(),
+        });
+        fields.extend(extra_fields);
+        self.game_events.push(GameEvent {
+            name: name.to_string(),
+            fields,
+            tick: self.tick,
+        });
+    }
+    // Compares the inventory and active weapon of every player to the previous tick. Items that
+    // show up for the first time in the buy zone during buy time are bought, anything else that
+    // shows up is picked up. The inventory of players that just spawned is taken as is.
+    pub fn create_custom_events_inventory(&mut self) {
+        let mut current = AHashMap::default();
+        for entity_id in self.players.keys() {
+            if matches!(self.find_is_alive(entity_id), Ok(Variant::Bool(true))) {
+                current.insert(
+                    *entity_id,
+                    (self.find_inventory_items(entity_id), self.find_active_item(entity_id)),
+                );
+            }
+        }
+        let buy_time = self.is_buy_time();
+        let mut entity_ids: Vec<i32> = current.keys().copied().collect();
+        entity_ids.sort();
+        for entity_id in entity_ids {
+            let (items, active) = &current[&entity_id];
+            let (last_items, last_active) = match self.inventory_last.get(&entity_id) {
+                Some(last) => last.clone(),
+                None => {
+                    self.inventory_seen_items.extend(items.iter().map(|item| item.unique_id));
+                    continue;
+                }
+            };
+            for change in diff_inventory(&last_items, items, &last_active, active) {
+                match change {
+                    InventoryChange::Dropped(item) => {
+                        // Thrown grenades and used up items are gone or still held
+                        let still_exists = matches!(self.entities.get(item.entity_id as usize), Some(Some(_)));
+                        if !still_exists || self.find_item_holder(&item.entity_id) == Some(entity_id) {
+                            continue;
+                        }
+                        let position = [CoordinateAxis::X, CoordinateAxis::Y, CoordinateAxis::Z]
+                            .map(|axis| self.collect_cell_coordinate_player(axis, &entity_id).ok());
+                        let fields = ["x", "y", "z"]
+                            .iter()
+                            .zip(position)
+                            .map(|(name, data)| EventField {
+                                data,
+                                name: name.to_string(),
+                            })
+                            .collect();
+                        self.create_inventory_event("item_dropped", entity_id, item, fields);
+                    }
+                    InventoryChange::Added(item) => {
+                        let in_buy_zone = match self.prop_controller.special_ids.in_buy_zone {
+                            Some(id) => matches!(self.get_prop_from_ent(&id, &entity_id), Ok(Variant::Bool(true))),
+                            None => false,
+                        };
+                        let is_new = self.inventory_seen_items.insert(item.unique_id);
+                        match bought_price(item, is_new, in_buy_zone, buy_time) {
+                            Some(price) => {
+                                let fields = vec![EventField {
+                                    data: Some(Variant::I32(price)),
+                                    name: "price".to_string(),
+                                }];
+                                self.create_inventory_event("item_bought", entity_id, item, fields);
+                            }
+                            None => {
+                                let original_owner = self.find_item_original_owner(&item.entity_id);
+                                let original_owner_name = self
+                                    .players
+                                    .values()
+                                    .find(|p| p.steamid.is_some() && p.steamid == original_owner)
+                                    .and_then(|p| p.name.clone());
+                                let fields = vec![
+                                    EventField {
+                                        data: original_owner.map(|s| Variant::String(s.to_string())),
+                                        name: "original_owner_steamid".to_string(),
+                                    },
+                                    EventField {
+                                        data: original_owner_name.map(Variant::String),
+                                        name: "original_owner_name".to_string(),
+                                    },
+                                ];
+                                self.create_inventory_event("item_picked_up", entity_id, item, fields);
+                            }
+                        }
+                    }
+                    InventoryChange::Switched { previous, current } => {
+                        let fields = vec![
+                            EventField {
+                                data: Some(Variant::String(previous.name.clone())),
+                                name: "previous_item".to_string(),
+                            },
+                            EventField {
+                                data: Some(Variant::U64(previous.unique_id)),
+                                name: "previous_item_id".to_string(),
+                            },
+                        ];
+                        self.create_inventory_event("weapon_switched", entity_id, current, fields);
+                    }
+                }
+            }
+        }
+        // Dead players start over when they spawn
+        self.inventory_last = current;
+    }
+}
+#[derive(Debug, PartialEq)]
+pub enum InventoryChange<'a> {
+    Dropped(&'a InventoryItem),
+    Added(&'a InventoryItem),
+    Switched {
+        previous: &'a InventoryItem,
+        current: &'a InventoryItem,
+    },
+}
+// Drops first, then new items, then the weapon switch. Same order as the events come out.
+pub fn diff_inventory<'a>(
+    last_items: &'a [InventoryItem],
+    items: &'a [InventoryItem],
+    last_active: &'a Option<InventoryItem>,
+    active: &'a Option<InventoryItem>,
+) -> Vec<InventoryChange<'a>> {
+    let mut changes = vec![];
+    changes.extend(
+        last_items
+            .iter()
+            .filter(|item| !items.contains(item))
+            .map(InventoryChange::Dropped),
+    );
+    changes.extend(
+        items
+            .iter()
+            .filter(|item| !last_items.contains(item))
+            .map(InventoryChange::Added),
+    );
+    if let (Some(previous), Some(current)) = (last_active, active) {
+        if previous.unique_id != current.unique_id {
+            changes.push(InventoryChange::Switched { previous, current });
+        }
+    }
+    changes
+}
+// A new item is bought if nobody had it before and the player could be in the buy menu
+pub fn bought_price(item: &InventoryItem, is_new: bool, in_buy_zone: bool, buy_time: bool) -> Option<i32> {
+    match ITEM_PRICES.get(item.name.as_str()) {
+        Some(price) if is_new && in_buy_zone && buy_time => Some(*price),
+        _ => None,
+    }
 }
 // what is this shit
 fn parse_key(key: &Key_t) -> Option<Variant> {
@@ -647,3 +885,73 @@ impl Serialize for GameEvent {
         map.end()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::second_pass::collect_data::InventoryItem;
+    use crate::second_pass::game_events::*;
+
+    fn item(entity_id: i32, unique_id: u64, name: &str) -> InventoryItem {
+        InventoryItem {
+            entity_id,
+            unique_id,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_inventory() {
+        let knife = item(100, 1, "Knife");
+        let glock = item(101, 2, "Glock-18");
+        let ak = item(102, 3, "AK-47");
+        let last = vec![knife.clone(), glock.clone()];
+
+        // Bought or picked up
+        let now = vec![knife.clone(), glock.clone(), ak.clone()];
+        assert_eq!(diff_inventory(&last, &now, &None, &None), vec![InventoryChange::Added(&ak)]);
+        // Dropped
+        let now = vec![knife.clone()];
+        assert_eq!(
+            diff_inventory(&last, &now, &None, &None),
+            vec![InventoryChange::Dropped(&glock)]
+        );
+        // Switched
+        let (last_active, active) = (Some(glock.clone()), Some(knife.clone()));
+        assert_eq!(
+            diff_inventory(&last, &last, &last_active, &active),
+            vec![InventoryChange::Switched {
+                previous: &glock,
+                current: &knife,
+            }]
+        );
+        // Dropping the active weapon switches to the next one
+        let now = vec![knife.clone(), ak.clone()];
+        assert_eq!(
+            diff_inventory(&last, &now, &last_active, &active),
+            vec![
+                InventoryChange::Dropped(&glock),
+                InventoryChange::Added(&ak),
+                InventoryChange::Switched {
+                    previous: &glock,
+                    current: &knife,
+                },
+            ]
+        );
+        // Same entity id but a new item
+        let now = vec![knife.clone(), item(101, 4, "Glock-18")];
+        assert_eq!(diff_inventory(&last, &now, &None, &None).len(), 2);
+        assert!(diff_inventory(&last, &last, &active, &active).is_empty());
+    }
+
+    #[test]
+    fn test_bought_price() {
+        let ak = item(102, 3, "AK-47");
+        assert_eq!(bought_price(&ak, true, true, true), Some(2700));
+        // Picked up from the ground, outside the buy zone or after buy time
+        assert_eq!(bought_price(&ak, false, true, true), None);
+        assert_eq!(bought_price(&ak, true, false, true), None);
+        assert_eq!(bought_price(&ak, true, true, false), None);
+        // Items that can't be bought
+        assert_eq!(bought_price(&item(103, 4, "C4"), true, true, true), None);
+    }
+}
@@ -1,12 +1,19 @@
 use crate::first_pass::read_bits::DemoParserError;
 use crate::first_pass::sendtables::Serializer;
+use crate::maps::ACCOLADES;
+use crate::maps::CS_STATS;
 use crate::maps::PAINTKITS;
 use crate::maps::WEAPINDICIES;
 use crate::second_pass::parser_settings::ChatMessageRecord;
 use crate::second_pass::parser_settings::EconItem;
+use crate::second_pass::parser_settings::MatchStatsUpdateRecord;
+use crate::second_pass::parser_settings::PlayerAccolade;
 use crate::second_pass::parser_settings::PlayerEndMetaData;
+use crate::second_pass::parser_settings::PlayerStatsUpdateRecord;
 use crate::second_pass::parser_settings::SecondPassParser;
 use csgoproto::cstrike15_usermessages::CCSUsrMsg_EndOfMatchAllPlayersData;
+use csgoproto::cstrike15_usermessages::CCSUsrMsg_MatchStatsUpdate;
+use csgoproto::cstrike15_usermessages::CCSUsrMsg_PlayerStatsUpdate;
 use csgoproto::cstrike15_usermessages::CCSUsrMsg_SendPlayerItemDrops;
 use csgoproto::networkbasetypes::CNETMsg_SetConVar;
 use csgoproto::usermessages::CUserMessageSayText2;
@@ -87,28 +94,23 @@ impl<'a> SecondPassParser<'a> {
             Ok(msg) => msg,
             Err(_) => return Err(DemoParserError::MalformedMessage),
         };
-        /*
-        Todo parse "accolade", seems to be the awards at the end like "most mvps in game"
-        But seems to only have integers so need to figure out what they mean
-        example:
-
-        Accolade {
-            eaccolade: Some(
-                21,
-            ),
-            value: Some(
-                5100.0,
-            ),
-            position: Some(
-                1,
-            ),
-        }
-        */
         for player in &end_data.allplayerdata {
+            let accolade = player.nomination.as_ref().map(|nomination| PlayerAccolade {
+                steamid: player.xuid,
+                name: player.name.clone(),
+                eaccolade: nomination.eaccolade,
+                accolade: nomination
+                    .eaccolade
+                    .and_then(|id| ACCOLADES.get(&id))
+                    .map(|name| name.to_string()),
+                value: nomination.value,
+                position: nomination.position,
+            });
             self.player_end_data.push(PlayerEndMetaData {
                 name: player.name.clone(),
                 steamid: player.xuid,
                 team_number: player.teamnumber,
+                accolade,
             });
             for item in &player.items {
                 if item.itemid() != 0 {
@@ -143,12 +145,41 @@ impl<'a> SecondPassParser<'a> {
         }
         Ok(())
     }
-    pub fn parse_player_stats_update(&mut self, _bytes: &[u8]) -> Result<(), DemoParserError> {
-        // let upd: CCSUsrMsg_PlayerStatsUpdate = Message::parse_from_bytes(bytes);
+    pub fn parse_player_stats_update(&mut self, bytes: &[u8]) -> Result<(), DemoParserError> {
+        let update: CCSUsrMsg_PlayerStatsUpdate = match Message::parse_from_bytes(bytes) {
+            Ok(msg) => msg,
+            Err(_) => return Err(DemoParserError::MalformedMessage),
+        };
+        // The handle can point to the pawn or the controller
+        let entity_id = update.ehandle.map(|handle| (handle & 0x7FF) as i32);
+        let player = entity_id.and_then(|entity_id| {
+            self.players
+                .get(&entity_id)
+                .or_else(|| self.players.values().find(|p| p.controller_entid == Some(entity_id)))
+        });
+        let steamid = player.and_then(|p| p.steamid);
+        let name = player.and_then(|p| p.name.clone());
+        for stat in &update.stats {
+            self.player_stats_updates.push(PlayerStatsUpdateRecord {
+                tick: self.tick,
+                steamid,
+                name: name.clone(),
+                stat_id: stat.idx,
+                stat: stat.idx.and_then(|idx| CS_STATS.get(&idx)).map(|name| name.to_string()),
+                delta: stat.delta,
+            });
+        }
         Ok(())
     }
-    pub fn parse_file_info(&mut self, _bytes: &[u8]) -> Result<(), DemoParserError> {
-        // let _info: CDemoFileInfo = Message::parse_from_bytes(bytes);
+    pub fn parse_match_stats_update(&mut self, bytes: &[u8]) -> Result<(), DemoParserError> {
+        let update: CCSUsrMsg_MatchStatsUpdate = match Message::parse_from_bytes(bytes) {
+            Ok(msg) => msg,
+            Err(_) => return Err(DemoParserError::MalformedMessage),
+        };
+        self.match_stats_updates.push(MatchStatsUpdateRecord {
+            tick: self.tick,
+            update: update.update,
+        });
         Ok(())
     }
 }
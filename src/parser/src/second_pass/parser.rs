@@ -8,16 +8,27 @@ use crate::first_pass::stringtables::parse_userinfo;
 use crate::maps::demo_cmd_type_from_int;
 use crate::maps::netmessage_type_from_int;
 use crate::maps::NetmessageType::*;
+use crate::maps::BUTTONMAP;
+use crate::second_pass::collect_data::AimRecord;
+use crate::second_pass::collect_data::BombRecord;
+use crate::second_pass::collect_data::EconomyRecord;
+use crate::second_pass::collect_data::InfernoRecord;
+use crate::second_pass::collect_data::MovementRecord;
 use crate::second_pass::collect_data::ProjectileRecord;
+use crate::second_pass::collect_data::SpottedRecord;
+use crate::second_pass::collect_data::WeaponSkinRecord;
 use crate::second_pass::game_events::GameEvent;
 use crate::second_pass::parser_settings::SecondPassParser;
 use crate::second_pass::parser_settings::*;
 use crate::second_pass::variants::PropColumn;
+use crate::voice::VoiceRecord;
 use ahash::AHashMap;
 use ahash::AHashSet;
+use csgoproto::cs_usercmd::CSGOUserCmdPB;
 use csgoproto::demo::*;
 use csgoproto::netmessages::*;
 use csgoproto::networkbasetypes::CNETMsg_Tick;
+use itertools::Itertools;
 use protobuf::Message;
 use snap::raw::decompress_len;
 use snap::raw::Decoder as SnapDecoder;
@@ -36,11 +47,21 @@ pub struct SecondPassOutput {
     pub convars: AHashMap<String, String>,
     pub header: Option<AHashMap<String, String>>,
     pub player_md: Vec<PlayerEndMetaData>,
+    pub player_stats_updates: Vec<PlayerStatsUpdateRecord>,
+    pub match_stats_updates: Vec<MatchStatsUpdateRecord>,
+    pub user_cmds: Vec<UserCmdRecord>,
     pub game_events_counter: AHashSet<String>,
     pub prop_info: PropController,
     pub projectiles: Vec<ProjectileRecord>,
+    pub infernos: Vec<InfernoRecord>,
+    pub bombs: Vec<BombRecord>,
+    pub spotted: Vec<SpottedRecord>,
+    pub movement: Vec<MovementRecord>,
+    pub aim: Vec<AimRecord>,
+    pub economy: Vec<EconomyRecord>,
+    pub weapon_skins: Vec<WeaponSkinRecord>,
     pub ptr: usize,
-    pub voice_data: Vec<CSVCMsg_VoiceData>,
+    pub voice_data: Vec<VoiceRecord>,
 }
 impl<'a> SecondPassParser<'a> {
     pub fn start(&mut self, demo_bytes: &[u8]) -> Result<(), DemoParserError> {
@@ -97,6 +118,7 @@ impl<'a> SecondPassParser<'a> {
                     }
                     Ok(())
                 }
+                DEM_UserCmd if self.parse_user_cmds => self.parse_user_command_cmd(bytes),
                 DEM_Stop => break,
                 _ => Ok(()),
             };
@@ -133,12 +155,19 @@ impl<'a> SecondPassParser<'a> {
                 UM_SayText2 => self.parse_chat_messages(msg_bytes),
                 net_SetConVar => self.create_custom_event_parse_convars(msg_bytes),
                 CS_UM_PlayerStatsUpdate => self.parse_player_stats_update(msg_bytes),
+                CS_UM_MatchStatsUpdate => self.parse_match_stats_update(msg_bytes),
                 CS_UM_ServerRankUpdate => self.create_custom_event_rank_update(msg_bytes),
+                CS_UM_AdjustMoney => self.create_custom_event_adjust_money(msg_bytes),
                 net_Tick => self.parse_net_tick(msg_bytes),
                 svc_ClearAllStringTables => self.clear_stringtables(),
                 svc_VoiceData => {
-                    if let Ok(m) = Message::parse_from_bytes(msg_bytes) {
-                        self.voice_data.push(m);
+                    if let Ok(msg) = CSVCMsg_VoiceData::parse_from_bytes(msg_bytes) {
+                        // Used for the timing of the voice, not every server fills it
+                        let tick = match msg.tick {
+                            Some(tick) => tick as i32,
+                            None => self.tick.max(0),
+                        };
+                        self.voice_data.push(VoiceRecord { tick, msg });
                     }
                     Ok(())
                 }
@@ -221,6 +250,7 @@ impl<'a> SecondPassParser<'a> {
                 UM_SayText2 => self.parse_chat_messages(&msg_bytes),
                 net_SetConVar => self.create_custom_event_parse_convars(&msg_bytes),
                 CS_UM_PlayerStatsUpdate => self.parse_player_stats_update(&msg_bytes),
+                CS_UM_MatchStatsUpdate => self.parse_match_stats_update(msg_bytes),
                 svc_ServerInfo => self.parse_server_info(&msg_bytes),
                 net_Tick => self.parse_net_tick(&msg_bytes),
                 svc_ClearAllStringTables => self.clear_stringtables(),
@@ -243,8 +273,147 @@ impl<'a> SecondPassParser<'a> {
         self.cls_bits = Some((class_count as f32 + 1.).log2().ceil() as u32);
         Ok(())
     }
-    pub fn parse_user_command_cmd(&mut self, _data: &[u8]) -> Result<(), DemoParserError> {
-        // Only in pov demos. Maybe implement sometime. Includes buttons etc.
+    pub fn parse_user_command_cmd(&mut self, data: &[u8]) -> Result<(), DemoParserError> {
+        // Only in pov demos. A command that doesn't decode is skipped, it shouldn't fail the demo
+        let (pawn_handle, mut record) = match decode_user_cmd(data, self.tick) {
+            Some(decoded) => decoded,
+            None => return Ok(()),
+        };
+        let player = pawn_handle.and_then(|handle| self.players.get(&((handle & 0x7FF) as i32)));
+        record.steamid = player.and_then(|p| p.steamid);
+        record.name = player.and_then(|p| p.name.clone());
+        self.user_cmds.push(record);
         Ok(())
     }
 }
+
+// DEM_UserCmd into the record and the handle of the pawn, the player is filled in by the caller
+fn decode_user_cmd(data: &[u8], tick: i32) -> Option<(Option<u32>, UserCmdRecord)> {
+    let demo_cmd: CDemoUserCmd = Message::parse_from_bytes(data).ok()?;
+    let user_cmd: CSGOUserCmdPB = Message::parse_from_bytes(demo_cmd.data()).ok()?;
+    let base = user_cmd.base.as_ref()?;
+    let buttons = base.buttons_pb.as_ref().and_then(|b| b.buttonstate1);
+    let subtick_moves = base
+        .subtick_moves
+        .iter()
+        .map(|step| SubtickMove {
+            button: step.button,
+            button_name: step.button.and_then(button_name),
+            pressed: step.pressed,
+            when: step.when,
+        })
+        .collect();
+    let attack = user_cmd
+        .attack1_start_history_index
+        .and_then(|idx| user_cmd.input_history.get(idx as usize));
+    let record = UserCmdRecord {
+        tick,
+        steamid: None,
+        name: None,
+        cmd_number: demo_cmd.cmd_number.or(base.command_number),
+        tick_count: base.tick_count,
+        pitch: base.viewangles.as_ref().and_then(|a| a.x),
+        yaw: base.viewangles.as_ref().and_then(|a| a.y),
+        forward_move: base.forwardmove,
+        side_move: base.leftmove,
+        up_move: base.upmove,
+        buttons,
+        button_names: buttons.map(button_names).unwrap_or_default(),
+        weapon_select: base.weaponselect,
+        mouse_dx: base.mousedx,
+        mouse_dy: base.mousedy,
+        subtick_moves,
+        attack_render_tick: attack.and_then(|a| a.render_tick_count),
+        attack_render_tick_fraction: attack.and_then(|a| a.render_tick_fraction),
+        attack_player_tick: attack.and_then(|a| a.player_tick_count),
+        attack_player_tick_fraction: attack.and_then(|a| a.player_tick_fraction),
+    };
+    Some((base.pawn_entity_handle, record))
+}
+
+fn button_name(button: u64) -> Option<String> {
+    BUTTONMAP
+        .entries()
+        .find(|(_, mask)| **mask == button)
+        .map(|(name, _)| name.to_string())
+}
+
+// Sorted by bit so that the order doesn't depend on the map
+fn button_names(buttons: u64) -> Vec<String> {
+    BUTTONMAP
+        .entries()
+        .filter(|(_, mask)| buttons & **mask != 0)
+        .sorted_by_key(|(_, mask)| **mask)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::second_pass::parser::*;
+    use csgoproto::cs_usercmd::CSGOInputHistoryEntryPB;
+    use csgoproto::usercmd::CBaseUserCmdPB;
+    use csgoproto::usercmd::CSubtickMoveStep;
+
+    fn subtick_move(button: u64, pressed: bool, when: f32) -> CSubtickMoveStep {
+        let mut step = CSubtickMoveStep::new();
+        step.set_button(button);
+        step.set_pressed(pressed);
+        step.set_when(when);
+        step
+    }
+
+    #[test]
+    fn test_decode_user_cmd() {
+        let mut base = CBaseUserCmdPB::new();
+        base.set_command_number(40);
+        base.set_tick_count(1234);
+        base.set_pawn_entity_handle((3 << 15) | 5);
+        base.set_forwardmove(1.0);
+        base.buttons_pb
+            .mut_or_insert_default()
+            .set_buttonstate1(BUTTONMAP["FIRE"] | BUTTONMAP["FORWARD"]);
+        base.viewangles.mut_or_insert_default().set_x(-3.5);
+        base.viewangles.mut_or_insert_default().set_y(90.0);
+        base.subtick_moves.push(subtick_move(BUTTONMAP["FORWARD"], true, 0.25));
+        base.subtick_moves.push(subtick_move(BUTTONMAP["FIRE"], true, 0.75));
+        let mut history = CSGOInputHistoryEntryPB::new();
+        history.set_render_tick_count(1233);
+        history.set_render_tick_fraction(0.5);
+        let mut user_cmd = CSGOUserCmdPB::new();
+        user_cmd.base = Some(base).into();
+        user_cmd.input_history.push(CSGOInputHistoryEntryPB::new());
+        user_cmd.input_history.push(history);
+        user_cmd.set_attack1_start_history_index(1);
+        let mut demo_cmd = CDemoUserCmd::new();
+        demo_cmd.set_cmd_number(41);
+        demo_cmd.set_data(user_cmd.write_to_bytes().unwrap());
+
+        let (pawn_handle, record) = decode_user_cmd(&demo_cmd.write_to_bytes().unwrap(), 500).unwrap();
+        assert_eq!(pawn_handle.map(|h| h & 0x7FF), Some(5));
+        assert_eq!(
+            (record.tick, record.cmd_number, record.tick_count),
+            (500, Some(41), Some(1234))
+        );
+        assert_eq!(
+            (record.pitch, record.yaw, record.forward_move),
+            (Some(-3.5), Some(90.0), Some(1.0))
+        );
+        assert_eq!(record.button_names, vec!["FIRE", "FORWARD"]);
+        let moves: Vec<(Option<String>, Option<f32>)> =
+            record.subtick_moves.iter().map(|m| (m.button_name.clone(), m.when)).collect();
+        assert_eq!(
+            moves,
+            vec![
+                (Some("FORWARD".to_string()), Some(0.25)),
+                (Some("FIRE".to_string()), Some(0.75)),
+            ]
+        );
+        assert_eq!(
+            (record.attack_render_tick, record.attack_render_tick_fraction),
+            (Some(1233), Some(0.5))
+        );
+        assert_eq!(record.attack_player_tick, None);
+        assert!(decode_user_cmd(&[0xFF, 0xFF], 500).is_none());
+    }
+}
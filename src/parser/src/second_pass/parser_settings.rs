@@ -1,26 +1,43 @@
 use crate::first_pass::parser::FirstPassOutput;
 use crate::first_pass::prop_controller::PropController;
+use crate::first_pass::prop_controller::PLAYER_HISTORY_PROPS;
 use crate::first_pass::read_bits::DemoParserError;
 use crate::first_pass::sendtables::Serializer;
 use crate::first_pass::stringtables::StringTable;
 use crate::first_pass::stringtables::UserInfo;
+use crate::radar::MapOverview;
+use crate::second_pass::collect_data::finish_flick;
+use crate::second_pass::collect_data::finish_tracking;
+use crate::second_pass::collect_data::AimRecord;
+use crate::second_pass::collect_data::BombRecord;
+use crate::second_pass::collect_data::EconomyRecord;
+use crate::second_pass::collect_data::InfernoRecord;
+use crate::second_pass::collect_data::InventoryItem;
+use crate::second_pass::collect_data::MovementRecord;
+use crate::second_pass::collect_data::PlayerSnapshot;
 use crate::second_pass::collect_data::ProjectileRecord;
+use crate::second_pass::collect_data::SpottedRecord;
+use crate::second_pass::collect_data::WeaponSkinRecord;
 use crate::second_pass::decoder::QfMapper;
 use crate::second_pass::entities::Entity;
 use crate::second_pass::entities::PlayerMetaData;
 use crate::second_pass::game_events::GameEvent;
+use crate::second_pass::game_events::DEFAULT_BUY_TIME_SECONDS;
+use crate::second_pass::game_events::DEFAULT_TICK_INTERVAL;
 use crate::second_pass::other_netmessages::Class;
 use crate::second_pass::parser::SecondPassOutput;
 use crate::second_pass::path_ops::FieldPath;
 use crate::second_pass::variants::PropColumn;
+use crate::voice::VoiceRecord;
+use crate::zones::Zones;
 use ahash::AHashMap;
 use ahash::AHashSet;
 use ahash::HashMap;
 use ahash::RandomState;
 use csgoproto::netmessages::csvcmsg_game_event_list::Descriptor_t;
-use csgoproto::netmessages::CSVCMsg_VoiceData;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::VecDeque;
 use std::env;
 
 const HUF_LOOKUPTABLE_MAXVALUE: u32 = (1 << 17) - 1;
@@ -33,6 +50,53 @@ pub struct SecondPassParser<'a> {
     pub stringtable_players: BTreeMap<u64, UserInfo>,
     pub net_tick: u32,
     pub parse_inventory: bool,
+    // Recent positions and angles of every player, only kept when events need them
+    pub track_player_history: bool,
+    pub player_history: AHashMap<i32, VecDeque<PlayerSnapshot>>,
+    // "A saw B" intervals from the spotted masks, only collected when events ask for the mask
+    pub collect_spotted: bool,
+    // (observer controller id, target entity id) -> interval that is still going on
+    pub spotted_open: AHashMap<(i32, i32), SpottedRecord>,
+    pub spotted_records: Vec<SpottedRecord>,
+    // Running/walking/crouched/airborne/still segments of every player, only collected when
+    // events ask for is_walking
+    pub collect_movement: bool,
+    // entity id -> (segment that is still going on, last position)
+    pub movement_open: AHashMap<i32, (MovementRecord, [f32; 2])>,
+    pub movement_records: Vec<MovementRecord>,
+    // Flicks and tracking of unspotted enemies, only collected when events ask for angular_velocity
+    pub collect_aim: bool,
+    pub aim_flicks_open: AHashMap<i32, AimRecord>,
+    // (observer entity id, target entity id) -> (interval, summed offset, ticks)
+    pub aim_tracking_open: AHashMap<(i32, i32), (AimRecord, f32, i32)>,
+    pub aim_records: Vec<AimRecord>,
+    // Only diffed when an inventory event is wanted
+    pub collect_inventory_events: bool,
+    // entity id -> (items, active weapon) of the previous tick
+    pub inventory_last: AHashMap<i32, (Vec<InventoryItem>, Option<InventoryItem>)>,
+    // Unique ids of every item that has been in someone's inventory
+    pub inventory_seen_items: AHashSet<u64>,
+    // Money and equipment of every player at the end of buy time, only collected when events ask
+    // for cash_earned_this_round
+    pub collect_economy: bool,
+    // Buy time and freeze period on the previous tick
+    pub economy_was_buy_time: bool,
+    pub economy_was_freeze_period: bool,
+    // mp_buytime and the length of a tick in seconds, buy time is measured in game time
+    pub buy_time_seconds: f32,
+    pub tick_interval: f32,
+    // steamid -> record of the round that is still going on
+    pub economy_open: AHashMap<(u64, Option<String>), EconomyRecord>,
+    pub economy_records: Vec<EconomyRecord>,
+    // Skin, stickers and owners of every weapon entity, only collected when events ask for weapon_skin
+    pub collect_weapon_skins: bool,
+    // (entity unique id, owner steamid) already in weapon_skins
+    pub weapon_skins_seen: AHashSet<(u64, Option<u64>)>,
+    // Entities created or updated since the weapon skins were last collected
+    pub weapon_skins_updated: AHashSet<i32>,
+    pub weapon_skins: Vec<WeaponSkinRecord>,
+    pub overview: Option<MapOverview>,
+    pub zones: Option<Zones>,
     pub paths: Vec<FieldPath>,
     pub ptr: usize,
     pub parse_all_packets: bool,
@@ -48,6 +112,7 @@ pub struct SecondPassParser<'a> {
     pub string_tables: Vec<StringTable>,
     pub rules_entity_id: Option<i32>,
     pub c4_entity_id: Option<i32>,
+    pub planted_c4_entity_id: Option<i32>,
     pub game_events_counter: AHashSet<String>,
     pub baselines: AHashMap<u32, Vec<u8>, RandomState>,
     pub projectiles: BTreeSet<i32>,
@@ -56,7 +121,10 @@ pub struct SecondPassParser<'a> {
     pub wanted_ticks: AHashSet<i32>,
     // Output from parsing
     pub projectile_records: Vec<ProjectileRecord>,
-    pub voice_data: Vec<CSVCMsg_VoiceData>,
+    pub infernos: BTreeSet<i32>,
+    pub inferno_records: Vec<InfernoRecord>,
+    pub bomb_records: Vec<BombRecord>,
+    pub voice_data: Vec<VoiceRecord>,
     pub output: AHashMap<u32, PropColumn, RandomState>,
     pub header: HashMap<String, String>,
     pub skins: Vec<EconItem>,
@@ -64,10 +132,16 @@ pub struct SecondPassParser<'a> {
     pub convars: AHashMap<String, String>,
     pub chat_messages: Vec<ChatMessageRecord>,
     pub player_end_data: Vec<PlayerEndMetaData>,
+    pub player_stats_updates: Vec<PlayerStatsUpdateRecord>,
+    pub match_stats_updates: Vec<MatchStatsUpdateRecord>,
+    pub user_cmds: Vec<UserCmdRecord>,
     // Settings
     pub wanted_events: Vec<String>,
     pub parse_entities: bool,
     pub parse_projectiles: bool,
+    pub collect_infernos: bool,
+    pub collect_bomb: bool,
+    pub parse_user_cmds: bool,
     pub is_debug_mode: bool,
 }
 #[derive(Debug, Clone)]
@@ -94,6 +168,60 @@ pub struct ChatMessageRecord {
     pub param3: Option<String>,
     pub param4: Option<String>,
 }
+// One stat of CCSUsrMsg_PlayerStatsUpdate, the server sends how much each stat changed
+#[derive(Debug, Clone)]
+pub struct PlayerStatsUpdateRecord {
+    pub tick: i32,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    pub stat_id: Option<i32>,
+    // Name from CS_STATS
+    pub stat: Option<String>,
+    pub delta: Option<i32>,
+}
+#[derive(Debug, Clone)]
+pub struct MatchStatsUpdateRecord {
+    pub tick: i32,
+    // Kept as sent. The protobuf only has a string field, the text inside is built by the server
+    // for its own UI and isn't documented or versioned, so splitting it into columns would
+    // guess at a format that can change with any game update
+    pub update: Option<String>,
+}
+// One DEM_UserCmd, only in POV demos
+#[derive(Debug, Clone)]
+pub struct UserCmdRecord {
+    pub tick: i32,
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    pub cmd_number: Option<i32>,
+    // Client tick the command was made on
+    pub tick_count: Option<i32>,
+    pub pitch: Option<f32>,
+    pub yaw: Option<f32>,
+    pub forward_move: Option<f32>,
+    pub side_move: Option<f32>,
+    pub up_move: Option<f32>,
+    // Held buttons, bits as in BUTTONMAP
+    pub buttons: Option<u64>,
+    pub button_names: Vec<String>,
+    pub weapon_select: Option<i32>,
+    pub mouse_dx: Option<i32>,
+    pub mouse_dy: Option<i32>,
+    pub subtick_moves: Vec<SubtickMove>,
+    // Where inside the frame the client pressed attack, from the input history of the command
+    pub attack_render_tick: Option<i32>,
+    pub attack_render_tick_fraction: Option<f32>,
+    pub attack_player_tick: Option<i32>,
+    pub attack_player_tick_fraction: Option<f32>,
+}
+#[derive(Debug, Clone)]
+pub struct SubtickMove {
+    pub button: Option<u64>,
+    pub button_name: Option<String>,
+    pub pressed: Option<bool>,
+    // Fraction of the tick, 0.0 - 1.0
+    pub when: Option<f32>,
+}
 #[derive(Debug, Clone)]
 pub struct EconItem {
     pub account_id: Option<u32>,
@@ -118,6 +246,18 @@ pub struct PlayerEndMetaData {
     pub steamid: Option<u64>,
     pub name: Option<String>,
     pub team_number: Option<i32>,
+    pub accolade: Option<PlayerAccolade>,
+}
+// The end of match award a player got, like "most mvps" or "most utility damage"
+#[derive(Debug, Clone)]
+pub struct PlayerAccolade {
+    pub steamid: Option<u64>,
+    pub name: Option<String>,
+    // Raw eaccolade and its name from ACCOLADES
+    pub eaccolade: Option<i32>,
+    pub accolade: Option<String>,
+    pub value: Option<f32>,
+    pub position: Option<i32>,
 }
 
 impl<'a> SecondPassParser<'a> {
@@ -132,9 +272,41 @@ impl<'a> SecondPassParser<'a> {
             item_drops: self.item_drops,
             header: None,
             player_md: self.player_end_data,
+            player_stats_updates: self.player_stats_updates,
+            match_stats_updates: self.match_stats_updates,
+            user_cmds: self.user_cmds,
             game_events_counter: self.game_events_counter,
             prop_info: PropController::new(vec![], vec![], AHashMap::default(), false),
             projectiles: self.projectile_records,
+            infernos: self.inferno_records,
+            bombs: self.bomb_records,
+            spotted: self
+                .spotted_records
+                .into_iter()
+                .chain(self.spotted_open.into_values())
+                .collect(),
+            movement: self
+                .movement_records
+                .into_iter()
+                .chain(self.movement_open.into_values().map(|(record, _)| record))
+                .collect(),
+            // Flicks and tracking still going when the demo ends
+            aim: self
+                .aim_records
+                .into_iter()
+                .chain(self.aim_flicks_open.into_values().filter_map(finish_flick))
+                .chain(
+                    self.aim_tracking_open
+                        .into_values()
+                        .filter_map(|(record, offset_sum, count)| finish_tracking(record, offset_sum, count)),
+                )
+                .collect(),
+            economy: self
+                .economy_records
+                .into_iter()
+                .chain(self.economy_open.into_values())
+                .collect(),
+            weapon_skins: self.weapon_skins,
             ptr: self.ptr,
         }
     }
@@ -159,11 +331,57 @@ impl<'a> SecondPassParser<'a> {
                 .prop_controller
                 .wanted_player_props
                 .contains(&"inventory".to_string()),
+            // angular_velocity needs the previous tick in parse_ticks too
+            track_player_history: first_pass_output.settings.track_player_history
+                || !first_pass_output.settings.wanted_events.is_empty()
+                    && first_pass_output
+                        .prop_controller
+                        .wanted_player_props
+                        .iter()
+                        .any(|p| PLAYER_HISTORY_PROPS.contains(&p.as_str()))
+                || first_pass_output
+                    .prop_controller
+                    .wanted_player_props
+                    .contains(&"angular_velocity".to_string()),
+            player_history: AHashMap::default(),
+            collect_spotted: first_pass_output.settings.collect_spotted,
+            spotted_open: AHashMap::default(),
+            spotted_records: vec![],
+            collect_movement: first_pass_output.settings.collect_movement,
+            movement_open: AHashMap::default(),
+            movement_records: vec![],
+            collect_aim: first_pass_output.settings.collect_aim,
+            aim_flicks_open: AHashMap::default(),
+            aim_tracking_open: AHashMap::default(),
+            aim_records: vec![],
+            collect_inventory_events: first_pass_output.settings.wants_inventory_events(),
+            inventory_last: AHashMap::default(),
+            inventory_seen_items: AHashSet::default(),
+            collect_economy: first_pass_output.settings.collect_economy,
+            economy_was_buy_time: false,
+            economy_was_freeze_period: false,
+            buy_time_seconds: first_pass_output
+                .convars
+                .get("mp_buytime")
+                .and_then(|v| v.parse::<f32>().ok())
+                .unwrap_or(DEFAULT_BUY_TIME_SECONDS),
+            tick_interval: first_pass_output.tick_interval.unwrap_or(DEFAULT_TICK_INTERVAL),
+            economy_open: AHashMap::default(),
+            economy_records: vec![],
+            collect_weapon_skins: first_pass_output.settings.collect_weapon_skins,
+            weapon_skins_seen: AHashSet::default(),
+            weapon_skins_updated: AHashSet::default(),
+            weapon_skins: vec![],
+            overview: first_pass_output.overview.clone(),
+            zones: first_pass_output.zones.clone(),
             net_tick: 0,
             c4_entity_id: None,
+            planted_c4_entity_id: None,
             stringtable_players: first_pass_output.stringtable_players,
             is_debug_mode: debug,
             projectile_records: vec![],
+            inferno_records: vec![],
+            bomb_records: vec![],
             parse_all_packets: parse_all_packets,
             wanted_players: first_pass_output.wanted_players.clone(),
             wanted_ticks: first_pass_output.wanted_ticks.clone(),
@@ -183,19 +401,26 @@ impl<'a> SecondPassParser<'a> {
             wanted_events: first_pass_output.settings.wanted_events.clone(),
             parse_entities: first_pass_output.settings.parse_ents,
             projectiles: BTreeSet::default(),
+            infernos: BTreeSet::default(),
             baselines: first_pass_output.baselines.clone(),
             string_tables: first_pass_output.string_tables.clone(),
             teams: Teams::new(),
             game_events_counter: AHashSet::default(),
             parse_projectiles: first_pass_output.settings.parse_projectiles,
+            collect_infernos: first_pass_output.settings.collect_infernos,
+            collect_bomb: first_pass_output.settings.collect_bomb,
+            parse_user_cmds: first_pass_output.settings.parse_user_cmds,
             rules_entity_id: None,
             convars: AHashMap::default(),
             chat_messages: vec![],
             item_drops: vec![],
             skins: vec![],
             player_end_data: vec![],
+            player_stats_updates: vec![],
+            match_stats_updates: vec![],
+            user_cmds: vec![],
             huffman_lookup_table: &first_pass_output.settings.huffman_lookup_table,
-            header: HashMap::default(),
+            header: first_pass_output.header.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
         })
     }
 }
@@ -230,12 +455,35 @@ pub struct SpecialIDs {
     pub m_cell_z_grenade: Option<u32>,
 
     pub grenade_owner_id: Option<u32>,
+    pub grenade_bounces: Option<u32>,
+    pub inferno_fire_count: Option<u32>,
+    pub bomb_site: Option<u32>,
+    pub bomb_blow_time: Option<u32>,
+    pub bomb_being_defused: Option<u32>,
+    pub bomb_defuser: Option<u32>,
+    pub bomb_defuse_countdown: Option<u32>,
+    pub bomb_defuse_length: Option<u32>,
     pub buttons: Option<u32>,
     pub eye_angles: Option<u32>,
 
     pub orig_own_low: Option<u32>,
     pub orig_own_high: Option<u32>,
+    pub item_id_high: Option<u32>,
+    pub item_id_low: Option<u32>,
+    pub item_quality: Option<u32>,
+    pub item_custom_name: Option<u32>,
     pub life_state: Option<u32>,
+    pub player_flags: Option<u32>,
+    pub spotted_by_mask: Option<u32>,
+    pub ducked: Option<u32>,
+    pub is_walking: Option<u32>,
+    pub in_buy_zone: Option<u32>,
+    pub round_start_equip_value: Option<u32>,
+    pub current_equip_value: Option<u32>,
+    pub balance: Option<u32>,
+    pub start_balance: Option<u32>,
+    pub cash_spent_this_round: Option<u32>,
+    pub cash_earned_this_round: Option<u32>,
 
     pub h_owner_entity: Option<u32>,
     pub agent_skin_idx: Option<u32>,
@@ -243,11 +491,17 @@ pub struct SpecialIDs {
 
     pub round_win_reason: Option<u32>,
     pub is_freeze_period: Option<u32>,
+    pub round_start_time: Option<u32>,
+    pub ct_losing_streak: Option<u32>,
+    pub t_losing_streak: Option<u32>,
 }
 impl SpecialIDs {
     pub fn new() -> Self {
         SpecialIDs {
             is_freeze_period: None,
+            round_start_time: None,
+            ct_losing_streak: None,
+            t_losing_streak: None,
             round_win_reason: None,
             total_rounds_played: None,
             h_owner_entity: None,
@@ -273,11 +527,34 @@ impl SpecialIDs {
             m_vec_y_grenade: None,
             m_vec_z_grenade: None,
             grenade_owner_id: None,
+            grenade_bounces: None,
+            inferno_fire_count: None,
+            bomb_site: None,
+            bomb_blow_time: None,
+            bomb_being_defused: None,
+            bomb_defuser: None,
+            bomb_defuse_countdown: None,
+            bomb_defuse_length: None,
             buttons: None,
             eye_angles: None,
             orig_own_high: None,
+            item_id_high: None,
+            item_id_low: None,
+            item_quality: None,
+            item_custom_name: None,
             orig_own_low: None,
             life_state: None,
+            player_flags: None,
+            spotted_by_mask: None,
+            ducked: None,
+            is_walking: None,
+            in_buy_zone: None,
+            round_start_equip_value: None,
+            current_equip_value: None,
+            balance: None,
+            start_balance: None,
+            cash_spent_this_round: None,
+            cash_earned_this_round: None,
             agent_skin_idx: None,
         }
     }
@@ -1,6 +1,17 @@
+use crate::first_pass::parser::FileInfo;
 use crate::first_pass::prop_controller::PropInfo;
+use crate::second_pass::collect_data::AimRecord;
+use crate::second_pass::collect_data::BombRecord;
+use crate::second_pass::collect_data::EconomyRecord;
+use crate::second_pass::collect_data::InfernoRecord;
+use crate::second_pass::collect_data::MovementRecord;
 use crate::second_pass::collect_data::ProjectileRecord;
-use crate::second_pass::parser_settings::{EconItem, PlayerEndMetaData};
+use crate::second_pass::collect_data::SpottedRecord;
+use crate::second_pass::collect_data::WeaponSkinRecord;
+use crate::second_pass::collect_data::WeaponSticker;
+use crate::second_pass::parser_settings::{
+    EconItem, MatchStatsUpdateRecord, PlayerAccolade, PlayerEndMetaData, SubtickMove, UserCmdRecord,
+};
 use ahash::{HashMap, HashMapExt};
 use itertools::Itertools;
 use memmap2::Mmap;
@@ -486,6 +497,87 @@ impl Serialize for PlayerEndMetaData {
         state.end()
     }
 }
+impl Serialize for PlayerAccolade {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PlayerAccolade", 6)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("accolade", &self.accolade)?;
+        state.serialize_field("eaccolade", &self.eaccolade)?;
+        state.serialize_field("value", &self.value)?;
+        state.serialize_field("position", &self.position)?;
+        state.end()
+    }
+}
+impl Serialize for FileInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("FileInfo", 4)?;
+        state.serialize_field("playback_time", &self.playback_time)?;
+        state.serialize_field("playback_ticks", &self.playback_ticks)?;
+        state.serialize_field("playback_frames", &self.playback_frames)?;
+        state.serialize_field("round_start_ticks", &self.round_start_ticks)?;
+        state.end()
+    }
+}
+impl Serialize for MatchStatsUpdateRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("MatchStatsUpdateRecord", 2)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("update", &self.update)?;
+        state.end()
+    }
+}
+impl Serialize for UserCmdRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("UserCmdRecord", 21)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("cmd_number", &self.cmd_number)?;
+        state.serialize_field("tick_count", &self.tick_count)?;
+        state.serialize_field("pitch", &self.pitch)?;
+        state.serialize_field("yaw", &self.yaw)?;
+        state.serialize_field("forward_move", &self.forward_move)?;
+        state.serialize_field("side_move", &self.side_move)?;
+        state.serialize_field("up_move", &self.up_move)?;
+        state.serialize_field("buttons", &self.buttons.map(|b| b.to_string()))?;
+        state.serialize_field("button_names", &self.button_names)?;
+        state.serialize_field("weapon_select", &self.weapon_select)?;
+        state.serialize_field("mouse_dx", &self.mouse_dx)?;
+        state.serialize_field("mouse_dy", &self.mouse_dy)?;
+        state.serialize_field("subtick_moves", &self.subtick_moves)?;
+        state.serialize_field("attack_render_tick", &self.attack_render_tick)?;
+        state.serialize_field("attack_render_tick_fraction", &self.attack_render_tick_fraction)?;
+        state.serialize_field("attack_player_tick", &self.attack_player_tick)?;
+        state.serialize_field("attack_player_tick_fraction", &self.attack_player_tick_fraction)?;
+        state.end()
+    }
+}
+impl Serialize for SubtickMove {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("SubtickMove", 4)?;
+        state.serialize_field("button", &self.button.map(|b| b.to_string()))?;
+        state.serialize_field("button_name", &self.button_name)?;
+        state.serialize_field("pressed", &self.pressed)?;
+        state.serialize_field("when", &self.when)?;
+        state.end()
+    }
+}
 impl Serialize for EconItem {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -520,7 +612,7 @@ impl Serialize for ProjectileRecord {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct("ProjectileRecord", 7)?;
+        let mut state = serializer.serialize_struct("ProjectileRecord", 15)?;
         let steamid = match self.steamid {
             Some(u) => Some(u.to_string()),
             None => None,
@@ -528,11 +620,166 @@ impl Serialize for ProjectileRecord {
         state.serialize_field("steamid", &steamid)?;
         state.serialize_field("grenade_type", &self.grenade_type)?;
         state.serialize_field("name", &self.name)?;
+        state.serialize_field("team_num", &self.team_num)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("x", &self.x)?;
+        state.serialize_field("y", &self.y)?;
+        state.serialize_field("z", &self.z)?;
+        state.serialize_field("entity_id", &self.entity_id)?;
+        state.serialize_field("unique_id", &self.unique_id)?;
+        state.serialize_field("bounces", &self.bounces)?;
+        state.serialize_field("radar_x", &self.radar_x)?;
+        state.serialize_field("radar_y", &self.radar_y)?;
+        state.serialize_field("radar_level", &self.radar_level)?;
+        state.serialize_field("zone", &self.zone)?;
+        state.end()
+    }
+}
+impl Serialize for InfernoRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("InfernoRecord", 7)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("entity_id", &self.entity_id)?;
+        state.serialize_field("unique_id", &self.unique_id)?;
+        state.serialize_field("fire_count", &self.fire_count)?;
+        state.serialize_field("fire_positions", &self.fire_positions)?;
+        state.end()
+    }
+}
+impl Serialize for BombRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("BombRecord", 13)?;
         state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("entity_id", &self.entity_id)?;
+        state.serialize_field("is_planted", &self.is_planted)?;
+        state.serialize_field("carrier_steamid", &self.carrier_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("carrier_name", &self.carrier_name)?;
         state.serialize_field("x", &self.x)?;
         state.serialize_field("y", &self.y)?;
         state.serialize_field("z", &self.z)?;
+        state.serialize_field("site", &self.site)?;
+        state.serialize_field("time_left", &self.time_left)?;
+        state.serialize_field("being_defused", &self.being_defused)?;
+        state.serialize_field("defuser_steamid", &self.defuser_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("defuse_progress", &self.defuse_progress)?;
+        state.end()
+    }
+}
+impl Serialize for SpottedRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("SpottedRecord", 6)?;
+        state.serialize_field("observer_steamid", &self.observer_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("observer_name", &self.observer_name)?;
+        state.serialize_field("target_steamid", &self.target_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("target_name", &self.target_name)?;
+        state.serialize_field("start_tick", &self.start_tick)?;
+        state.serialize_field("end_tick", &self.end_tick)?;
+        state.end()
+    }
+}
+impl Serialize for MovementRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("MovementRecord", 6)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("state", &self.state)?;
+        state.serialize_field("start_tick", &self.start_tick)?;
+        state.serialize_field("end_tick", &self.end_tick)?;
+        state.serialize_field("distance", &self.distance)?;
+        state.end()
+    }
+}
+impl Serialize for AimRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("AimRecord", 12)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("steamid", &self.steamid.map(|s| s.to_string()))?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("target_steamid", &self.target_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("target_name", &self.target_name)?;
+        state.serialize_field("start_tick", &self.start_tick)?;
+        state.serialize_field("end_tick", &self.end_tick)?;
+        state.serialize_field("angle", &self.angle)?;
+        state.serialize_field("peak_speed", &self.peak_speed)?;
+        state.serialize_field("peak_acceleration", &self.peak_acceleration)?;
+        state.serialize_field("target_offset", &self.target_offset)?;
+        state.serialize_field("target_spotted", &self.target_spotted)?;
+        state.end()
+    }
+}
+impl Serialize for EconomyRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("EconomyRecord", 12)?;
+        state.serialize_field("steamid", &self.steamid.to_string())?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("team", &self.team)?;
+        state.serialize_field("round", &self.round)?;
+        state.serialize_field("tick", &self.tick)?;
+        state.serialize_field("start_balance", &self.start_balance)?;
+        state.serialize_field("balance", &self.balance)?;
+        state.serialize_field("spent", &self.spent)?;
+        state.serialize_field("start_equip_value", &self.start_equip_value)?;
+        state.serialize_field("equip_value", &self.equip_value)?;
+        state.serialize_field("earned", &self.earned)?;
+        state.serialize_field("loss_streak", &self.loss_streak)?;
+        state.end()
+    }
+}
+impl Serialize for WeaponSticker {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("WeaponSticker", 3)?;
+        state.serialize_field("slot", &self.slot)?;
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("wear", &self.wear)?;
+        state.end()
+    }
+}
+impl Serialize for WeaponSkinRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("WeaponSkinRecord", 17)?;
+        state.serialize_field("tick", &self.tick)?;
         state.serialize_field("entity_id", &self.entity_id)?;
+        state.serialize_field("item_id", &self.item_id.map(|s| s.to_string()))?;
+        state.serialize_field("def_index", &self.def_index)?;
+        state.serialize_field("weapon", &self.weapon)?;
+        state.serialize_field("paint_index", &self.paint_index)?;
+        state.serialize_field("skin_name", &self.skin_name)?;
+        state.serialize_field("paint_wear", &self.paint_wear)?;
+        state.serialize_field("paint_seed", &self.paint_seed)?;
+        state.serialize_field("stattrak", &self.stattrak)?;
+        state.serialize_field("quality", &self.quality)?;
+        state.serialize_field("custom_name", &self.custom_name)?;
+        state.serialize_field("stickers", &self.stickers)?;
+        state.serialize_field("charm_id", &self.charm_id)?;
+        state.serialize_field("owner_steamid", &self.owner_steamid.map(|s| s.to_string()))?;
+        state.serialize_field("owner_name", &self.owner_name)?;
+        state.serialize_field("original_owner_steamid", &self.original_owner_steamid.map(|s| s.to_string()))?;
         state.end()
     }
 }
@@ -0,0 +1,355 @@
+use crate::first_pass::read_bits::DemoParserError;
+use csgoproto::netmessages::CSVCMsg_VoiceData;
+use csgoproto::netmessages::VoiceDataFormat_t;
+use std::collections::BTreeMap;
+
+// Voice is always 16 bit mono 48 kHz
+pub const VOICE_SAMPLE_RATE: u32 = 48000;
+pub const SAMPLES_PER_TICK: usize = VOICE_SAMPLE_RATE as usize / 64;
+// Longest Opus frame (120 ms)
+pub const MAX_FRAME_SAMPLES: usize = 5760;
+
+// Payload types of the Steam voice format
+const STEAM_PAYLOAD_SILENCE: u8 = 0;
+const STEAM_PAYLOAD_OPUS_PLC: u8 = 6;
+const STEAM_PAYLOAD_SAMPLE_RATE: u8 = 11;
+// steamid before the payloads and crc after them
+const STEAM_HEADER_LEN: usize = 8;
+const STEAM_CRC_LEN: usize = 4;
+
+/// Decodes one Opus frame into 16 bit mono PCM at VOICE_SAMPLE_RATE and returns the number of
+/// samples written. The parser doesn't link an Opus library, the bindings plug one in.
+pub trait OpusDecoder {
+    fn decode(&mut self, frame: &[u8], pcm: &mut [i16]) -> Result<usize, DemoParserError>;
+}
+
+// One svc_VoiceData as received
+#[derive(Debug, Clone)]
+pub struct VoiceRecord {
+    // Tick of the message, the parser's tick when the server didn't fill it in
+    pub tick: i32,
+    pub msg: CSVCMsg_VoiceData,
+}
+
+#[derive(Debug, Clone)]
+pub struct VoiceFrame {
+    pub tick: i32,
+    // Where the frame starts in the audio of the player, frames that arrive together are played
+    // back to back so they can start later than this
+    pub sample: usize,
+    // One Opus frame
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayerVoice {
+    pub steamid: Option<u64>,
+    // Used to tell players apart when the demo has no xuid
+    pub client: Option<i32>,
+    // Tick of the first frame, sample 0 of the audio
+    pub start_tick: i32,
+    pub frames: Vec<VoiceFrame>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayerVoiceWav {
+    pub steamid: Option<u64>,
+    pub client: Option<i32>,
+    pub start_tick: i32,
+    // WAV file including the header
+    pub wav: Vec<u8>,
+}
+
+// (steamid, client)
+pub(crate) type VoiceKey = (Option<u64>, Option<i32>);
+
+// Players are told apart by xuid, the client slot is only used when the demo doesn't have it
+pub(crate) fn voice_key(msg: &CSVCMsg_VoiceData) -> VoiceKey {
+    match msg.xuid {
+        Some(xuid) if xuid != 0 => (Some(xuid), None),
+        _ => (None, msg.client),
+    }
+}
+
+impl PlayerVoiceWav {
+    // steamid, or the client slot when the demo doesn't have it
+    pub fn player_id(&self) -> String {
+        match (self.steamid, self.client) {
+            (Some(steamid), _) => steamid.to_string(),
+            (None, Some(client)) => format!("client_{}", client),
+            (None, None) => "unknown".to_string(),
+        }
+    }
+}
+
+/// Splits the voice messages into Opus frames per player, ordered by tick. Both the Opus format
+/// of CS2 and the older Steam format (Opus with packet loss concealment) are supported.
+pub fn voice_frames(voice_data: &[VoiceRecord]) -> Vec<PlayerVoice> {
+    let mut per_player: BTreeMap<VoiceKey, Vec<(i32, Vec<u8>)>> = BTreeMap::default();
+    for record in voice_data {
+        let msg = &record.msg;
+        let audio = match msg.audio.as_ref() {
+            Some(audio) => audio,
+            None => continue,
+        };
+        let key = voice_key(msg);
+        let frames = match audio.format() {
+            VoiceDataFormat_t::VOICEDATA_FORMAT_OPUS => split_opus_packets(audio.voice_data(), &audio.packet_offsets),
+            VoiceDataFormat_t::VOICEDATA_FORMAT_STEAM => steam_opus_frames(audio.voice_data()),
+            VoiceDataFormat_t::VOICEDATA_FORMAT_ENGINE => vec![],
+        };
+        let tick = record.tick;
+        per_player
+            .entry(key)
+            .or_default()
+            .extend(frames.into_iter().map(|frame| (tick, frame.to_vec())));
+    }
+    per_player
+        .into_iter()
+        .filter_map(|((steamid, client), mut frames)| {
+            frames.sort_by_key(|(tick, _)| *tick);
+            let start_tick = frames.first()?.0;
+            let frames = frames
+                .into_iter()
+                .map(|(tick, data)| VoiceFrame {
+                    tick,
+                    sample: (tick - start_tick) as usize * SAMPLES_PER_TICK,
+                    data,
+                })
+                .collect();
+            Some(PlayerVoice {
+                steamid,
+                client,
+                start_tick,
+                frames,
+            })
+        })
+        .collect()
+}
+
+// The offsets split the message into packets when it has more than one
+fn split_opus_packets<'a>(bytes: &'a [u8], packet_offsets: &[u32]) -> Vec<&'a [u8]> {
+    let mut bounds: Vec<usize> = packet_offsets
+        .iter()
+        .map(|o| *o as usize)
+        .filter(|o| *o < bytes.len())
+        .collect();
+    bounds.push(0);
+    bounds.push(bytes.len());
+    bounds.sort();
+    bounds.dedup();
+    bounds.windows(2).map(|w| &bytes[w[0]..w[1]]).collect()
+}
+
+// https://github.com/DandrewsDev/CS2VoiceData
+fn steam_opus_frames(bytes: &[u8]) -> Vec<&[u8]> {
+    let mut frames = vec![];
+    if bytes.len() < STEAM_HEADER_LEN + STEAM_CRC_LEN {
+        return frames;
+    }
+    let end = bytes.len() - STEAM_CRC_LEN;
+    let mut ptr = STEAM_HEADER_LEN;
+    while ptr < end {
+        let payload_type = bytes[ptr];
+        ptr += 1;
+        match payload_type {
+            STEAM_PAYLOAD_SAMPLE_RATE | STEAM_PAYLOAD_SILENCE => ptr += 2,
+            STEAM_PAYLOAD_OPUS_PLC => {
+                let len = match read_u16(bytes, ptr) {
+                    Some(len) => len as usize,
+                    None => break,
+                };
+                ptr += 2;
+                let payload_end = (ptr + len).min(end);
+                // [u16 length][u16 sequence number][frame], 0xFFFF ends the stream
+                while ptr + 4 <= payload_end {
+                    let frame_len = match read_u16(bytes, ptr) {
+                        Some(u16::MAX) | None => break,
+                        Some(frame_len) => frame_len as usize,
+                    };
+                    ptr += 4;
+                    if ptr + frame_len > payload_end {
+                        break;
+                    }
+                    frames.push(&bytes[ptr..ptr + frame_len]);
+                    ptr += frame_len;
+                }
+                ptr = payload_end;
+            }
+            // Older codecs that CS2 doesn't use
+            _ => break,
+        }
+    }
+    frames
+}
+
+fn read_u16(bytes: &[u8], ptr: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(ptr..ptr + 2)?.try_into().ok()?))
+}
+
+/// Decodes the frames of a player into PCM. Time without voice between frames is filled with
+/// silence so the audio stays in sync with the demo.
+pub fn decode_voice<D: OpusDecoder>(player: &PlayerVoice, decoder: &mut D) -> Result<Vec<i16>, DemoParserError> {
+    let mut pcm: Vec<i16> = vec![];
+    let mut buf = vec![0_i16; MAX_FRAME_SAMPLES];
+    for frame in &player.frames {
+        if pcm.len() < frame.sample {
+            pcm.resize(frame.sample, 0);
+        }
+        let n_samples = decoder.decode(&frame.data, &mut buf)?;
+        pcm.extend_from_slice(&buf[..n_samples.min(buf.len())]);
+    }
+    Ok(pcm)
+}
+
+/// 16 bit mono WAV file with the header.
+pub fn pcm_to_wav(pcm: &[i16], sample_rate: u32) -> Vec<u8> {
+    let data_len = (pcm.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + pcm.len() * 2);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16_u32.to_le_bytes());
+    // PCM, mono
+    wav.extend_from_slice(&1_u16.to_le_bytes());
+    wav.extend_from_slice(&1_u16.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2_u16.to_le_bytes());
+    wav.extend_from_slice(&16_u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in pcm {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+/// A WAV file per player from output.voice_data. new_decoder is called once per player since
+/// Opus decoders keep state between frames.
+pub fn voice_to_wav<D, F>(voice_data: &[VoiceRecord], mut new_decoder: F) -> Result<Vec<PlayerVoiceWav>, DemoParserError>
+where
+    D: OpusDecoder,
+    F: FnMut() -> Result<D, DemoParserError>,
+{
+    let mut wavs = vec![];
+    for player in voice_frames(voice_data) {
+        let mut decoder = new_decoder()?;
+        let pcm = decode_voice(&player, &mut decoder)?;
+        wavs.push(PlayerVoiceWav {
+            steamid: player.steamid,
+            client: player.client,
+            start_tick: player.start_tick,
+            wav: pcm_to_wav(&pcm, VOICE_SAMPLE_RATE),
+        });
+    }
+    Ok(wavs)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voice::*;
+    use csgoproto::netmessages::CMsgVoiceAudio;
+    use protobuf::EnumOrUnknown;
+    use protobuf::MessageField;
+
+    fn record(tick: i32, xuid: Option<u64>, client: i32, data: Vec<u8>, packet_offsets: Vec<u32>) -> VoiceRecord {
+        let audio = CMsgVoiceAudio {
+            format: Some(EnumOrUnknown::new(VoiceDataFormat_t::VOICEDATA_FORMAT_OPUS)),
+            voice_data: Some(data),
+            packet_offsets,
+            ..Default::default()
+        };
+        VoiceRecord {
+            tick,
+            msg: CSVCMsg_VoiceData {
+                audio: MessageField::some(audio),
+                client: Some(client),
+                xuid,
+                ..Default::default()
+            },
+        }
+    }
+
+    // Writes frame.len() samples of the first byte of the frame
+    struct FakeDecoder;
+
+    impl OpusDecoder for FakeDecoder {
+        fn decode(&mut self, frame: &[u8], pcm: &mut [i16]) -> Result<usize, DemoParserError> {
+            for sample in pcm.iter_mut().take(frame.len()) {
+                *sample = frame[0] as i16;
+            }
+            Ok(frame.len())
+        }
+    }
+
+    #[test]
+    fn test_split_opus_packets() {
+        let bytes = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        assert_eq!(split_opus_packets(&bytes, &[]), vec![&bytes[..]]);
+        assert_eq!(
+            split_opus_packets(&bytes, &[7, 4, 100]),
+            vec![&bytes[0..4], &bytes[4..7], &bytes[7..10]]
+        );
+    }
+
+    #[test]
+    fn test_steam_opus_frames() {
+        let mut bytes = vec![0; STEAM_HEADER_LEN];
+        bytes.push(STEAM_PAYLOAD_SAMPLE_RATE);
+        bytes.extend_from_slice(&24000_u16.to_le_bytes());
+        bytes.push(STEAM_PAYLOAD_OPUS_PLC);
+        bytes.extend_from_slice(&13_u16.to_le_bytes());
+        // [length][sequence][frame] twice
+        bytes.extend_from_slice(&[3, 0, 0, 0, 10, 11, 12]);
+        bytes.extend_from_slice(&[2, 0, 1, 0, 20, 21]);
+        bytes.extend_from_slice(&[0; STEAM_CRC_LEN]);
+        assert_eq!(steam_opus_frames(&bytes), vec![&[10, 11, 12][..], &[20, 21][..]]);
+        assert!(steam_opus_frames(&bytes[..STEAM_HEADER_LEN]).is_empty());
+    }
+
+    #[test]
+    fn test_voice_frames() {
+        let records = vec![
+            record(12, Some(7), 1, vec![3, 3, 4, 4], vec![2]),
+            record(10, Some(7), 1, vec![1, 1], vec![]),
+            record(20, None, 5, vec![9], vec![]),
+        ];
+        let players = voice_frames(&records);
+        assert_eq!(players.len(), 2);
+        assert_eq!(
+            (players[0].steamid, players[0].client, players[0].start_tick),
+            (None, Some(5), 20)
+        );
+        let player = &players[1];
+        assert_eq!((player.steamid, player.client, player.start_tick), (Some(7), None, 10));
+        let frames: Vec<(i32, usize, &[u8])> = player.frames.iter().map(|f| (f.tick, f.sample, &f.data[..])).collect();
+        assert_eq!(
+            frames,
+            vec![
+                (10, 0, &[1, 1][..]),
+                (12, 2 * SAMPLES_PER_TICK, &[3, 3][..]),
+                (12, 2 * SAMPLES_PER_TICK, &[4, 4][..]),
+            ]
+        );
+        // Silence up to the second tick, then both frames back to back
+        let pcm = decode_voice(player, &mut FakeDecoder).unwrap();
+        assert_eq!(pcm.len(), 2 * SAMPLES_PER_TICK + 4);
+        assert_eq!(&pcm[..3], &[1, 1, 0]);
+        assert_eq!(&pcm[2 * SAMPLES_PER_TICK..], &[3, 3, 4, 4]);
+    }
+
+    #[test]
+    fn test_pcm_to_wav() {
+        let wav = pcm_to_wav(&[1, -1, 256], VOICE_SAMPLE_RATE);
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 36 + 6);
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), VOICE_SAMPLE_RATE);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 6);
+        assert_eq!(&wav[44..], &[1, 0, 255, 255, 0, 1]);
+    }
+}
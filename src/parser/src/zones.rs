@@ -0,0 +1,381 @@
+use crate::first_pass::read_bits::DemoParserError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Zone {
+    pub name: String,
+    // None applies the zone to every map
+    pub map: Option<String>,
+    // Outer rings and holes, a point is inside if it is inside an odd number of them
+    pub rings: Vec<Vec<[f32; 2]>>,
+    // Only set for 3D zones
+    pub z_min: Option<f32>,
+    pub z_max: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Zones {
+    pub zones: Vec<Zone>,
+}
+
+impl Zone {
+    pub fn contains(&self, x: f32, y: f32, z: f32) -> bool {
+        if self.z_min.is_some_and(|min| z < min) || self.z_max.is_some_and(|max| z > max) {
+            return false;
+        }
+        self.rings.iter().filter(|ring| ring_contains(ring, x, y)).count() % 2 == 1
+    }
+}
+
+fn ring_contains(ring: &[[f32; 2]], x: f32, y: f32) -> bool {
+    let mut inside = false;
+    let mut j = ring.len().wrapping_sub(1);
+    for i in 0..ring.len() {
+        let ([xi, yi], [xj, yj]) = (ring[i], ring[j]);
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+impl Zones {
+    pub fn from_file(path: &str) -> Result<Zones, DemoParserError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Zones::from_json(&text),
+            Err(e) => Err(DemoParserError::FileNotFound(format!("{}. File name: {}", e, path))),
+        }
+    }
+    /// Accepts either a GeoJSON FeatureCollection of Polygons/MultiPolygons with the zone name in
+    /// properties.name, or plain JSON keyed by map name:
+    /// {"de_mirage": [{"name": "A site", "polygon": [[x, y], ...], "z_min": -200, "z_max": 0}]}
+    /// Polygons with [x, y, z] points are 3D and span from the lowest to the highest z,
+    /// unless z_min/z_max are given.
+    pub fn from_json(text: &str) -> Result<Zones, DemoParserError> {
+        let mut chars = text.char_indices().peekable();
+        let json = parse_json(text, &mut chars)?;
+        let mut zones = vec![];
+        match &json {
+            Json::Object(fields) if get(fields, "type").and_then(Json::as_str) == Some("FeatureCollection") => {
+                let features = match get(fields, "features") {
+                    Some(Json::Array(features)) => features,
+                    _ => return Err(malformed("FeatureCollection without features")),
+                };
+                for feature in features {
+                    zones.push(zone_from_feature(feature)?);
+                }
+            }
+            Json::Object(maps) => {
+                for (map, map_zones) in maps {
+                    match map_zones {
+                        Json::Array(map_zones) => {
+                            for zone in map_zones {
+                                zones.push(zone_from_object(zone, Some(map))?);
+                            }
+                        }
+                        _ => return Err(malformed(&format!("zones of {} are not a list", map))),
+                    }
+                }
+            }
+            Json::Array(all_zones) => {
+                for zone in all_zones {
+                    zones.push(zone_from_object(zone, None)?);
+                }
+            }
+            _ => return Err(malformed("expected an object or a list")),
+        }
+        Ok(Zones { zones })
+    }
+    /// Zones of one map, map_name is the "map_name" from the header.
+    pub fn for_map(&self, map_name: &str) -> Zones {
+        let map_name = map_name.rsplit('/').next().unwrap_or(map_name);
+        Zones {
+            zones: self
+                .zones
+                .iter()
+                .filter(|z| z.map.as_ref().is_none_or(|m| m.eq_ignore_ascii_case(map_name)))
+                .cloned()
+                .collect(),
+        }
+    }
+    /// Name of the first zone (in file order) that contains the point.
+    pub fn zone_at(&self, x: f32, y: f32, z: f32) -> Option<&str> {
+        self.zones
+            .iter()
+            .find(|zone| zone.contains(x, y, z))
+            .map(|zone| zone.name.as_str())
+    }
+}
+
+fn malformed(reason: &str) -> DemoParserError {
+    DemoParserError::MalformedZones(reason.to_string())
+}
+
+fn zone_from_feature(feature: &Json) -> Result<Zone, DemoParserError> {
+    let fields = feature.as_object().ok_or_else(|| malformed("feature is not an object"))?;
+    let properties = get(fields, "properties").and_then(Json::as_object).unwrap_or(&[]);
+    let geometry = get(fields, "geometry")
+        .and_then(Json::as_object)
+        .ok_or_else(|| malformed("feature without geometry"))?;
+    let coordinates = get(geometry, "coordinates").ok_or_else(|| malformed("geometry without coordinates"))?;
+    // Polygon is a list of rings, MultiPolygon a list of those
+    let polygons: Vec<&Json> = match get(geometry, "type").and_then(Json::as_str) {
+        Some("Polygon") => vec![coordinates],
+        Some("MultiPolygon") => coordinates.as_array().unwrap_or(&[]).iter().collect(),
+        other => return Err(malformed(&format!("unsupported geometry: {:?}", other))),
+    };
+    let mut rings = vec![];
+    for polygon in polygons {
+        for ring in polygon.as_array().unwrap_or(&[]) {
+            rings.push(ring);
+        }
+    }
+    build_zone(properties, &rings, None)
+}
+
+fn zone_from_object(zone: &Json, map: Option<&str>) -> Result<Zone, DemoParserError> {
+    let fields = zone.as_object().ok_or_else(|| malformed("zone is not an object"))?;
+    let polygon = get(fields, "polygon").ok_or_else(|| malformed("zone without polygon"))?;
+    build_zone(fields, &[polygon], map)
+}
+
+fn build_zone(fields: &[(String, Json)], rings: &[&Json], map: Option<&str>) -> Result<Zone, DemoParserError> {
+    let name = match get(fields, "name").and_then(Json::as_str) {
+        Some(name) => name.to_string(),
+        None => return Err(malformed("zone without name")),
+    };
+    let mut zs: Vec<f32> = vec![];
+    let mut out_rings = vec![];
+    for ring in rings {
+        let mut points = vec![];
+        for point in ring.as_array().unwrap_or(&[]) {
+            let coords: Vec<f32> = point
+                .as_array()
+                .unwrap_or(&[])
+                .iter()
+                .filter_map(|c| c.as_f64().map(|f| f as f32))
+                .collect();
+            if coords.len() < 2 {
+                return Err(malformed(&format!("bad point in zone {}", name)));
+            }
+            points.push([coords[0], coords[1]]);
+            if let Some(z) = coords.get(2) {
+                zs.push(*z);
+            }
+        }
+        if points.len() < 3 {
+            return Err(malformed(&format!("zone {} needs at least 3 points", name)));
+        }
+        out_rings.push(points);
+    }
+    let number = |key: &str| get(fields, key).and_then(Json::as_f64).map(|f| f as f32);
+    Ok(Zone {
+        map: get(fields, "map").and_then(Json::as_str).or(map).map(|m| m.to_string()),
+        rings: out_rings,
+        z_min: number("z_min").or_else(|| zs.iter().cloned().reduce(f32::min)),
+        z_max: number("z_max").or_else(|| zs.iter().cloned().reduce(f32::max)),
+        name,
+    })
+}
+
+enum Json {
+    // null, true and false, zones have no use for them
+    Literal,
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(f) => Some(*f),
+            _ => None,
+        }
+    }
+    fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(a) => Some(a),
+            _ => None,
+        }
+    }
+    fn as_object(&self) -> Option<&[(String, Json)]> {
+        match self {
+            Json::Object(o) => Some(o),
+            _ => None,
+        }
+    }
+}
+
+fn get<'a>(fields: &'a [(String, Json)], key: &str) -> Option<&'a Json> {
+    fields.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::CharIndices<'a>>;
+
+fn skip_whitespace(chars: &mut Chars) {
+    while chars.peek().is_some_and(|(_, c)| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn expect(chars: &mut Chars, wanted: char) -> Result<(), DemoParserError> {
+    skip_whitespace(chars);
+    match chars.next() {
+        Some((_, c)) if c == wanted => Ok(()),
+        Some((idx, c)) => Err(malformed(&format!("expected '{}' at {}, found '{}'", wanted, idx, c))),
+        None => Err(malformed(&format!("expected '{}', found end of file", wanted))),
+    }
+}
+
+fn parse_json(text: &str, chars: &mut Chars) -> Result<Json, DemoParserError> {
+    skip_whitespace(chars);
+    let (start, c) = match chars.peek() {
+        Some((idx, c)) => (*idx, *c),
+        None => return Err(malformed("unexpected end of file")),
+    };
+    match c {
+        '{' => {
+            chars.next();
+            let mut fields = vec![];
+            skip_whitespace(chars);
+            if chars.peek().is_some_and(|(_, c)| *c == '}') {
+                chars.next();
+                return Ok(Json::Object(fields));
+            }
+            loop {
+                skip_whitespace(chars);
+                let key = match parse_json(text, chars)? {
+                    Json::String(key) => key,
+                    _ => return Err(malformed(&format!("expected a key at {}", start))),
+                };
+                expect(chars, ':')?;
+                fields.push((key, parse_json(text, chars)?));
+                skip_whitespace(chars);
+                match chars.next() {
+                    Some((_, ',')) => continue,
+                    Some((_, '}')) => return Ok(Json::Object(fields)),
+                    _ => return Err(malformed(&format!("unclosed object starting at {}", start))),
+                }
+            }
+        }
+        '[' => {
+            chars.next();
+            let mut items = vec![];
+            skip_whitespace(chars);
+            if chars.peek().is_some_and(|(_, c)| *c == ']') {
+                chars.next();
+                return Ok(Json::Array(items));
+            }
+            loop {
+                items.push(parse_json(text, chars)?);
+                skip_whitespace(chars);
+                match chars.next() {
+                    Some((_, ',')) => continue,
+                    Some((_, ']')) => return Ok(Json::Array(items)),
+                    _ => return Err(malformed(&format!("unclosed list starting at {}", start))),
+                }
+            }
+        }
+        '"' => {
+            chars.next();
+            let mut s = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => return Ok(Json::String(s)),
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => s.push('\n'),
+                        Some((_, 't')) => s.push('\t'),
+                        Some((_, 'u')) => {
+                            let hex: String = (0..4).filter_map(|_| chars.next().map(|(_, c)| c)).collect();
+                            let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                            s.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+                        }
+                        Some((_, c)) => s.push(c),
+                        None => break,
+                    },
+                    Some((_, c)) => s.push(c),
+                    None => break,
+                }
+            }
+            Err(malformed(&format!("unclosed string starting at {}", start)))
+        }
+        _ => {
+            let mut end = start;
+            while let Some((idx, c)) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || *c == '-' || *c == '+' || *c == '.') {
+                    break;
+                }
+                end = idx + c.len_utf8();
+                chars.next();
+            }
+            match &text[start..end] {
+                "null" | "true" | "false" => Ok(Json::Literal),
+                number => match number.parse::<f64>() {
+                    Ok(f) => Ok(Json::Number(f)),
+                    Err(_) => Err(malformed(&format!("unexpected '{}' at {}", number, start))),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::*;
+
+    #[test]
+    fn test_zones_from_map_json() {
+        let zones = Zones::from_json(
+            r#"{
+                "de_mirage": [
+                    {"name": "A site", "polygon": [[0, 0], [100, 0], [100, 100], [0, 100]], "z_min": -200, "z_max": 0},
+                    {"name": "Mid", "polygon": [[-100, -100], [300, -100], [300, 300], [-100, 300]]}
+                ],
+                "de_nuke": [{"name": "Outside", "polygon": [[0, 0], [10, 0], [10, 10]]}]
+            }"#,
+        )
+        .unwrap();
+        let mirage = zones.for_map("workshop/123/de_mirage");
+        assert_eq!(mirage.zones.len(), 2);
+        assert_eq!(mirage.zone_at(50.0, 50.0, -100.0), Some("A site"));
+        // Above the A site zone, falls through to the next one
+        assert_eq!(mirage.zone_at(50.0, 50.0, 100.0), Some("Mid"));
+        assert_eq!(mirage.zone_at(200.0, 50.0, 0.0), Some("Mid"));
+        assert_eq!(mirage.zone_at(500.0, 50.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_zones_from_geojson_with_hole() {
+        let zones = Zones::from_json(
+            r#"{"type": "FeatureCollection", "features": [{
+                "type": "Feature",
+                "properties": {"name": "Ring"},
+                "geometry": {"type": "Polygon", "coordinates": [
+                    [[0, 0, -50], [100, 0, -50], [100, 100, 50], [0, 100, 50]],
+                    [[40, 40], [60, 40], [60, 60], [40, 60]]
+                ]}
+            }]}"#,
+        )
+        .unwrap();
+        let zone = &zones.zones[0];
+        assert_eq!((zone.z_min, zone.z_max), (Some(-50.0), Some(50.0)));
+        assert!(zone.contains(10.0, 10.0, 0.0));
+        assert!(!zone.contains(50.0, 50.0, 0.0));
+        assert!(!zone.contains(10.0, 10.0, 100.0));
+    }
+
+    #[test]
+    fn test_malformed_zones() {
+        assert!(Zones::from_json(r#"[{"name": "A", "polygon": [[0, 0], [1, 1]]}]"#).is_err());
+        assert!(Zones::from_json(r#"[{"polygon": [[0, 0], [1, 0], [1, 1]]}]"#).is_err());
+        assert!(Zones::from_json("[{").is_err());
+    }
+}
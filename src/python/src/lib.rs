@@ -2,6 +2,44 @@ use crate::arrow::array::*;
 use ahash::AHashMap;
 use arrow::ffi;
 use itertools::Itertools;
+use parser::analysis::aim::aim;
+use parser::analysis::aim::aim_inputs;
+use parser::analysis::bomb::bomb_inputs;
+use parser::analysis::bomb::bomb_timeline;
+use parser::analysis::duels::duels;
+use parser::analysis::duels::duels_inputs;
+use parser::analysis::economy::economy;
+use parser::analysis::economy::economy_inputs;
+use parser::analysis::economy::BuyThresholds;
+use parser::analysis::fires_and_smokes::fires_and_smokes;
+use parser::analysis::fires_and_smokes::fires_and_smokes_inputs;
+use parser::analysis::flashes::flashes;
+use parser::analysis::flashes::flashes_inputs;
+use parser::analysis::grenades::grenade_throws;
+use parser::analysis::grenades::grenade_throws_inputs;
+use parser::analysis::highlights::highlights;
+use parser::analysis::highlights::highlights_inputs;
+use parser::analysis::kills::kills;
+use parser::analysis::kills::kills_inputs;
+use parser::analysis::movement::movement;
+use parser::analysis::movement::movement_inputs;
+use parser::analysis::player_stats::player_stats;
+use parser::analysis::player_stats::player_stats_inputs;
+use parser::analysis::recoil::recoil;
+use parser::analysis::recoil::recoil_inputs;
+use parser::analysis::shots::shots;
+use parser::analysis::shots::shots_inputs;
+use parser::analysis::stats_updates::stats_updates;
+use parser::analysis::stats_updates::stats_updates_inputs;
+use parser::analysis::user_cmds::user_cmds_inputs;
+use parser::analysis::visibility::visibility;
+use parser::analysis::visibility::visibility_inputs;
+use parser::analysis::voice_activity::voice_activity;
+use parser::analysis::voice_activity::voice_activity_inputs;
+use parser::analysis::weapon_skins::weapon_skins_inputs;
+use parser::analysis::weapons::weapons;
+use parser::analysis::weapons::weapons_inputs;
+use parser::first_pass::parser::parse_file_info;
 use parser::first_pass::parser_settings::create_mmap;
 use parser::first_pass::parser_settings::rm_user_friendly_names;
 use parser::first_pass::parser_settings::ParserInputs;
@@ -9,9 +47,20 @@ use parser::first_pass::read_bits::DemoParserError;
 use parser::parse_demo::Parser;
 use parser::second_pass::game_events::EventField;
 use parser::second_pass::game_events::GameEvent;
+use parser::radar::MapOverview;
 use parser::second_pass::parser_settings::create_huffman_lookup_table;
+use parser::second_pass::parser_settings::PlayerAccolade;
 use parser::second_pass::variants::VarVec;
 use parser::second_pass::variants::Variant;
+#[cfg(feature = "voice")]
+use parser::voice::voice_to_wav;
+#[cfg(feature = "voice")]
+use parser::voice::OpusDecoder;
+use parser::voice::PlayerVoiceWav;
+use parser::voice::VoiceRecord;
+#[cfg(feature = "voice")]
+use parser::voice::VOICE_SAMPLE_RATE;
+use parser::zones::Zones;
 use polars::prelude::ArrowField;
 use polars::prelude::NamedFrom;
 use polars::series::Series;
@@ -21,6 +70,7 @@ use pyo3::exceptions::PyValueError;
 use pyo3::ffi::Py_uintptr_t;
 use pyo3::prelude::*;
 use pyo3::types::IntoPyDict;
+use pyo3::types::PyBytes;
 use pyo3::types::PyDict;
 use pyo3::types::PyList;
 use pyo3::Python;
@@ -32,12 +82,34 @@ create_exception!(DemoParser, Exception, pyo3::exceptions::PyException);
 
 #[pymethods]
 impl DemoParser {
+    /// overview: path to the overview .txt of the map (pos_x, pos_y, scale) used for the radar
+    /// props. Only needed for workshop maps, official maps are built-in.
+    /// zones: path to a JSON or GeoJSON file with named polygons used for the "zone" prop.
     #[new]
-    pub fn py_new(demo_path: String) -> PyResult<Self> {
+    #[args(overview = "None", zones = "None")]
+    pub fn py_new(demo_path: String, overview: Option<String>, zones: Option<String>) -> PyResult<Self> {
         // let file = File::open(demo_path.clone()).unwrap();
         // let mmap = unsafe { MmapOptions::new().map(&file).unwrap() };
         // let huf = create_huffman_lookup_table();
-        Ok(DemoParser { path: demo_path })
+        let overview = match overview {
+            Some(path) => match MapOverview::from_file(&path) {
+                Ok(overview) => Some(overview),
+                Err(e) => return Err(Exception::new_err(format!("{}", e))),
+            },
+            None => None,
+        };
+        let zones = match zones {
+            Some(path) => match Zones::from_file(&path) {
+                Ok(zones) => Some(zones),
+                Err(e) => return Err(Exception::new_err(format!("{}", e))),
+            },
+            None => None,
+        };
+        Ok(DemoParser {
+            path: demo_path,
+            overview: overview,
+            zones: zones,
+        })
     }
 
     /// Parses header message (different from the first 16 bytes of the file)
@@ -68,6 +140,15 @@ impl DemoParser {
             parse_ents: false,
             wanted_ticks: vec![],
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
@@ -80,6 +161,31 @@ impl DemoParser {
         };
         Ok(output.header.unwrap_or(AHashMap::default()).to_object(py))
     }
+    /// Returns a dictionary with the length of the demo from DEM_FileInfo without parsing the
+    /// rest of the demo: "playback_time" in seconds, "playback_ticks", "playback_frames" and
+    /// "round_start_ticks" (the tick where each round started).
+    pub fn parse_file_info(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let file_info = match parse_file_info(&mmap) {
+            Ok(file_info) => file_info,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let dict = PyDict::new(py);
+        dict.set_item("playback_time", file_info.playback_time)?;
+        dict.set_item("playback_ticks", file_info.playback_ticks)?;
+        dict.set_item("playback_frames", file_info.playback_frames)?;
+        dict.set_item("round_start_ticks", file_info.round_start_ticks)?;
+        Ok(dict.to_object(py))
+    }
     /// Returns a dictionary with console vars set. This includes data
     /// like this: "mp_roundtime": "1.92", "mp_buytime": "20" ...
     pub fn parse_convars(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
@@ -104,6 +210,15 @@ impl DemoParser {
             parse_ents: false,
             wanted_ticks: vec![],
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
@@ -139,6 +254,15 @@ impl DemoParser {
             parse_ents: false,
             wanted_ticks: vec![],
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
@@ -155,14 +279,15 @@ impl DemoParser {
     }
 
     /// Returns all coordinates of all grenades along with info about thrower.
+    /// With radar=True the radar_x, radar_y and radar_level columns are added.
     ///
     /// Example:
     ///          X           Y       Z  tick  thrower_steamid grenade_type
     /// 0 -388.875  1295.46875 -5120.0   982              NaN    HeGrenade
     /// 1 -388.875  1295.46875 -5120.0   983              NaN    HeGrenade
     /// 2 -388.875  1295.46875 -5120.0   983              NaN    HeGrenade
-
-    pub fn parse_grenades(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+    #[args(radar = "false")]
+    pub fn parse_grenades(&self, py: Python<'_>, radar: bool) -> PyResult<Py<PyAny>> {
         let mmap = match create_mmap(self.path.clone()) {
             Ok(mmap) => mmap,
             Err(e) => {
@@ -184,18 +309,30 @@ impl DemoParser {
             parse_ents: true,
             wanted_ticks: vec![],
             parse_projectiles: true,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
             huffman_lookup_table: &arc_huf,
         };
         let mut parser = Parser::new(settings, false);
+        parser.overview = self.overview.clone();
+        parser.zones = self.zones.clone();
         let output = match parser.parse_demo(&mmap) {
             Ok(output) => output,
             Err(e) => return Err(Exception::new_err(format!("{}", e))),
         };
 
         let entity_id: Vec<Option<i32>> = output.projectiles.iter().map(|s| s.entity_id).collect();
+        let unique_id: Vec<Option<u64>> = output.projectiles.iter().map(|s| s.unique_id).collect();
         let xs: Vec<Option<f32>> = output.projectiles.iter().map(|s| s.x).collect();
         let ys: Vec<Option<f32>> = output.projectiles.iter().map(|s| s.y).collect();
         let zs: Vec<Option<f32>> = output.projectiles.iter().map(|s| s.z).collect();
@@ -219,23 +356,34 @@ impl DemoParser {
         let name = arr_to_py(Box::new(Utf8Array::<i32>::from(name))).unwrap();
         let steamids = arr_to_py(Box::new(UInt64Array::from(steamid))).unwrap();
         let entity_ids = arr_to_py(Box::new(Int32Array::from(entity_id))).unwrap();
+        let unique_ids = arr_to_py(Box::new(UInt64Array::from(unique_id))).unwrap();
+
+        let mut all_series = vec![xs, ys, zs, ticks, steamids, name, grenade_type, entity_ids, unique_ids];
+        let mut column_names = vec![
+            "X",
+            "Y",
+            "Z",
+            "tick",
+            "thrower_steamid",
+            "name",
+            "grenade_type",
+            "entity_id",
+            "unique_id",
+        ];
+        if radar {
+            let radar_x: Vec<Option<f32>> = output.projectiles.iter().map(|s| s.radar_x).collect();
+            let radar_y: Vec<Option<f32>> = output.projectiles.iter().map(|s| s.radar_y).collect();
+            let radar_level: Vec<Option<String>> = output.projectiles.iter().map(|s| s.radar_level.clone()).collect();
+            all_series.push(arr_to_py(Box::new(Float32Array::from(radar_x)))?);
+            all_series.push(arr_to_py(Box::new(Float32Array::from(radar_y)))?);
+            all_series.push(arr_to_py(Box::new(Utf8Array::<i32>::from(radar_level)))?);
+            column_names.extend(["radar_x", "radar_y", "radar_level"]);
+        }
 
         let polars = py.import("polars")?;
-        let all_series_py =
-            [xs, ys, zs, ticks, steamids, name, grenade_type, entity_ids].to_object(py);
+        let all_series_py = all_series.to_object(py);
         Python::with_gil(|py| {
             let df = polars.call_method1("DataFrame", (all_series_py,))?;
-            // Set column names
-            let column_names = [
-                "X",
-                "Y",
-                "Z",
-                "tick",
-                "thrower_steamid",
-                "name",
-                "grenade_type",
-                "entity_id",
-            ];
             df.setattr("columns", column_names.to_object(py)).unwrap();
             // Call to_pandas with use_pyarrow_extension_array = true
             let kwargs = vec![("use_pyarrow_extension_array", true)].into_py_dict(py);
@@ -272,6 +420,15 @@ impl DemoParser {
             parse_ents: false,
             wanted_ticks: vec![],
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
@@ -344,6 +501,15 @@ impl DemoParser {
             parse_ents: false,
             wanted_ticks: vec![],
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
@@ -377,6 +543,85 @@ impl DemoParser {
             Ok(pandas_df.to_object(py))
         })
     }
+    pub fn parse_accolades(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+
+        let settings = ParserInputs {
+            real_name_to_og_name: AHashMap::default(),
+            wanted_players: vec![],
+            wanted_player_props: vec![],
+            wanted_other_props: vec![],
+            wanted_events: vec![],
+            parse_ents: false,
+            wanted_ticks: vec![],
+            parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
+            only_header: true,
+            count_props: false,
+            only_convars: false,
+            huffman_lookup_table: &arc_huf,
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let accolades: Vec<PlayerAccolade> = output
+            .player_md
+            .iter()
+            .filter_map(|p| p.accolade.clone())
+            .collect();
+        let steamids: Vec<Option<u64>> = accolades.iter().map(|a| a.steamid).collect();
+        let names: Vec<Option<String>> = accolades.iter().map(|a| a.name.clone()).collect();
+        let accolade_names: Vec<Option<String>> =
+            accolades.iter().map(|a| a.accolade.clone()).collect();
+        let eaccolades: Vec<Option<i32>> = accolades.iter().map(|a| a.eaccolade).collect();
+        let values: Vec<Option<f32>> = accolades.iter().map(|a| a.value).collect();
+        let positions: Vec<Option<i32>> = accolades.iter().map(|a| a.position).collect();
+
+        let steamid = rust_series_to_py_series(&Series::new("steamid", steamids))?;
+        let name = rust_series_to_py_series(&Series::new("name", names))?;
+        let accolade = rust_series_to_py_series(&Series::new("accolade", accolade_names))?;
+        let eaccolade = arr_to_py(Box::new(Int32Array::from(eaccolades)))?;
+        let value = rust_series_to_py_series(&Series::new("value", values))?;
+        let position = arr_to_py(Box::new(Int32Array::from(positions)))?;
+
+        let polars = py.import("polars")?;
+        let all_series_py = [steamid, name, accolade, eaccolade, value, position].to_object(py);
+        Python::with_gil(|py| {
+            let df = polars.call_method1("DataFrame", (all_series_py,))?;
+            let column_names = [
+                "steamid",
+                "name",
+                "accolade",
+                "eaccolade",
+                "value",
+                "position",
+            ];
+            df.setattr("columns", column_names.to_object(py))?;
+            let kwargs = vec![("use_pyarrow_extension_array", true)].into_py_dict(py);
+            let pandas_df = df.call_method("to_pandas", (), Some(kwargs))?;
+            Ok(pandas_df.to_object(py))
+        })
+    }
     pub fn parse_item_drops(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
         let mmap = match create_mmap(self.path.clone()) {
             Ok(mmap) => mmap,
@@ -399,6 +644,15 @@ impl DemoParser {
             parse_ents: false,
             wanted_ticks: vec![],
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
@@ -490,6 +744,15 @@ impl DemoParser {
             parse_ents: false,
             wanted_ticks: vec![],
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
@@ -596,12 +859,23 @@ impl DemoParser {
             parse_ents: true,
             wanted_ticks: vec![],
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
             huffman_lookup_table: &arc_huf,
         };
         let mut parser = Parser::new(settings, false);
+        parser.overview = self.overview.clone();
+        parser.zones = self.zones.clone();
         let output = match parser.parse_demo(&mmap) {
             Ok(output) => output,
             Err(e) => return Err(Exception::new_err(format!("{}", e))),
@@ -659,12 +933,23 @@ impl DemoParser {
             parse_ents: true,
             wanted_ticks: vec![],
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
             huffman_lookup_table: &arc_huf,
         };
         let mut parser = Parser::new(settings, false);
+        parser.overview = self.overview.clone();
+        parser.zones = self.zones.clone();
         let output = match parser.parse_demo(&mmap) {
             Ok(output) => output,
             Err(e) => return Err(Exception::new_err(format!("{}", e))),
@@ -716,6 +1001,15 @@ impl DemoParser {
             parse_ents: true,
             wanted_ticks: wanted_ticks,
             parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
             only_header: true,
             count_props: false,
             only_convars: false,
@@ -723,6 +1017,8 @@ impl DemoParser {
             //huf: huf,
         };
         let mut parser = Parser::new(settings, false);
+        parser.overview = self.overview.clone();
+        parser.zones = self.zones.clone();
         let output = match parser.parse_demo(&mmap) {
             Ok(output) => output,
             Err(e) => return Err(Exception::new_err(format!("{}", e))),
@@ -791,51 +1087,2180 @@ impl DemoParser {
             Ok(pandas_df.to_object(py))
         })
     }
-}
+    /// Returns a DF with kills, deaths, assists, ADR, HS% and KAST per player.
+    /// Players are identified by steamid so reconnecting players end up in one row.
+    /// With per_round=True one row per player per round is returned instead.
+    ///
+    /// Example output:
+    ///              steamid    name  team_num  rounds_played  kills  deaths  assists  ...     adr  headshot_percentage  kast
+    /// 0  76561198048924300  person1         2             24     21      15        4  ...  87.125            47.619049  75.0
+    #[args(per_round = "false")]
+    pub fn parse_player_stats(&self, py: Python<'_>, per_round: bool) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match player_stats_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let stats = player_stats(&output.game_events);
 
-/// https://github.com/pola-rs/polars/blob/master/examples/python_rust_compiled_function/src/ffi.rs
-pub(crate) fn to_py_array(py: Python, pyarrow: &PyModule, array: ArrayRef) -> PyResult<PyObject> {
-    let schema = Box::new(ffi::export_field_to_c(&ArrowField::new(
-        "",
-        array.data_type().clone(),
-        true,
-    )));
-    let array = Box::new(ffi::export_array_to_c(array));
-    let schema_ptr: *const ffi::ArrowSchema = &*schema;
-    let array_ptr: *const ffi::ArrowArray = &*array;
-    let array = pyarrow.getattr("Array")?.call_method1(
-        "_import_from_c",
-        (array_ptr as Py_uintptr_t, schema_ptr as Py_uintptr_t),
-    )?;
-    Ok(array.to_object(py))
-}
-/// https://github.com/pola-rs/polars/blob/master/examples/python_rust_compiled_function/src/ffi.rs
-pub fn rust_series_to_py_series(series: &Series) -> PyResult<PyObject> {
-    let series = series.rechunk();
-    let array = series.to_arrow(0);
-    let gil = Python::acquire_gil();
-    let py = gil.python();
-    let pyarrow = py.import("pyarrow")?;
-    let pyarrow_array = to_py_array(py, pyarrow, array)?;
-    let polars = py.import("polars")?;
-    let out = polars.call_method1("from_arrow", (pyarrow_array,))?;
-    Ok(out.to_object(py))
-}
-/// https://github.com/pola-rs/polars/blob/master/examples/python_rust_compiled_function/src/ffi.rs
-pub fn arr_to_py(array: Box<dyn Array>) -> PyResult<PyObject> {
-    //let series = series.rechunk();
-    //let array = series.to_arrow(0);
-    let gil = Python::acquire_gil();
-    let py = gil.python();
-    let pyarrow = py.import("pyarrow")?;
-    let pyarrow_array = to_py_array(py, pyarrow, array)?;
-    let polars = py.import("polars")?;
-    let out = polars.call_method1("from_arrow", (pyarrow_array,))?;
-    Ok(out.to_object(py))
-}
-#[pyclass]
-struct DemoParser {
-    path: String,
+        if per_round {
+            let rows = &stats.per_round;
+            let steamid: Vec<u64> = rows.iter().map(|s| s.steamid).collect();
+            let name: Vec<Option<String>> = rows.iter().map(|s| s.name.clone()).collect();
+            let round: Vec<i32> = rows.iter().map(|s| s.round).collect();
+            let team_num: Vec<Option<i32>> = rows.iter().map(|s| s.team_num).collect();
+            let kills: Vec<i32> = rows.iter().map(|s| s.kills).collect();
+            let deaths: Vec<i32> = rows.iter().map(|s| s.deaths).collect();
+            let assists: Vec<i32> = rows.iter().map(|s| s.assists).collect();
+            let flash_assists: Vec<i32> = rows.iter().map(|s| s.flash_assists).collect();
+            let headshot_kills: Vec<i32> = rows.iter().map(|s| s.headshot_kills).collect();
+            let damage: Vec<i32> = rows.iter().map(|s| s.damage).collect();
+            let survived: Vec<bool> = rows.iter().map(|s| s.survived).collect();
+            let traded: Vec<bool> = rows.iter().map(|s| s.traded).collect();
+            let kast: Vec<bool> = rows.iter().map(|s| s.kast).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("steamid", steamid))?,
+                rust_series_to_py_series(&Series::new("name", name))?,
+                rust_series_to_py_series(&Series::new("round", round))?,
+                arr_to_py(Box::new(Int32Array::from(team_num)))?,
+                rust_series_to_py_series(&Series::new("kills", kills))?,
+                rust_series_to_py_series(&Series::new("deaths", deaths))?,
+                rust_series_to_py_series(&Series::new("assists", assists))?,
+                rust_series_to_py_series(&Series::new("flash_assists", flash_assists))?,
+                rust_series_to_py_series(&Series::new("headshot_kills", headshot_kills))?,
+                rust_series_to_py_series(&Series::new("damage", damage))?,
+                rust_series_to_py_series(&Series::new("survived", survived))?,
+                rust_series_to_py_series(&Series::new("traded", traded))?,
+                rust_series_to_py_series(&Series::new("kast", kast))?,
+            ];
+            let column_names = [
+                "steamid",
+                "name",
+                "round",
+                "team_num",
+                "kills",
+                "deaths",
+                "assists",
+                "flash_assists",
+                "headshot_kills",
+                "damage",
+                "survived",
+                "traded",
+                "kast",
+            ];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows = &stats.per_match;
+        let steamid: Vec<u64> = rows.iter().map(|s| s.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|s| s.name.clone()).collect();
+        let team_num: Vec<Option<i32>> = rows.iter().map(|s| s.team_num).collect();
+        let rounds_played: Vec<i32> = rows.iter().map(|s| s.rounds_played).collect();
+        let kills: Vec<i32> = rows.iter().map(|s| s.kills).collect();
+        let deaths: Vec<i32> = rows.iter().map(|s| s.deaths).collect();
+        let assists: Vec<i32> = rows.iter().map(|s| s.assists).collect();
+        let flash_assists: Vec<i32> = rows.iter().map(|s| s.flash_assists).collect();
+        let headshot_kills: Vec<i32> = rows.iter().map(|s| s.headshot_kills).collect();
+        let damage: Vec<i32> = rows.iter().map(|s| s.damage).collect();
+        let adr: Vec<f32> = rows.iter().map(|s| s.adr).collect();
+        let headshot_percentage: Vec<f32> = rows.iter().map(|s| s.headshot_percentage).collect();
+        let kast: Vec<f32> = rows.iter().map(|s| s.kast).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            arr_to_py(Box::new(Int32Array::from(team_num)))?,
+            rust_series_to_py_series(&Series::new("rounds_played", rounds_played))?,
+            rust_series_to_py_series(&Series::new("kills", kills))?,
+            rust_series_to_py_series(&Series::new("deaths", deaths))?,
+            rust_series_to_py_series(&Series::new("assists", assists))?,
+            rust_series_to_py_series(&Series::new("flash_assists", flash_assists))?,
+            rust_series_to_py_series(&Series::new("headshot_kills", headshot_kills))?,
+            rust_series_to_py_series(&Series::new("damage", damage))?,
+            rust_series_to_py_series(&Series::new("adr", adr))?,
+            rust_series_to_py_series(&Series::new("headshot_percentage", headshot_percentage))?,
+            rust_series_to_py_series(&Series::new("kast", kast))?,
+        ];
+        let column_names = [
+            "steamid",
+            "name",
+            "team_num",
+            "rounds_played",
+            "kills",
+            "deaths",
+            "assists",
+            "flash_assists",
+            "headshot_kills",
+            "damage",
+            "adr",
+            "headshot_percentage",
+            "kast",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns player_death events with the columns "round", "is_opening_kill", "is_trade_kill",
+    /// "was_traded", "traded_steamid" and "trade_time" added. A kill is a trade if the victim had
+    /// killed a teammate of the attacker within trade_window seconds.
+    /// With per_player=True returns opening duel and trade stats per player instead.
+    ///
+    /// Example output (per_player=True):
+    ///              steamid     name  kills  deaths  opening_kills  opening_deaths  opening_attempts  opening_win_percentage  ...
+    /// 0  76561198048924300  person1     21      15              5               2                 7               71.428574  ...
+    #[args(trade_window = "5.0", per_player = "false")]
+    pub fn parse_duels(
+        &self,
+        py: Python<'_>,
+        trade_window: f32,
+        per_player: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match duels_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let duels = duels(&output.game_events, trade_window);
+
+        if !per_player {
+            return match series_from_event(&duels.kills, py) {
+                Ok(ser) => Ok(ser),
+                Err(_e) => Ok(PyList::empty(py).into()),
+            };
+        }
+        let rows = &duels.players;
+        let steamid: Vec<u64> = rows.iter().map(|s| s.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|s| s.name.clone()).collect();
+        let kills: Vec<i32> = rows.iter().map(|s| s.kills).collect();
+        let deaths: Vec<i32> = rows.iter().map(|s| s.deaths).collect();
+        let opening_kills: Vec<i32> = rows.iter().map(|s| s.opening_kills).collect();
+        let opening_deaths: Vec<i32> = rows.iter().map(|s| s.opening_deaths).collect();
+        let opening_attempts: Vec<i32> = rows.iter().map(|s| s.opening_attempts).collect();
+        let opening_win_percentage: Vec<f32> =
+            rows.iter().map(|s| s.opening_win_percentage).collect();
+        let trade_kills: Vec<i32> = rows.iter().map(|s| s.trade_kills).collect();
+        let traded_deaths: Vec<i32> = rows.iter().map(|s| s.traded_deaths).collect();
+        let trade_kill_percentage: Vec<f32> =
+            rows.iter().map(|s| s.trade_kill_percentage).collect();
+        let traded_death_percentage: Vec<f32> =
+            rows.iter().map(|s| s.traded_death_percentage).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            rust_series_to_py_series(&Series::new("kills", kills))?,
+            rust_series_to_py_series(&Series::new("deaths", deaths))?,
+            rust_series_to_py_series(&Series::new("opening_kills", opening_kills))?,
+            rust_series_to_py_series(&Series::new("opening_deaths", opening_deaths))?,
+            rust_series_to_py_series(&Series::new("opening_attempts", opening_attempts))?,
+            rust_series_to_py_series(&Series::new("opening_win_percentage", opening_win_percentage))?,
+            rust_series_to_py_series(&Series::new("trade_kills", trade_kills))?,
+            rust_series_to_py_series(&Series::new("traded_deaths", traded_deaths))?,
+            rust_series_to_py_series(&Series::new("trade_kill_percentage", trade_kill_percentage))?,
+            rust_series_to_py_series(&Series::new("traded_death_percentage", traded_death_percentage))?,
+        ];
+        let column_names = [
+            "steamid",
+            "name",
+            "kills",
+            "deaths",
+            "opening_kills",
+            "opening_deaths",
+            "opening_attempts",
+            "opening_win_percentage",
+            "trade_kills",
+            "traded_deaths",
+            "trade_kill_percentage",
+            "traded_death_percentage",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with 3k/4k/aces, 1vX clutches won or lost, knife and noscope kills and
+    /// collaterals. start_tick and end_tick are padded by padding seconds and can be fed
+    /// straight into demo_gototick.
+    ///
+    /// Example output:
+    ///   highlight_type            steamid     name  round  start_tick  end_tick  kills  opponents  weapon
+    /// 0             3k  76561198048924300  person1      4       10281     11409      3       <NA>    <NA>
+    /// 1     clutch_won  76561198048924301  person2      7       20022     21490      2          2    <NA>
+    #[args(padding = "3.0")]
+    pub fn parse_highlights(&self, py: Python<'_>, padding: f32) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match highlights_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let rows = highlights(&output.game_events, padding);
+
+        let highlight_type: Vec<&str> = rows.iter().map(|h| h.highlight_type.as_str()).collect();
+        let steamid: Vec<u64> = rows.iter().map(|h| h.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|h| h.name.clone()).collect();
+        let round: Vec<i32> = rows.iter().map(|h| h.round).collect();
+        let start_tick: Vec<i32> = rows.iter().map(|h| h.start_tick).collect();
+        let end_tick: Vec<i32> = rows.iter().map(|h| h.end_tick).collect();
+        let kills: Vec<i32> = rows.iter().map(|h| h.kills).collect();
+        let opponents: Vec<Option<i32>> = rows.iter().map(|h| h.opponents).collect();
+        let weapon: Vec<Option<String>> = rows.iter().map(|h| h.weapon.clone()).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("highlight_type", highlight_type))?,
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("start_tick", start_tick))?,
+            rust_series_to_py_series(&Series::new("end_tick", end_tick))?,
+            rust_series_to_py_series(&Series::new("kills", kills))?,
+            arr_to_py(Box::new(Int32Array::from(opponents)))?,
+            rust_series_to_py_series(&Series::new("weapon", weapon))?,
+        ];
+        let column_names = [
+            "highlight_type",
+            "steamid",
+            "name",
+            "round",
+            "start_tick",
+            "end_tick",
+            "kills",
+            "opponents",
+            "weapon",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with one row per flashbang: who threw it, how many enemies and teammates it
+    /// blinded and for how long, and how many of the blinded enemies were killed by the
+    /// thrower's team within flash_assist_window seconds.
+    /// With per_player=True returns the same numbers summed up per thrower.
+    ///
+    /// Example output:
+    ///    entity_id  round   tick    thrower_steamid thrower_name  thrower_team_num  ...  enemies_blinded  enemies_blind_duration  ...  flash_assists
+    /// 0        211      1   6712  76561198048924300      person1                 2  ...                2                3.140625  ...              1
+    #[args(flash_assist_window = "3.0", per_player = "false")]
+    pub fn parse_flashes(
+        &self,
+        py: Python<'_>,
+        flash_assist_window: f32,
+        per_player: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match flashes_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let flashes = flashes(&output.game_events, &output.projectiles, flash_assist_window);
+
+        if per_player {
+            let rows = &flashes.players;
+            let steamid: Vec<u64> = rows.iter().map(|s| s.steamid).collect();
+            let name: Vec<Option<String>> = rows.iter().map(|s| s.name.clone()).collect();
+            let flashes_thrown: Vec<i32> = rows.iter().map(|s| s.flashes_thrown).collect();
+            let enemies_blinded: Vec<i32> = rows.iter().map(|s| s.enemies_blinded).collect();
+            let enemies_blind_duration: Vec<f32> =
+                rows.iter().map(|s| s.enemies_blind_duration).collect();
+            let avg_enemy_blind_duration: Vec<f32> =
+                rows.iter().map(|s| s.avg_enemy_blind_duration).collect();
+            let teammates_blinded: Vec<i32> = rows.iter().map(|s| s.teammates_blinded).collect();
+            let teammates_blind_duration: Vec<f32> =
+                rows.iter().map(|s| s.teammates_blind_duration).collect();
+            let flash_assists: Vec<i32> = rows.iter().map(|s| s.flash_assists).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("steamid", steamid))?,
+                rust_series_to_py_series(&Series::new("name", name))?,
+                rust_series_to_py_series(&Series::new("flashes_thrown", flashes_thrown))?,
+                rust_series_to_py_series(&Series::new("enemies_blinded", enemies_blinded))?,
+                rust_series_to_py_series(&Series::new("enemies_blind_duration", enemies_blind_duration))?,
+                rust_series_to_py_series(&Series::new("avg_enemy_blind_duration", avg_enemy_blind_duration))?,
+                rust_series_to_py_series(&Series::new("teammates_blinded", teammates_blinded))?,
+                rust_series_to_py_series(&Series::new("teammates_blind_duration", teammates_blind_duration))?,
+                rust_series_to_py_series(&Series::new("flash_assists", flash_assists))?,
+            ];
+            let column_names = [
+                "steamid",
+                "name",
+                "flashes_thrown",
+                "enemies_blinded",
+                "enemies_blind_duration",
+                "avg_enemy_blind_duration",
+                "teammates_blinded",
+                "teammates_blind_duration",
+                "flash_assists",
+            ];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows = &flashes.flashes;
+        let entity_id: Vec<i32> = rows.iter().map(|f| f.entity_id).collect();
+        let round: Vec<i32> = rows.iter().map(|f| f.round).collect();
+        let tick: Vec<i32> = rows.iter().map(|f| f.tick).collect();
+        let thrower_steamid: Vec<Option<u64>> = rows.iter().map(|f| f.thrower_steamid).collect();
+        let thrower_name: Vec<Option<String>> = rows.iter().map(|f| f.thrower_name.clone()).collect();
+        let thrower_team_num: Vec<Option<i32>> = rows.iter().map(|f| f.thrower_team_num).collect();
+        let x: Vec<Option<f32>> = rows.iter().map(|f| f.x).collect();
+        let y: Vec<Option<f32>> = rows.iter().map(|f| f.y).collect();
+        let z: Vec<Option<f32>> = rows.iter().map(|f| f.z).collect();
+        let enemies_blinded: Vec<i32> = rows.iter().map(|f| f.enemies_blinded).collect();
+        let enemies_blind_duration: Vec<f32> = rows.iter().map(|f| f.enemies_blind_duration).collect();
+        let teammates_blinded: Vec<i32> = rows.iter().map(|f| f.teammates_blinded).collect();
+        let teammates_blind_duration: Vec<f32> =
+            rows.iter().map(|f| f.teammates_blind_duration).collect();
+        let self_blind_duration: Vec<f32> = rows.iter().map(|f| f.self_blind_duration).collect();
+        let flash_assists: Vec<i32> = rows.iter().map(|f| f.flash_assists).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("entity_id", entity_id))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("tick", tick))?,
+            rust_series_to_py_series(&Series::new("thrower_steamid", thrower_steamid))?,
+            rust_series_to_py_series(&Series::new("thrower_name", thrower_name))?,
+            arr_to_py(Box::new(Int32Array::from(thrower_team_num)))?,
+            rust_series_to_py_series(&Series::new("x", x))?,
+            rust_series_to_py_series(&Series::new("y", y))?,
+            rust_series_to_py_series(&Series::new("z", z))?,
+            rust_series_to_py_series(&Series::new("enemies_blinded", enemies_blinded))?,
+            rust_series_to_py_series(&Series::new("enemies_blind_duration", enemies_blind_duration))?,
+            rust_series_to_py_series(&Series::new("teammates_blinded", teammates_blinded))?,
+            rust_series_to_py_series(&Series::new("teammates_blind_duration", teammates_blind_duration))?,
+            rust_series_to_py_series(&Series::new("self_blind_duration", self_blind_duration))?,
+            rust_series_to_py_series(&Series::new("flash_assists", flash_assists))?,
+        ];
+        let column_names = [
+            "entity_id",
+            "round",
+            "tick",
+            "thrower_steamid",
+            "thrower_name",
+            "thrower_team_num",
+            "x",
+            "y",
+            "z",
+            "enemies_blinded",
+            "enemies_blind_duration",
+            "teammates_blinded",
+            "teammates_blind_duration",
+            "self_blind_duration",
+            "flash_assists",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with one row per thrown grenade. Unlike parse_grenades, which has one row
+    /// per projectile per tick, rows are keyed by "unique_id" (entity id + entity serial) so
+    /// reused entity ids don't get mixed up. "path" is a list of (tick, X, Y, Z) points from
+    /// the throw until the detonation. "detonate_zone" is the zone it landed in when zones were
+    /// given to DemoParser.
+    ///
+    /// Example output:
+    ///    unique_id  entity_id grenade_type    thrower_steamid thrower_name  round  throw_tick  ...  bounces  detonate_tick  ...                                               path
+    /// 0    1311089        305        smoke  76561198048924300      person1      1        6598  ...        1           6790  ...  [(6598, -1350.0, 2178.2, 27.1), (6599, -1342...
+    pub fn parse_grenade_throws(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match grenade_throws_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        parser.zones = self.zones.clone();
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let rows = grenade_throws(&output.game_events, &output.projectiles);
+
+        let unique_id: Vec<u64> = rows.iter().map(|t| t.unique_id).collect();
+        let entity_id: Vec<i32> = rows.iter().map(|t| t.entity_id).collect();
+        let grenade_type: Vec<Option<String>> = rows.iter().map(|t| t.grenade_type.clone()).collect();
+        let thrower_steamid: Vec<Option<u64>> = rows.iter().map(|t| t.thrower_steamid).collect();
+        let thrower_name: Vec<Option<String>> = rows.iter().map(|t| t.thrower_name.clone()).collect();
+        let round: Vec<i32> = rows.iter().map(|t| t.round).collect();
+        let throw_tick: Vec<i32> = rows.iter().map(|t| t.throw_tick).collect();
+        let throw_x: Vec<Option<f32>> = rows.iter().map(|t| t.throw_x).collect();
+        let throw_y: Vec<Option<f32>> = rows.iter().map(|t| t.throw_y).collect();
+        let throw_z: Vec<Option<f32>> = rows.iter().map(|t| t.throw_z).collect();
+        let bounces: Vec<i32> = rows.iter().map(|t| t.bounces).collect();
+        let detonate_tick: Vec<Option<i32>> = rows.iter().map(|t| t.detonate_tick).collect();
+        let detonate_x: Vec<Option<f32>> = rows.iter().map(|t| t.detonate_x).collect();
+        let detonate_y: Vec<Option<f32>> = rows.iter().map(|t| t.detonate_y).collect();
+        let detonate_z: Vec<Option<f32>> = rows.iter().map(|t| t.detonate_z).collect();
+        let detonate_zone: Vec<Option<String>> = rows.iter().map(|t| t.detonate_zone.clone()).collect();
+        let detonate_event: Vec<Option<String>> = rows
+            .iter()
+            .map(|t| t.detonate_event.as_ref().map(|e| e.name.clone()))
+            .collect();
+        let paths: Vec<Vec<(i32, f32, f32, f32)>> = rows
+            .iter()
+            .map(|t| t.path.iter().map(|p| (p.tick, p.x, p.y, p.z)).collect())
+            .collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("unique_id", unique_id))?,
+            rust_series_to_py_series(&Series::new("entity_id", entity_id))?,
+            rust_series_to_py_series(&Series::new("grenade_type", grenade_type))?,
+            rust_series_to_py_series(&Series::new("thrower_steamid", thrower_steamid))?,
+            rust_series_to_py_series(&Series::new("thrower_name", thrower_name))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("throw_tick", throw_tick))?,
+            rust_series_to_py_series(&Series::new("throw_X", throw_x))?,
+            rust_series_to_py_series(&Series::new("throw_Y", throw_y))?,
+            rust_series_to_py_series(&Series::new("throw_Z", throw_z))?,
+            rust_series_to_py_series(&Series::new("bounces", bounces))?,
+            arr_to_py(Box::new(Int32Array::from(detonate_tick)))?,
+            rust_series_to_py_series(&Series::new("detonate_X", detonate_x))?,
+            rust_series_to_py_series(&Series::new("detonate_Y", detonate_y))?,
+            rust_series_to_py_series(&Series::new("detonate_Z", detonate_z))?,
+            rust_series_to_py_series(&Series::new("detonate_zone", detonate_zone))?,
+            rust_series_to_py_series(&Series::new("detonate_event", detonate_event))?,
+        ];
+        let column_names = [
+            "unique_id",
+            "entity_id",
+            "grenade_type",
+            "thrower_steamid",
+            "thrower_name",
+            "round",
+            "throw_tick",
+            "throw_X",
+            "throw_Y",
+            "throw_Z",
+            "bounces",
+            "detonate_tick",
+            "detonate_X",
+            "detonate_Y",
+            "detonate_Z",
+            "detonate_zone",
+            "detonate_event",
+        ];
+        let df = columns_to_pandas(py, columns, &column_names)?;
+        // Lists of points don't fit in an arrow column nicely, add them as plain python objects
+        df.call_method1(py, "__setitem__", ("path", paths.to_object(py)))?;
+        Ok(df)
+    }
+    /// Returns a DF with one row per burning molotov/incendiary per tick. "points" is the
+    /// list of (X, Y, Z) positions that are burning on that tick.
+    ///
+    /// Example output:
+    ///    unique_id  entity_id    thrower_steamid thrower_name  round   tick  fire_count                                             points
+    /// 0    2097523        371  76561198048924300      person1      3  15840           1                          [(-1636.2, 2091.9, 60.0)]
+    /// 1    2097523        371  76561198048924300      person1      3  15841           3  [(-1636.2, 2091.9, 60.0), (-1601.0, 2070.4, ...
+    pub fn parse_fires(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match fires_and_smokes_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let fires = fires_and_smokes(&output.game_events, &output.infernos).fires;
+
+        let mut unique_id: Vec<u64> = vec![];
+        let mut entity_id: Vec<i32> = vec![];
+        let mut thrower_steamid: Vec<Option<u64>> = vec![];
+        let mut thrower_name: Vec<Option<String>> = vec![];
+        let mut round: Vec<i32> = vec![];
+        let mut tick: Vec<i32> = vec![];
+        let mut fire_count: Vec<i32> = vec![];
+        let mut points: Vec<Vec<(f32, f32, f32)>> = vec![];
+        for fire in &fires {
+            for fire_tick in &fire.timeline {
+                unique_id.push(fire.unique_id);
+                entity_id.push(fire.entity_id);
+                thrower_steamid.push(fire.thrower_steamid);
+                thrower_name.push(fire.thrower_name.clone());
+                round.push(fire.round);
+                tick.push(fire_tick.tick);
+                fire_count.push(fire_tick.points.len() as i32);
+                points.push(fire_tick.points.iter().map(|p| (p[0], p[1], p[2])).collect());
+            }
+        }
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("unique_id", unique_id))?,
+            rust_series_to_py_series(&Series::new("entity_id", entity_id))?,
+            rust_series_to_py_series(&Series::new("thrower_steamid", thrower_steamid))?,
+            rust_series_to_py_series(&Series::new("thrower_name", thrower_name))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("tick", tick))?,
+            rust_series_to_py_series(&Series::new("fire_count", fire_count))?,
+        ];
+        let column_names = [
+            "unique_id",
+            "entity_id",
+            "thrower_steamid",
+            "thrower_name",
+            "round",
+            "tick",
+            "fire_count",
+        ];
+        let df = columns_to_pandas(py, columns, &column_names)?;
+        df.call_method1(py, "__setitem__", ("points", points.to_object(py)))?;
+        Ok(df)
+    }
+    /// Returns a DF with one row per smoke with its center and lifetime. end_tick and
+    /// duration are missing if the smoke was still up when the demo ended.
+    ///
+    /// Example output:
+    ///    entity_id    thrower_steamid thrower_name  thrower_team_num  round  start_tick  end_tick        X       Y      Z   duration
+    /// 0        305  76561198048924300      person1                 2      1        6790      7934  -1004.5  1402.9  -57.9  17.875000
+    pub fn parse_smokes(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match fires_and_smokes_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let rows = fires_and_smokes(&output.game_events, &output.infernos).smokes;
+
+        let entity_id: Vec<i32> = rows.iter().map(|s| s.entity_id).collect();
+        let thrower_steamid: Vec<Option<u64>> = rows.iter().map(|s| s.thrower_steamid).collect();
+        let thrower_name: Vec<Option<String>> = rows.iter().map(|s| s.thrower_name.clone()).collect();
+        let thrower_team_num: Vec<Option<i32>> = rows.iter().map(|s| s.thrower_team_num).collect();
+        let round: Vec<i32> = rows.iter().map(|s| s.round).collect();
+        let start_tick: Vec<i32> = rows.iter().map(|s| s.start_tick).collect();
+        let end_tick: Vec<Option<i32>> = rows.iter().map(|s| s.end_tick).collect();
+        let x: Vec<f32> = rows.iter().map(|s| s.x).collect();
+        let y: Vec<f32> = rows.iter().map(|s| s.y).collect();
+        let z: Vec<f32> = rows.iter().map(|s| s.z).collect();
+        let duration: Vec<Option<f32>> = rows.iter().map(|s| s.duration).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("entity_id", entity_id))?,
+            rust_series_to_py_series(&Series::new("thrower_steamid", thrower_steamid))?,
+            rust_series_to_py_series(&Series::new("thrower_name", thrower_name))?,
+            arr_to_py(Box::new(Int32Array::from(thrower_team_num)))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("start_tick", start_tick))?,
+            arr_to_py(Box::new(Int32Array::from(end_tick)))?,
+            rust_series_to_py_series(&Series::new("X", x))?,
+            rust_series_to_py_series(&Series::new("Y", y))?,
+            rust_series_to_py_series(&Series::new("Z", z))?,
+            rust_series_to_py_series(&Series::new("duration", duration))?,
+        ];
+        let column_names = [
+            "entity_id",
+            "thrower_steamid",
+            "thrower_name",
+            "thrower_team_num",
+            "round",
+            "start_tick",
+            "end_tick",
+            "X",
+            "Y",
+            "Z",
+            "duration",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with the bomb timeline of every round: who carried it, drops and pickups,
+    /// plant and defuse attempts, and the plant, defuse or explosion. Positions are where it
+    /// happened, defuse_progress shows how far an aborted defuse got.
+    /// With per_round=True returns one row per round with the planter, site and outcome.
+    ///
+    /// Example output:
+    ///    round   tick   event_type            steamid     name        X        Y       Z  site  has_kit  defuse_progress
+    /// 0      1   5380       pickup  76561198048924300  person1  -1620.4  -1104.3  -167.9  <NA>     <NA>             <NA>
+    /// 1      1  11720  plant_start  76561198048924300  person1  -1532.1    440.5     0.0  <NA>     <NA>             <NA>
+    #[args(per_round = "false")]
+    pub fn parse_bomb(&self, py: Python<'_>, per_round: bool) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match bomb_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let timeline = bomb_timeline(&output.game_events, &output.bombs);
+
+        if per_round {
+            let rows = &timeline.rounds;
+            let round: Vec<i32> = rows.iter().map(|r| r.round).collect();
+            let first_carrier_steamid: Vec<Option<u64>> = rows.iter().map(|r| r.first_carrier_steamid).collect();
+            let first_carrier_name: Vec<Option<String>> = rows.iter().map(|r| r.first_carrier_name.clone()).collect();
+            let drops: Vec<i32> = rows.iter().map(|r| r.drops).collect();
+            let plant_attempts: Vec<i32> = rows.iter().map(|r| r.plant_attempts).collect();
+            let planter_steamid: Vec<Option<u64>> = rows.iter().map(|r| r.planter_steamid).collect();
+            let planter_name: Vec<Option<String>> = rows.iter().map(|r| r.planter_name.clone()).collect();
+            let plant_tick: Vec<Option<i32>> = rows.iter().map(|r| r.plant_tick).collect();
+            let site: Vec<Option<String>> = rows.iter().map(|r| r.site.clone()).collect();
+            let plant_x: Vec<Option<f32>> = rows.iter().map(|r| r.plant_x).collect();
+            let plant_y: Vec<Option<f32>> = rows.iter().map(|r| r.plant_y).collect();
+            let plant_z: Vec<Option<f32>> = rows.iter().map(|r| r.plant_z).collect();
+            let defuse_attempts: Vec<i32> = rows.iter().map(|r| r.defuse_attempts).collect();
+            let defuser_steamid: Vec<Option<u64>> = rows.iter().map(|r| r.defuser_steamid).collect();
+            let defuser_name: Vec<Option<String>> = rows.iter().map(|r| r.defuser_name.clone()).collect();
+            let defused_tick: Vec<Option<i32>> = rows.iter().map(|r| r.defused_tick).collect();
+            let exploded_tick: Vec<Option<i32>> = rows.iter().map(|r| r.exploded_tick).collect();
+            let time_left_on_defuse: Vec<Option<f32>> = rows.iter().map(|r| r.time_left_on_defuse).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("round", round))?,
+                rust_series_to_py_series(&Series::new("first_carrier_steamid", first_carrier_steamid))?,
+                rust_series_to_py_series(&Series::new("first_carrier_name", first_carrier_name))?,
+                rust_series_to_py_series(&Series::new("drops", drops))?,
+                rust_series_to_py_series(&Series::new("plant_attempts", plant_attempts))?,
+                rust_series_to_py_series(&Series::new("planter_steamid", planter_steamid))?,
+                rust_series_to_py_series(&Series::new("planter_name", planter_name))?,
+                arr_to_py(Box::new(Int32Array::from(plant_tick)))?,
+                rust_series_to_py_series(&Series::new("site", site))?,
+                rust_series_to_py_series(&Series::new("plant_X", plant_x))?,
+                rust_series_to_py_series(&Series::new("plant_Y", plant_y))?,
+                rust_series_to_py_series(&Series::new("plant_Z", plant_z))?,
+                rust_series_to_py_series(&Series::new("defuse_attempts", defuse_attempts))?,
+                rust_series_to_py_series(&Series::new("defuser_steamid", defuser_steamid))?,
+                rust_series_to_py_series(&Series::new("defuser_name", defuser_name))?,
+                arr_to_py(Box::new(Int32Array::from(defused_tick)))?,
+                arr_to_py(Box::new(Int32Array::from(exploded_tick)))?,
+                rust_series_to_py_series(&Series::new("time_left_on_defuse", time_left_on_defuse))?,
+            ];
+            let column_names = [
+                "round",
+                "first_carrier_steamid",
+                "first_carrier_name",
+                "drops",
+                "plant_attempts",
+                "planter_steamid",
+                "planter_name",
+                "plant_tick",
+                "site",
+                "plant_X",
+                "plant_Y",
+                "plant_Z",
+                "defuse_attempts",
+                "defuser_steamid",
+                "defuser_name",
+                "defused_tick",
+                "exploded_tick",
+                "time_left_on_defuse",
+            ];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows: Vec<_> = timeline.rounds.iter().flat_map(|r| r.events.iter()).collect();
+        let round: Vec<i32> = rows.iter().map(|e| e.round).collect();
+        let tick: Vec<i32> = rows.iter().map(|e| e.tick).collect();
+        let event_type: Vec<String> = rows.iter().map(|e| e.event_type.clone()).collect();
+        let steamid: Vec<Option<u64>> = rows.iter().map(|e| e.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|e| e.name.clone()).collect();
+        let x: Vec<Option<f32>> = rows.iter().map(|e| e.x).collect();
+        let y: Vec<Option<f32>> = rows.iter().map(|e| e.y).collect();
+        let z: Vec<Option<f32>> = rows.iter().map(|e| e.z).collect();
+        let site: Vec<Option<String>> = rows.iter().map(|e| e.site.clone()).collect();
+        let has_kit: Vec<Option<bool>> = rows.iter().map(|e| e.has_kit).collect();
+        let defuse_progress: Vec<Option<f32>> = rows.iter().map(|e| e.defuse_progress).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("tick", tick))?,
+            rust_series_to_py_series(&Series::new("event_type", event_type))?,
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            rust_series_to_py_series(&Series::new("X", x))?,
+            rust_series_to_py_series(&Series::new("Y", y))?,
+            rust_series_to_py_series(&Series::new("Z", z))?,
+            rust_series_to_py_series(&Series::new("site", site))?,
+            rust_series_to_py_series(&Series::new("has_kit", has_kit))?,
+            rust_series_to_py_series(&Series::new("defuse_progress", defuse_progress))?,
+        ];
+        let column_names = [
+            "round",
+            "tick",
+            "event_type",
+            "steamid",
+            "name",
+            "X",
+            "Y",
+            "Z",
+            "site",
+            "has_kit",
+            "defuse_progress",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with every player_death and extra columns computed at the time of the kill:
+    /// distance, both positions, how far off the attacker's crosshair was from the victim at the
+    /// kill and 8 ticks before it, the victim's speed, if the victim was flashed, if the
+    /// attacker was in the air and if the victim had spotted the attacker.
+    ///
+    /// Example output:
+    ///    attacker_name  user_name  distance  view_angle_offset  view_angle_offset_before  user_velocity  victim_flashed  attacker_airborne  victim_spotted_attacker
+    /// 0        person1    person2   1243.51               0.84                      6.12         215.03           False              False                     True
+    pub fn parse_kills(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match kills_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let kills = kills(&output.game_events);
+        match series_from_event(&kills, py) {
+            Ok(ser) => Ok(ser),
+            Err(_e) => Ok(PyList::empty(py).into()),
+        }
+    }
+    /// Returns a DF with every "A spotted B" interval: the parser tracks the spotted mask of every
+    /// player and records when it turns on and off. With per_kill=True returns one row per kill
+    /// with when the attacker and the victim last started seeing each other, who saw who first
+    /// and the seconds from the attacker spotting the victim to the kill.
+    ///
+    /// Example output:
+    ///     observer_steamid  observer_name     target_steamid  target_name  round  start_tick  end_tick  duration
+    /// 0  76561198048924300        person1  76561198189734257      person2      1        6122      6180   0.921875
+    #[args(per_kill = "false")]
+    pub fn parse_visibility(&self, py: Python<'_>, per_kill: bool) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match visibility_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let visibility = visibility(&output.game_events, &output.spotted);
+
+        if per_kill {
+            let rows = &visibility.kills;
+            let tick: Vec<i32> = rows.iter().map(|k| k.tick).collect();
+            let round: Vec<i32> = rows.iter().map(|k| k.round).collect();
+            let attacker_steamid: Vec<Option<u64>> = rows.iter().map(|k| k.attacker_steamid).collect();
+            let attacker_name: Vec<Option<String>> = rows.iter().map(|k| k.attacker_name.clone()).collect();
+            let victim_steamid: Vec<Option<u64>> = rows.iter().map(|k| k.victim_steamid).collect();
+            let victim_name: Vec<Option<String>> = rows.iter().map(|k| k.victim_name.clone()).collect();
+            let attacker_saw_victim_tick: Vec<Option<i32>> = rows.iter().map(|k| k.attacker_saw_victim_tick).collect();
+            let victim_saw_attacker_tick: Vec<Option<i32>> = rows.iter().map(|k| k.victim_saw_attacker_tick).collect();
+            let time_to_kill: Vec<Option<f32>> = rows.iter().map(|k| k.time_to_kill).collect();
+            let spotted_first: Vec<Option<String>> = rows.iter().map(|k| k.spotted_first.clone()).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("tick", tick))?,
+                rust_series_to_py_series(&Series::new("round", round))?,
+                rust_series_to_py_series(&Series::new("attacker_steamid", attacker_steamid))?,
+                rust_series_to_py_series(&Series::new("attacker_name", attacker_name))?,
+                rust_series_to_py_series(&Series::new("victim_steamid", victim_steamid))?,
+                rust_series_to_py_series(&Series::new("victim_name", victim_name))?,
+                arr_to_py(Box::new(Int32Array::from(attacker_saw_victim_tick)))?,
+                arr_to_py(Box::new(Int32Array::from(victim_saw_attacker_tick)))?,
+                rust_series_to_py_series(&Series::new("time_to_kill", time_to_kill))?,
+                rust_series_to_py_series(&Series::new("spotted_first", spotted_first))?,
+            ];
+            let column_names = [
+                "tick",
+                "round",
+                "attacker_steamid",
+                "attacker_name",
+                "victim_steamid",
+                "victim_name",
+                "attacker_saw_victim_tick",
+                "victim_saw_attacker_tick",
+                "time_to_kill",
+                "spotted_first",
+            ];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows = &visibility.intervals;
+        let observer_steamid: Vec<Option<u64>> = rows.iter().map(|i| i.observer_steamid).collect();
+        let observer_name: Vec<Option<String>> = rows.iter().map(|i| i.observer_name.clone()).collect();
+        let target_steamid: Vec<Option<u64>> = rows.iter().map(|i| i.target_steamid).collect();
+        let target_name: Vec<Option<String>> = rows.iter().map(|i| i.target_name.clone()).collect();
+        let round: Vec<i32> = rows.iter().map(|i| i.round).collect();
+        let start_tick: Vec<i32> = rows.iter().map(|i| i.start_tick).collect();
+        let end_tick: Vec<i32> = rows.iter().map(|i| i.end_tick).collect();
+        let duration: Vec<f32> = rows.iter().map(|i| i.duration).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("observer_steamid", observer_steamid))?,
+            rust_series_to_py_series(&Series::new("observer_name", observer_name))?,
+            rust_series_to_py_series(&Series::new("target_steamid", target_steamid))?,
+            rust_series_to_py_series(&Series::new("target_name", target_name))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("start_tick", start_tick))?,
+            rust_series_to_py_series(&Series::new("end_tick", end_tick))?,
+            rust_series_to_py_series(&Series::new("duration", duration))?,
+        ];
+        let column_names = [
+            "observer_steamid",
+            "observer_name",
+            "target_steamid",
+            "target_name",
+            "round",
+            "start_tick",
+            "end_tick",
+            "duration",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with the movement of every player per round: horizontal distance, seconds
+    /// spent running, walking, crouched, in the air and standing still (from the end of
+    /// freezetime to the end of the round), jumps and how many shots were fired while moving
+    /// slow enough to be accurate. With per_shot=True returns one row per weapon_fire with the
+    /// shooter's horizontal speed, the weapon's max speed and if the shot was accurate
+    /// (below 34% of the max speed and on the ground), useful for checking counter-strafes.
+    ///
+    /// Example output:
+    ///              steamid     name  round     distance  time_running  time_walking  time_crouched  time_airborne  time_still  jumps  shots  gun_shots  accurate_shots
+    /// 0  76561198048924300  person1      1  4532.210938     18.234375      3.109375       1.453125        0.84375   12.390625      2     14         14              11
+    #[args(per_shot = "false")]
+    pub fn parse_movement(&self, py: Python<'_>, per_shot: bool) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match movement_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let movement = movement(&output.game_events, &output.movement);
+
+        if per_shot {
+            let rows = &movement.shots;
+            let tick: Vec<i32> = rows.iter().map(|s| s.tick).collect();
+            let round: Vec<i32> = rows.iter().map(|s| s.round).collect();
+            let steamid: Vec<Option<u64>> = rows.iter().map(|s| s.steamid).collect();
+            let name: Vec<Option<String>> = rows.iter().map(|s| s.name.clone()).collect();
+            let weapon: Vec<Option<String>> = rows.iter().map(|s| s.weapon.clone()).collect();
+            let speed: Vec<Option<f32>> = rows.iter().map(|s| s.speed).collect();
+            let max_speed: Vec<Option<f32>> = rows.iter().map(|s| s.max_speed).collect();
+            let airborne: Vec<Option<bool>> = rows.iter().map(|s| s.airborne).collect();
+            let accurate: Vec<Option<bool>> = rows.iter().map(|s| s.accurate).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("tick", tick))?,
+                rust_series_to_py_series(&Series::new("round", round))?,
+                rust_series_to_py_series(&Series::new("steamid", steamid))?,
+                rust_series_to_py_series(&Series::new("name", name))?,
+                rust_series_to_py_series(&Series::new("weapon", weapon))?,
+                rust_series_to_py_series(&Series::new("speed", speed))?,
+                rust_series_to_py_series(&Series::new("max_speed", max_speed))?,
+                rust_series_to_py_series(&Series::new("airborne", airborne))?,
+                rust_series_to_py_series(&Series::new("accurate", accurate))?,
+            ];
+            let column_names = [
+                "tick",
+                "round",
+                "steamid",
+                "name",
+                "weapon",
+                "speed",
+                "max_speed",
+                "airborne",
+                "accurate",
+            ];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows = &movement.players;
+        let steamid: Vec<u64> = rows.iter().map(|p| p.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|p| p.name.clone()).collect();
+        let round: Vec<i32> = rows.iter().map(|p| p.round).collect();
+        let distance: Vec<f32> = rows.iter().map(|p| p.distance).collect();
+        let time_running: Vec<f32> = rows.iter().map(|p| p.time_running).collect();
+        let time_walking: Vec<f32> = rows.iter().map(|p| p.time_walking).collect();
+        let time_crouched: Vec<f32> = rows.iter().map(|p| p.time_crouched).collect();
+        let time_airborne: Vec<f32> = rows.iter().map(|p| p.time_airborne).collect();
+        let time_still: Vec<f32> = rows.iter().map(|p| p.time_still).collect();
+        let jumps: Vec<i32> = rows.iter().map(|p| p.jumps).collect();
+        let shots: Vec<i32> = rows.iter().map(|p| p.shots).collect();
+        let gun_shots: Vec<i32> = rows.iter().map(|p| p.gun_shots).collect();
+        let accurate_shots: Vec<i32> = rows.iter().map(|p| p.accurate_shots).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("distance", distance))?,
+            rust_series_to_py_series(&Series::new("time_running", time_running))?,
+            rust_series_to_py_series(&Series::new("time_walking", time_walking))?,
+            rust_series_to_py_series(&Series::new("time_crouched", time_crouched))?,
+            rust_series_to_py_series(&Series::new("time_airborne", time_airborne))?,
+            rust_series_to_py_series(&Series::new("time_still", time_still))?,
+            rust_series_to_py_series(&Series::new("jumps", jumps))?,
+            rust_series_to_py_series(&Series::new("shots", shots))?,
+            rust_series_to_py_series(&Series::new("gun_shots", gun_shots))?,
+            rust_series_to_py_series(&Series::new("accurate_shots", accurate_shots))?,
+        ];
+        let column_names = [
+            "steamid",
+            "name",
+            "round",
+            "distance",
+            "time_running",
+            "time_walking",
+            "time_crouched",
+            "time_airborne",
+            "time_still",
+            "jumps",
+            "shots",
+            "gun_shots",
+            "accurate_shots",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with every shot: weapon_fire events matched with the player_hurt events they
+    /// caused, so every shot is a hit or a miss with the hitgroup, damage and victim of the hit.
+    /// "spray_index" is the number of the bullet within the spray, starting from 1. With
+    /// per_weapon=True returns accuracy (shots that hit / shots) and headshots per hit per
+    /// player per weapon.
+    ///
+    /// Example output:
+    ///     tick  round            steamid     name weapon  spray_index    hit  hits  damage hitgroup  headshot     victim_steamid victim_name
+    /// 0  12022      1  76561198048924300  person1   ak47            1   True     1     111     head      True  76561198189734257     person2
+    #[args(per_weapon = "false")]
+    pub fn parse_shots(&self, py: Python<'_>, per_weapon: bool) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match shots_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let shots = shots(&output.game_events);
+
+        if per_weapon {
+            let rows = &shots.accuracy;
+            let steamid: Vec<u64> = rows.iter().map(|a| a.steamid).collect();
+            let name: Vec<Option<String>> = rows.iter().map(|a| a.name.clone()).collect();
+            let weapon: Vec<String> = rows.iter().map(|a| a.weapon.clone()).collect();
+            let shot_count: Vec<i32> = rows.iter().map(|a| a.shots).collect();
+            let hits: Vec<i32> = rows.iter().map(|a| a.hits).collect();
+            let headshots: Vec<i32> = rows.iter().map(|a| a.headshots).collect();
+            let damage: Vec<i32> = rows.iter().map(|a| a.damage).collect();
+            let accuracy: Vec<f32> = rows.iter().map(|a| a.accuracy).collect();
+            let headshots_per_hit: Vec<f32> = rows.iter().map(|a| a.headshots_per_hit).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("steamid", steamid))?,
+                rust_series_to_py_series(&Series::new("name", name))?,
+                rust_series_to_py_series(&Series::new("weapon", weapon))?,
+                rust_series_to_py_series(&Series::new("shots", shot_count))?,
+                rust_series_to_py_series(&Series::new("hits", hits))?,
+                rust_series_to_py_series(&Series::new("headshots", headshots))?,
+                rust_series_to_py_series(&Series::new("damage", damage))?,
+                rust_series_to_py_series(&Series::new("accuracy", accuracy))?,
+                rust_series_to_py_series(&Series::new("headshots_per_hit", headshots_per_hit))?,
+            ];
+            let column_names = [
+                "steamid",
+                "name",
+                "weapon",
+                "shots",
+                "hits",
+                "headshots",
+                "damage",
+                "accuracy",
+                "headshots_per_hit",
+            ];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows = &shots.shots;
+        let tick: Vec<i32> = rows.iter().map(|s| s.tick).collect();
+        let round: Vec<i32> = rows.iter().map(|s| s.round).collect();
+        let steamid: Vec<Option<u64>> = rows.iter().map(|s| s.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|s| s.name.clone()).collect();
+        let weapon: Vec<String> = rows.iter().map(|s| s.weapon.clone()).collect();
+        let spray_index: Vec<i32> = rows.iter().map(|s| s.spray_index).collect();
+        let hit: Vec<bool> = rows.iter().map(|s| s.hit).collect();
+        let hits: Vec<i32> = rows.iter().map(|s| s.hits).collect();
+        let damage: Vec<i32> = rows.iter().map(|s| s.damage).collect();
+        let hitgroup: Vec<Option<String>> = rows.iter().map(|s| s.hitgroup.clone()).collect();
+        let headshot: Vec<bool> = rows.iter().map(|s| s.headshot).collect();
+        let victim_steamid: Vec<Option<u64>> = rows.iter().map(|s| s.victim_steamid).collect();
+        let victim_name: Vec<Option<String>> = rows.iter().map(|s| s.victim_name.clone()).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("tick", tick))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            rust_series_to_py_series(&Series::new("weapon", weapon))?,
+            rust_series_to_py_series(&Series::new("spray_index", spray_index))?,
+            rust_series_to_py_series(&Series::new("hit", hit))?,
+            rust_series_to_py_series(&Series::new("hits", hits))?,
+            rust_series_to_py_series(&Series::new("damage", damage))?,
+            rust_series_to_py_series(&Series::new("hitgroup", hitgroup))?,
+            rust_series_to_py_series(&Series::new("headshot", headshot))?,
+            rust_series_to_py_series(&Series::new("victim_steamid", victim_steamid))?,
+            rust_series_to_py_series(&Series::new("victim_name", victim_name))?,
+        ];
+        let column_names = [
+            "tick",
+            "round",
+            "steamid",
+            "name",
+            "weapon",
+            "spray_index",
+            "hit",
+            "hits",
+            "damage",
+            "hitgroup",
+            "headshot",
+            "victim_steamid",
+            "victim_name",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with every bullet of every spray: eye angles ("pitch", "yaw"), aim punch and its
+    /// velocity, where the bullet went ("aim_pitch"/"aim_yaw" = eye angles + 2 * punch) and the
+    /// compensation error: degrees between where the bullet went and where the first bullet of the
+    /// spray went. With per_player=True returns the average compensation error and the accuracy
+    /// after the first bullet per player per weapon, a spray control score.
+    ///
+    /// Example output:
+    ///      tick  round            steamid     name weapon  spray_start_tick  spray_index      pitch        yaw  punch_pitch  punch_yaw  punch_vel_pitch  punch_vel_yaw  aim_pitch    aim_yaw  compensation_error    hit
+    /// 0   12022      1  76561198048924300  person1   ak47             12022            1   2.197266  91.054688     0.000000   0.000000         0.000000       0.000000   2.197266  91.054688            0.000000   True
+    /// 1   12028      1  76561198048924300  person1   ak47             12022            2   2.856445  91.142578    -0.512695   0.061523       -20.478516       2.512695   1.831055  91.265625            0.384863  False
+    #[args(per_player = "false")]
+    pub fn parse_recoil(&self, py: Python<'_>, per_player: bool) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match recoil_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let recoil = recoil(&output.game_events);
+
+        if per_player {
+            let rows = &recoil.players;
+            let steamid: Vec<u64> = rows.iter().map(|c| c.steamid).collect();
+            let name: Vec<Option<String>> = rows.iter().map(|c| c.name.clone()).collect();
+            let weapon: Vec<String> = rows.iter().map(|c| c.weapon.clone()).collect();
+            let sprays: Vec<i32> = rows.iter().map(|c| c.sprays).collect();
+            let bullets: Vec<i32> = rows.iter().map(|c| c.bullets).collect();
+            let avg_compensation_error: Vec<Option<f32>> = rows.iter().map(|c| c.avg_compensation_error).collect();
+            let spray_accuracy: Vec<f32> = rows.iter().map(|c| c.spray_accuracy).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("steamid", steamid))?,
+                rust_series_to_py_series(&Series::new("name", name))?,
+                rust_series_to_py_series(&Series::new("weapon", weapon))?,
+                rust_series_to_py_series(&Series::new("sprays", sprays))?,
+                rust_series_to_py_series(&Series::new("bullets", bullets))?,
+                rust_series_to_py_series(&Series::new("avg_compensation_error", avg_compensation_error))?,
+                rust_series_to_py_series(&Series::new("spray_accuracy", spray_accuracy))?,
+            ];
+            let column_names = [
+                "steamid",
+                "name",
+                "weapon",
+                "sprays",
+                "bullets",
+                "avg_compensation_error",
+                "spray_accuracy",
+            ];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows = &recoil.bullets;
+        let tick: Vec<i32> = rows.iter().map(|b| b.tick).collect();
+        let round: Vec<i32> = rows.iter().map(|b| b.round).collect();
+        let steamid: Vec<Option<u64>> = rows.iter().map(|b| b.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|b| b.name.clone()).collect();
+        let weapon: Vec<String> = rows.iter().map(|b| b.weapon.clone()).collect();
+        let spray_start_tick: Vec<i32> = rows.iter().map(|b| b.spray_start_tick).collect();
+        let spray_index: Vec<i32> = rows.iter().map(|b| b.spray_index).collect();
+        let pitch: Vec<Option<f32>> = rows.iter().map(|b| b.pitch).collect();
+        let yaw: Vec<Option<f32>> = rows.iter().map(|b| b.yaw).collect();
+        let punch_pitch: Vec<Option<f32>> = rows.iter().map(|b| b.punch_pitch).collect();
+        let punch_yaw: Vec<Option<f32>> = rows.iter().map(|b| b.punch_yaw).collect();
+        let punch_vel_pitch: Vec<Option<f32>> = rows.iter().map(|b| b.punch_vel_pitch).collect();
+        let punch_vel_yaw: Vec<Option<f32>> = rows.iter().map(|b| b.punch_vel_yaw).collect();
+        let aim_pitch: Vec<Option<f32>> = rows.iter().map(|b| b.aim_pitch).collect();
+        let aim_yaw: Vec<Option<f32>> = rows.iter().map(|b| b.aim_yaw).collect();
+        let compensation_error: Vec<Option<f32>> = rows.iter().map(|b| b.compensation_error).collect();
+        let hit: Vec<bool> = rows.iter().map(|b| b.hit).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("tick", tick))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            rust_series_to_py_series(&Series::new("weapon", weapon))?,
+            rust_series_to_py_series(&Series::new("spray_start_tick", spray_start_tick))?,
+            rust_series_to_py_series(&Series::new("spray_index", spray_index))?,
+            rust_series_to_py_series(&Series::new("pitch", pitch))?,
+            rust_series_to_py_series(&Series::new("yaw", yaw))?,
+            rust_series_to_py_series(&Series::new("punch_pitch", punch_pitch))?,
+            rust_series_to_py_series(&Series::new("punch_yaw", punch_yaw))?,
+            rust_series_to_py_series(&Series::new("punch_vel_pitch", punch_vel_pitch))?,
+            rust_series_to_py_series(&Series::new("punch_vel_yaw", punch_vel_yaw))?,
+            rust_series_to_py_series(&Series::new("aim_pitch", aim_pitch))?,
+            rust_series_to_py_series(&Series::new("aim_yaw", aim_yaw))?,
+            rust_series_to_py_series(&Series::new("compensation_error", compensation_error))?,
+            rust_series_to_py_series(&Series::new("hit", hit))?,
+        ];
+        let column_names = [
+            "tick",
+            "round",
+            "steamid",
+            "name",
+            "weapon",
+            "spray_start_tick",
+            "spray_index",
+            "pitch",
+            "yaw",
+            "punch_pitch",
+            "punch_yaw",
+            "punch_vel_pitch",
+            "punch_vel_yaw",
+            "aim_pitch",
+            "aim_yaw",
+            "compensation_error",
+            "hit",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF of suspicious aim to review, most unusual first. "flick_to_head" rows are flicks
+    /// (view turning faster than 600 deg/s) that stop within 2 degrees of an enemy head right before
+    /// the player shoots, "occluded_tracking" rows are the crosshair following an enemy head through
+    /// walls while the enemy is not spotted. "review_start_tick"/"review_end_tick" pad the moment by
+    /// 2 seconds and "score" is how many standard deviations the flick speed / tracking duration is
+    /// above the rest of the demo. This is a first pass for a human to check, not a verdict.
+    ///
+    /// Example output:
+    ///                  kind  round            steamid     name     target_steamid target_name  start_tick  end_tick  review_start_tick  review_end_tick  duration      angle   peak_speed  peak_acceleration  target_offset  target_spotted  shot_tick  killed_target     score
+    /// 0       flick_to_head      7  76561198048924300  person1  76561198189734257     person2       60121     60126              59993            60254  0.093750  54.123047  1432.617188       48213.867188       0.812305            True      60128           True  4.018431
+    /// 1   occluded_tracking     12  76561198048924300  person1  76561198189734257     person2       98032     98170              97904            98298  2.171875  23.455078   168.310547        2904.296875       1.104492           False        NaN          False  3.220512
+    pub fn parse_aim(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match aim_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let aim = aim(&output.game_events, &output.aim);
+
+        let rows = &aim;
+        let kind: Vec<String> = rows.iter().map(|e| e.kind.clone()).collect();
+        let round: Vec<i32> = rows.iter().map(|e| e.round).collect();
+        let steamid: Vec<Option<u64>> = rows.iter().map(|e| e.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|e| e.name.clone()).collect();
+        let target_steamid: Vec<Option<u64>> = rows.iter().map(|e| e.target_steamid).collect();
+        let target_name: Vec<Option<String>> = rows.iter().map(|e| e.target_name.clone()).collect();
+        let start_tick: Vec<i32> = rows.iter().map(|e| e.start_tick).collect();
+        let end_tick: Vec<i32> = rows.iter().map(|e| e.end_tick).collect();
+        let review_start_tick: Vec<i32> = rows.iter().map(|e| e.review_start_tick).collect();
+        let review_end_tick: Vec<i32> = rows.iter().map(|e| e.review_end_tick).collect();
+        let duration: Vec<f32> = rows.iter().map(|e| e.duration).collect();
+        let angle: Vec<f32> = rows.iter().map(|e| e.angle).collect();
+        let peak_speed: Vec<f32> = rows.iter().map(|e| e.peak_speed).collect();
+        let peak_acceleration: Vec<f32> = rows.iter().map(|e| e.peak_acceleration).collect();
+        let target_offset: Vec<Option<f32>> = rows.iter().map(|e| e.target_offset).collect();
+        let target_spotted: Vec<Option<bool>> = rows.iter().map(|e| e.target_spotted).collect();
+        let shot_tick: Vec<Option<i32>> = rows.iter().map(|e| e.shot_tick).collect();
+        let killed_target: Vec<bool> = rows.iter().map(|e| e.killed_target).collect();
+        let score: Vec<f32> = rows.iter().map(|e| e.score).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("kind", kind))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            rust_series_to_py_series(&Series::new("target_steamid", target_steamid))?,
+            rust_series_to_py_series(&Series::new("target_name", target_name))?,
+            rust_series_to_py_series(&Series::new("start_tick", start_tick))?,
+            rust_series_to_py_series(&Series::new("end_tick", end_tick))?,
+            rust_series_to_py_series(&Series::new("review_start_tick", review_start_tick))?,
+            rust_series_to_py_series(&Series::new("review_end_tick", review_end_tick))?,
+            rust_series_to_py_series(&Series::new("duration", duration))?,
+            rust_series_to_py_series(&Series::new("angle", angle))?,
+            rust_series_to_py_series(&Series::new("peak_speed", peak_speed))?,
+            rust_series_to_py_series(&Series::new("peak_acceleration", peak_acceleration))?,
+            rust_series_to_py_series(&Series::new("target_offset", target_offset))?,
+            rust_series_to_py_series(&Series::new("target_spotted", target_spotted))?,
+            arr_to_py(Box::new(Int32Array::from(shot_tick)))?,
+            rust_series_to_py_series(&Series::new("killed_target", killed_target))?,
+            rust_series_to_py_series(&Series::new("score", score))?,
+        ];
+        let column_names = [
+            "kind",
+            "round",
+            "steamid",
+            "name",
+            "target_steamid",
+            "target_name",
+            "start_tick",
+            "end_tick",
+            "review_start_tick",
+            "review_end_tick",
+            "duration",
+            "angle",
+            "peak_speed",
+            "peak_acceleration",
+            "target_offset",
+            "target_spotted",
+            "shot_tick",
+            "killed_target",
+            "score",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with the economy of both teams every round, taken when buy time ends: money at
+    /// the start of the round, spent, saved, equipment value (kept + bought), money earned during the
+    /// round, loss bonus level and the buy type. Rounds where nobody had more than $800 are "pistol",
+    /// the rest are classified by the average equipment value per player: below eco is "eco", below
+    /// semi_eco "semi_eco", below force "force" and the rest "full_buy". "money_adjusted" is only filled
+    /// in POV demos. With per_player=True returns the money of every player when buy time ended.
+    ///
+    /// Example output:
+    ///     round  team_num  players  start_money  spent  saved  equipment_value  earned  money_adjusted  loss_streak  loss_bonus_level  loss_bonus  buy_type
+    /// 0       1         2        5         4000   3350    650             3350    9350               0            1                 1        1900    pistol
+    /// 1       1         3        5         4000   2800   1200             2800    8200               0            0                 0        1400    pistol
+    /// 2       2         2        5        10690   9300   1390             9600   17900               0            0                 0        1400  full_buy
+    /// 3       2         3        5         8950   2250   6700             2850    7450               0            1                 1        1900       eco
+    #[args(eco = "1000", semi_eco = "2000", force = "4000", per_player = "false")]
+    pub fn parse_economy(
+        &self,
+        py: Python<'_>,
+        eco: i32,
+        semi_eco: i32,
+        force: i32,
+        per_player: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match economy_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let thresholds = BuyThresholds {
+            eco,
+            semi_eco,
+            force,
+        };
+        let economy = economy(&output.game_events, &output.economy, &thresholds);
+
+        if per_player {
+            let rows = &economy.players;
+            let steamid: Vec<u64> = rows.iter().map(|r| r.steamid).collect();
+            let name: Vec<Option<String>> = rows.iter().map(|r| r.name.clone()).collect();
+            let team_num: Vec<Option<i32>> =
+                rows.iter().map(|r| r.team.map(|t| t as i32)).collect();
+            let round: Vec<i32> = rows.iter().map(|r| r.round).collect();
+            let tick: Vec<i32> = rows.iter().map(|r| r.tick).collect();
+            let start_balance: Vec<Option<i32>> = rows.iter().map(|r| r.start_balance).collect();
+            let balance: Vec<Option<i32>> = rows.iter().map(|r| r.balance).collect();
+            let spent: Vec<Option<i32>> = rows.iter().map(|r| r.spent).collect();
+            let start_equip_value: Vec<Option<i32>> =
+                rows.iter().map(|r| r.start_equip_value).collect();
+            let equip_value: Vec<Option<i32>> = rows.iter().map(|r| r.equip_value).collect();
+            let earned: Vec<Option<i32>> = rows.iter().map(|r| r.earned).collect();
+            let loss_streak: Vec<Option<i32>> = rows.iter().map(|r| r.loss_streak).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("steamid", steamid))?,
+                rust_series_to_py_series(&Series::new("name", name))?,
+                arr_to_py(Box::new(Int32Array::from(team_num)))?,
+                rust_series_to_py_series(&Series::new("round", round))?,
+                rust_series_to_py_series(&Series::new("tick", tick))?,
+                arr_to_py(Box::new(Int32Array::from(start_balance)))?,
+                arr_to_py(Box::new(Int32Array::from(balance)))?,
+                arr_to_py(Box::new(Int32Array::from(spent)))?,
+                arr_to_py(Box::new(Int32Array::from(start_equip_value)))?,
+                arr_to_py(Box::new(Int32Array::from(equip_value)))?,
+                arr_to_py(Box::new(Int32Array::from(earned)))?,
+                arr_to_py(Box::new(Int32Array::from(loss_streak)))?,
+            ];
+            let column_names = [
+                "steamid",
+                "name",
+                "team_num",
+                "round",
+                "tick",
+                "start_balance",
+                "balance",
+                "spent",
+                "start_equip_value",
+                "equip_value",
+                "earned",
+                "loss_streak",
+            ];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows = &economy.teams;
+        let round: Vec<i32> = rows.iter().map(|t| t.round).collect();
+        let team_num: Vec<i32> = rows.iter().map(|t| t.team_num).collect();
+        let players: Vec<i32> = rows.iter().map(|t| t.players).collect();
+        let start_money: Vec<i32> = rows.iter().map(|t| t.start_money).collect();
+        let spent: Vec<i32> = rows.iter().map(|t| t.spent).collect();
+        let saved: Vec<i32> = rows.iter().map(|t| t.saved).collect();
+        let equipment_value: Vec<i32> = rows.iter().map(|t| t.equipment_value).collect();
+        let earned: Vec<i32> = rows.iter().map(|t| t.earned).collect();
+        let money_adjusted: Vec<i32> = rows.iter().map(|t| t.money_adjusted).collect();
+        let loss_streak: Vec<Option<i32>> = rows.iter().map(|t| t.loss_streak).collect();
+        let loss_bonus_level: Vec<Option<i32>> = rows.iter().map(|t| t.loss_bonus_level).collect();
+        let loss_bonus: Vec<Option<i32>> = rows.iter().map(|t| t.loss_bonus).collect();
+        let buy_type: Vec<String> = rows.iter().map(|t| t.buy_type.clone()).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("team_num", team_num))?,
+            rust_series_to_py_series(&Series::new("players", players))?,
+            rust_series_to_py_series(&Series::new("start_money", start_money))?,
+            rust_series_to_py_series(&Series::new("spent", spent))?,
+            rust_series_to_py_series(&Series::new("saved", saved))?,
+            rust_series_to_py_series(&Series::new("equipment_value", equipment_value))?,
+            rust_series_to_py_series(&Series::new("earned", earned))?,
+            rust_series_to_py_series(&Series::new("money_adjusted", money_adjusted))?,
+            arr_to_py(Box::new(Int32Array::from(loss_streak)))?,
+            arr_to_py(Box::new(Int32Array::from(loss_bonus_level)))?,
+            arr_to_py(Box::new(Int32Array::from(loss_bonus)))?,
+            rust_series_to_py_series(&Series::new("buy_type", buy_type))?,
+        ];
+        let column_names = [
+            "round",
+            "team_num",
+            "players",
+            "start_money",
+            "spent",
+            "saved",
+            "equipment_value",
+            "earned",
+            "money_adjusted",
+            "loss_streak",
+            "loss_bonus_level",
+            "loss_bonus",
+            "buy_type",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with kills, damage, shots, hits, headshots and deaths per player per weapon.
+    /// Weapon names are the same everywhere: "weapon_m4a1_silencer", "m4a1_silencer_off" and "M4A1-S"
+    /// are all "m4a1_silencer", every knife is "knife" and molotovs, incendiaries and their fire
+    /// ("inferno") are "molotov". "deaths" is how many times the player was killed with the weapon.
+    /// Team kills, team damage and self damage are left out.
+    ///
+    /// Example output:
+    ///               steamid     name         weapon  kills  headshot_kills  deaths  damage  shots  hits  headshots  accuracy
+    /// 0   76561198048924300  person1           ak47     14               7       9    1721    312    58         12  0.185897
+    /// 1   76561198048924300  person1      hegrenade      0               0       0      96      0     0          0       NaN
+    pub fn parse_weapons(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match weapons_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let weapons = weapons(&output.game_events);
+
+        let rows = &weapons;
+        let steamid: Vec<u64> = rows.iter().map(|w| w.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|w| w.name.clone()).collect();
+        let weapon: Vec<String> = rows.iter().map(|w| w.weapon.clone()).collect();
+        let kills: Vec<i32> = rows.iter().map(|w| w.kills).collect();
+        let headshot_kills: Vec<i32> = rows.iter().map(|w| w.headshot_kills).collect();
+        let deaths: Vec<i32> = rows.iter().map(|w| w.deaths).collect();
+        let damage: Vec<i32> = rows.iter().map(|w| w.damage).collect();
+        let shots: Vec<i32> = rows.iter().map(|w| w.shots).collect();
+        let hits: Vec<i32> = rows.iter().map(|w| w.hits).collect();
+        let headshots: Vec<i32> = rows.iter().map(|w| w.headshots).collect();
+        let accuracy: Vec<Option<f32>> = rows.iter().map(|w| w.accuracy).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            rust_series_to_py_series(&Series::new("weapon", weapon))?,
+            rust_series_to_py_series(&Series::new("kills", kills))?,
+            rust_series_to_py_series(&Series::new("headshot_kills", headshot_kills))?,
+            rust_series_to_py_series(&Series::new("deaths", deaths))?,
+            rust_series_to_py_series(&Series::new("damage", damage))?,
+            rust_series_to_py_series(&Series::new("shots", shots))?,
+            rust_series_to_py_series(&Series::new("hits", hits))?,
+            rust_series_to_py_series(&Series::new("headshots", headshots))?,
+            rust_series_to_py_series(&Series::new("accuracy", accuracy))?,
+        ];
+        let column_names = [
+            "steamid",
+            "name",
+            "weapon",
+            "kills",
+            "headshot_kills",
+            "deaths",
+            "damage",
+            "shots",
+            "hits",
+            "headshots",
+            "accuracy",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a DF with the skin of every weapon entity in the demo, a row every time the weapon gets
+    /// a new owner (the first row of a dropped weapon has no owner). Read from the CEconItemAttribute
+    /// lists of the weapons: paint kit, "paint_wear" (the float), "paint_seed", StatTrak count, sticker
+    /// ids and their wear per slot and the charm. "item_id" is the inventory item id, None for default
+    /// weapons. "original_owner_steamid" is the player that bought the weapon.
+    ///
+    /// Example output:
+    ///     tick  entity_id            item_id  def_index  weapon  paint_index        skin_name  paint_wear  paint_seed  stattrak  quality custom_name  sticker_0_id  sticker_0_wear  ...       owner_steamid owner_name  original_owner_steamid
+    /// 0   2913        187  33721384521       7  AK-47          675  AK-47 | The Empress    0.071204         612       NaN        4        None          4769        0.000000  ...  76561198048924300    person1       76561198048924300
+    pub fn parse_weapon_skins(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match weapon_skins_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let weapon_skins = output.weapon_skins;
+
+        let rows = &weapon_skins;
+        let tick: Vec<i32> = rows.iter().map(|r| r.tick).collect();
+        let entity_id: Vec<i32> = rows.iter().map(|r| r.entity_id).collect();
+        let item_id: Vec<Option<u64>> = rows.iter().map(|r| r.item_id).collect();
+        let def_index: Vec<Option<u32>> = rows.iter().map(|r| r.def_index).collect();
+        let weapon: Vec<Option<String>> = rows.iter().map(|r| r.weapon.clone()).collect();
+        let paint_index: Vec<Option<u32>> = rows.iter().map(|r| r.paint_index).collect();
+        let skin_name: Vec<Option<String>> = rows.iter().map(|r| r.skin_name.clone()).collect();
+        let paint_wear: Vec<Option<f32>> = rows.iter().map(|r| r.paint_wear).collect();
+        let paint_seed: Vec<Option<u32>> = rows.iter().map(|r| r.paint_seed).collect();
+        let stattrak: Vec<Option<u32>> = rows.iter().map(|r| r.stattrak).collect();
+        let quality: Vec<Option<u32>> = rows.iter().map(|r| r.quality).collect();
+        let custom_name: Vec<Option<String>> = rows.iter().map(|r| r.custom_name.clone()).collect();
+        let sticker_0_id: Vec<Option<u32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 0).map(|s| s.id))
+            .collect();
+        let sticker_0_wear: Vec<Option<f32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 0).map(|s| s.wear))
+            .collect();
+        let sticker_1_id: Vec<Option<u32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 1).map(|s| s.id))
+            .collect();
+        let sticker_1_wear: Vec<Option<f32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 1).map(|s| s.wear))
+            .collect();
+        let sticker_2_id: Vec<Option<u32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 2).map(|s| s.id))
+            .collect();
+        let sticker_2_wear: Vec<Option<f32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 2).map(|s| s.wear))
+            .collect();
+        let sticker_3_id: Vec<Option<u32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 3).map(|s| s.id))
+            .collect();
+        let sticker_3_wear: Vec<Option<f32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 3).map(|s| s.wear))
+            .collect();
+        let sticker_4_id: Vec<Option<u32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 4).map(|s| s.id))
+            .collect();
+        let sticker_4_wear: Vec<Option<f32>> = rows
+            .iter()
+            .map(|r| r.stickers.iter().find(|s| s.slot == 4).map(|s| s.wear))
+            .collect();
+        let charm_id: Vec<Option<u32>> = rows.iter().map(|r| r.charm_id).collect();
+        let owner_steamid: Vec<Option<u64>> = rows.iter().map(|r| r.owner_steamid).collect();
+        let owner_name: Vec<Option<String>> = rows.iter().map(|r| r.owner_name.clone()).collect();
+        let original_owner_steamid: Vec<Option<u64>> =
+            rows.iter().map(|r| r.original_owner_steamid).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("tick", tick))?,
+            rust_series_to_py_series(&Series::new("entity_id", entity_id))?,
+            rust_series_to_py_series(&Series::new("item_id", item_id))?,
+            rust_series_to_py_series(&Series::new("def_index", def_index))?,
+            rust_series_to_py_series(&Series::new("weapon", weapon))?,
+            rust_series_to_py_series(&Series::new("paint_index", paint_index))?,
+            rust_series_to_py_series(&Series::new("skin_name", skin_name))?,
+            rust_series_to_py_series(&Series::new("paint_wear", paint_wear))?,
+            rust_series_to_py_series(&Series::new("paint_seed", paint_seed))?,
+            rust_series_to_py_series(&Series::new("stattrak", stattrak))?,
+            rust_series_to_py_series(&Series::new("quality", quality))?,
+            rust_series_to_py_series(&Series::new("custom_name", custom_name))?,
+            rust_series_to_py_series(&Series::new("sticker_0_id", sticker_0_id))?,
+            rust_series_to_py_series(&Series::new("sticker_0_wear", sticker_0_wear))?,
+            rust_series_to_py_series(&Series::new("sticker_1_id", sticker_1_id))?,
+            rust_series_to_py_series(&Series::new("sticker_1_wear", sticker_1_wear))?,
+            rust_series_to_py_series(&Series::new("sticker_2_id", sticker_2_id))?,
+            rust_series_to_py_series(&Series::new("sticker_2_wear", sticker_2_wear))?,
+            rust_series_to_py_series(&Series::new("sticker_3_id", sticker_3_id))?,
+            rust_series_to_py_series(&Series::new("sticker_3_wear", sticker_3_wear))?,
+            rust_series_to_py_series(&Series::new("sticker_4_id", sticker_4_id))?,
+            rust_series_to_py_series(&Series::new("sticker_4_wear", sticker_4_wear))?,
+            rust_series_to_py_series(&Series::new("charm_id", charm_id))?,
+            rust_series_to_py_series(&Series::new("owner_steamid", owner_steamid))?,
+            rust_series_to_py_series(&Series::new("owner_name", owner_name))?,
+            rust_series_to_py_series(&Series::new(
+                "original_owner_steamid",
+                original_owner_steamid,
+            ))?,
+        ];
+        let column_names = [
+            "tick",
+            "entity_id",
+            "item_id",
+            "def_index",
+            "weapon",
+            "paint_index",
+            "skin_name",
+            "paint_wear",
+            "paint_seed",
+            "stattrak",
+            "quality",
+            "custom_name",
+            "sticker_0_id",
+            "sticker_0_wear",
+            "sticker_1_id",
+            "sticker_1_wear",
+            "sticker_2_id",
+            "sticker_2_wear",
+            "sticker_3_id",
+            "sticker_3_wear",
+            "sticker_4_id",
+            "sticker_4_wear",
+            "charm_id",
+            "owner_steamid",
+            "owner_name",
+            "original_owner_steamid",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns the stats the server keeps for every player (CS_UM_PlayerStatsUpdate) as a timeline.
+    /// The server sends how much a stat changed ("delta"), "value" is the sum of the deltas of the
+    /// player for that stat so far. "stat" is the name of "stat_id" for the general stats (kills,
+    /// deaths, damage, shots_fired ...), None for the per weapon and per map stats. With
+    /// match_stats=True returns the raw strings of CS_UM_MatchStatsUpdate instead.
+    ///
+    /// Example output:
+    ///     tick  round            steamid     name  stat_id    stat  delta  value
+    /// 0   5312      1  76561111111111111  player1        2   kills      1      1
+    /// 1   5312      1  76561111111111111  player1        4  damage    100    100
+    #[args(match_stats = "false")]
+    pub fn parse_stats_updates(&self, py: Python<'_>, match_stats: bool) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match stats_updates_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let stats_updates = stats_updates(
+            &output.game_events,
+            &output.player_stats_updates,
+            &output.match_stats_updates,
+        );
+
+        if match_stats {
+            let rows = &stats_updates.match_stats;
+            let tick: Vec<i32> = rows.iter().map(|u| u.tick).collect();
+            let update: Vec<Option<String>> = rows.iter().map(|u| u.update.clone()).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("tick", tick))?,
+                rust_series_to_py_series(&Series::new("update", update))?,
+            ];
+            let column_names = ["tick", "update"];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows = &stats_updates.player_stats;
+        let tick: Vec<i32> = rows.iter().map(|u| u.tick).collect();
+        let round: Vec<i32> = rows.iter().map(|u| u.round).collect();
+        let steamid: Vec<Option<u64>> = rows.iter().map(|u| u.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|u| u.name.clone()).collect();
+        let stat_id: Vec<Option<i32>> = rows.iter().map(|u| u.stat_id).collect();
+        let stat: Vec<Option<String>> = rows.iter().map(|u| u.stat.clone()).collect();
+        let delta: Vec<i32> = rows.iter().map(|u| u.delta).collect();
+        let value: Vec<i32> = rows.iter().map(|u| u.value).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("tick", tick))?,
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            arr_to_py(Box::new(Int32Array::from(stat_id)))?,
+            rust_series_to_py_series(&Series::new("stat", stat))?,
+            rust_series_to_py_series(&Series::new("delta", delta))?,
+            rust_series_to_py_series(&Series::new("value", value))?,
+        ];
+        let column_names = [
+            "tick", "round", "steamid", "name", "stat_id", "stat", "delta", "value",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns every user command (DEM_UserCmd) of a POV demo: the view angles, movement, held buttons
+    /// and the attack tick of each command. Only POV demos have these, HLTV/GOTV demos return an empty
+    /// DF. "button_names" are the held buttons of BUTTONMAP separated by commas and "subtick_moves" is
+    /// how many subtick moves the command had. "attack_*" is the render/player tick and fraction where
+    /// attack was pressed. With subtick=True returns one row per subtick move with the button, if it
+    /// was pressed or released and "when" (fraction of the tick) instead.
+    ///
+    /// Example output:
+    ///    tick            steamid     name  cmd_number  tick_count      pitch         yaw  forward_move  side_move  up_move  buttons button_names  weapon_select  mouse_dx  mouse_dy  subtick_moves  attack_render_tick  attack_render_tick_fraction  attack_player_tick  attack_player_tick_fraction
+    /// 0  8711  76561111111111111  player1        8533        8710   3.212402  179.296875           1.0        0.0      0.0        9 FIRE,FORWARD              0        -3         1              2                8709                     0.631836                8710                     0.250000
+    #[args(subtick = "false")]
+    pub fn parse_user_cmds(&self, py: Python<'_>, subtick: bool) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match user_cmds_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let user_cmds = output.user_cmds;
+
+        if subtick {
+            let rows = &user_cmds
+                .iter()
+                .flat_map(|c| c.subtick_moves.iter().map(move |m| (c, m)))
+                .collect::<Vec<_>>();
+            let tick: Vec<i32> = rows.iter().map(|(c, _)| c.tick).collect();
+            let steamid: Vec<Option<u64>> = rows.iter().map(|(c, _)| c.steamid).collect();
+            let name: Vec<Option<String>> = rows.iter().map(|(c, _)| c.name.clone()).collect();
+            let cmd_number: Vec<Option<i32>> = rows.iter().map(|(c, _)| c.cmd_number).collect();
+            let button: Vec<Option<u64>> = rows.iter().map(|(_, m)| m.button).collect();
+            let button_name: Vec<Option<String>> =
+                rows.iter().map(|(_, m)| m.button_name.clone()).collect();
+            let pressed: Vec<Option<bool>> = rows.iter().map(|(_, m)| m.pressed).collect();
+            let when: Vec<Option<f32>> = rows.iter().map(|(_, m)| m.when).collect();
+            let columns = vec![
+                rust_series_to_py_series(&Series::new("tick", tick))?,
+                rust_series_to_py_series(&Series::new("steamid", steamid))?,
+                rust_series_to_py_series(&Series::new("name", name))?,
+                arr_to_py(Box::new(Int32Array::from(cmd_number)))?,
+                rust_series_to_py_series(&Series::new("button", button))?,
+                rust_series_to_py_series(&Series::new("button_name", button_name))?,
+                rust_series_to_py_series(&Series::new("pressed", pressed))?,
+                rust_series_to_py_series(&Series::new("when", when))?,
+            ];
+            let column_names = [
+                "tick",
+                "steamid",
+                "name",
+                "cmd_number",
+                "button",
+                "button_name",
+                "pressed",
+                "when",
+            ];
+            return columns_to_pandas(py, columns, &column_names);
+        }
+        let rows = &user_cmds;
+        let tick: Vec<i32> = rows.iter().map(|c| c.tick).collect();
+        let steamid: Vec<Option<u64>> = rows.iter().map(|c| c.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|c| c.name.clone()).collect();
+        let cmd_number: Vec<Option<i32>> = rows.iter().map(|c| c.cmd_number).collect();
+        let tick_count: Vec<Option<i32>> = rows.iter().map(|c| c.tick_count).collect();
+        let pitch: Vec<Option<f32>> = rows.iter().map(|c| c.pitch).collect();
+        let yaw: Vec<Option<f32>> = rows.iter().map(|c| c.yaw).collect();
+        let forward_move: Vec<Option<f32>> = rows.iter().map(|c| c.forward_move).collect();
+        let side_move: Vec<Option<f32>> = rows.iter().map(|c| c.side_move).collect();
+        let up_move: Vec<Option<f32>> = rows.iter().map(|c| c.up_move).collect();
+        let buttons: Vec<Option<u64>> = rows.iter().map(|c| c.buttons).collect();
+        let button_names: Vec<String> = rows.iter().map(|c| c.button_names.join(",")).collect();
+        let weapon_select: Vec<Option<i32>> = rows.iter().map(|c| c.weapon_select).collect();
+        let mouse_dx: Vec<Option<i32>> = rows.iter().map(|c| c.mouse_dx).collect();
+        let mouse_dy: Vec<Option<i32>> = rows.iter().map(|c| c.mouse_dy).collect();
+        let subtick_moves: Vec<i32> = rows.iter().map(|c| c.subtick_moves.len() as i32).collect();
+        let attack_render_tick: Vec<Option<i32>> =
+            rows.iter().map(|c| c.attack_render_tick).collect();
+        let attack_render_tick_fraction: Vec<Option<f32>> =
+            rows.iter().map(|c| c.attack_render_tick_fraction).collect();
+        let attack_player_tick: Vec<Option<i32>> =
+            rows.iter().map(|c| c.attack_player_tick).collect();
+        let attack_player_tick_fraction: Vec<Option<f32>> =
+            rows.iter().map(|c| c.attack_player_tick_fraction).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("tick", tick))?,
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            arr_to_py(Box::new(Int32Array::from(cmd_number)))?,
+            arr_to_py(Box::new(Int32Array::from(tick_count)))?,
+            rust_series_to_py_series(&Series::new("pitch", pitch))?,
+            rust_series_to_py_series(&Series::new("yaw", yaw))?,
+            rust_series_to_py_series(&Series::new("forward_move", forward_move))?,
+            rust_series_to_py_series(&Series::new("side_move", side_move))?,
+            rust_series_to_py_series(&Series::new("up_move", up_move))?,
+            rust_series_to_py_series(&Series::new("buttons", buttons))?,
+            rust_series_to_py_series(&Series::new("button_names", button_names))?,
+            arr_to_py(Box::new(Int32Array::from(weapon_select)))?,
+            arr_to_py(Box::new(Int32Array::from(mouse_dx)))?,
+            arr_to_py(Box::new(Int32Array::from(mouse_dy)))?,
+            rust_series_to_py_series(&Series::new("subtick_moves", subtick_moves))?,
+            arr_to_py(Box::new(Int32Array::from(attack_render_tick)))?,
+            rust_series_to_py_series(&Series::new(
+                "attack_render_tick_fraction",
+                attack_render_tick_fraction,
+            ))?,
+            arr_to_py(Box::new(Int32Array::from(attack_player_tick)))?,
+            rust_series_to_py_series(&Series::new(
+                "attack_player_tick_fraction",
+                attack_player_tick_fraction,
+            ))?,
+        ];
+        let column_names = [
+            "tick",
+            "steamid",
+            "name",
+            "cmd_number",
+            "tick_count",
+            "pitch",
+            "yaw",
+            "forward_move",
+            "side_move",
+            "up_move",
+            "buttons",
+            "button_names",
+            "weapon_select",
+            "mouse_dx",
+            "mouse_dy",
+            "subtick_moves",
+            "attack_render_tick",
+            "attack_render_tick_fraction",
+            "attack_player_tick",
+            "attack_player_tick_fraction",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+    /// Returns a dictionary of steamid -> WAV bytes with the voice of each player. The bytes include
+    /// the WAV header (16 bit mono 48 kHz) so they can be written directly to a file. The audio of each
+    /// player starts at the first tick they spoke and is padded with silence between transmissions so
+    /// it stays in sync with the demo. Players without a steamid are keyed "client_<slot>".
+    pub fn parse_voice(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+
+        let settings = ParserInputs {
+            real_name_to_og_name: AHashMap::default(),
+            wanted_players: vec![],
+            wanted_player_props: vec![],
+            wanted_other_props: vec![],
+            wanted_events: vec![],
+            parse_ents: false,
+            wanted_ticks: vec![],
+            parse_projectiles: false,
+            collect_bomb: false,
+            collect_infernos: false,
+            collect_weapon_skins: false,
+            collect_economy: false,
+            collect_aim: false,
+            collect_movement: false,
+            collect_spotted: false,
+            track_player_history: false,
+            parse_user_cmds: false,
+            only_header: true,
+            count_props: false,
+            only_convars: false,
+            huffman_lookup_table: &arc_huf,
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let wavs = match voice_wavs(&output.voice_data) {
+            Ok(wavs) => wavs,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let dict = PyDict::new(py);
+        for w in wavs {
+            dict.set_item(w.player_id(), PyBytes::new(py, &w.wav))?;
+        }
+        Ok(dict.to_object(py))
+    }
+    /// Returns who spoke when: one row per segment of continuous voice of a player, without decoding
+    /// any audio. A pause longer than gap_seconds or a new voice transmission starts a new segment and
+    /// segments are split at round ends, so "round" and the ticks can be joined with the events.
+    /// "packets" is how many voice messages the segment had and "bytes" the compressed voice, roughly
+    /// how much was said. "client" is the player slot when the demo has no steamid for the voice.
+    ///
+    /// Example output:
+    ///    round            steamid     name client  start_tick  end_tick  duration  packets  bytes
+    /// 0      1  76561111111111111  player1    NaN        5402      5571  2.671875       55  10230
+    /// 1      1  76561111111111112  player2    NaN        5630      5688  0.921875       19   3410
+    #[args(gap_seconds = "0.5")]
+    pub fn parse_voice_activity(&self, py: Python<'_>, gap_seconds: f32) -> PyResult<Py<PyAny>> {
+        let mmap = match create_mmap(self.path.clone()) {
+            Ok(mmap) => mmap,
+            Err(e) => {
+                return Err(Exception::new_err(format!(
+                    "{}. File name: {}",
+                    e,
+                    self.path.clone()
+                )))
+            }
+        };
+        let arc_huf = create_huffman_lookup_table();
+        let settings = match voice_activity_inputs(&arc_huf) {
+            Ok(settings) => settings,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let mut parser = Parser::new(settings, false);
+        let output = match parser.parse_demo(&mmap) {
+            Ok(output) => output,
+            Err(e) => return Err(Exception::new_err(format!("{}", e))),
+        };
+        let voice_activity = voice_activity(
+            &output.game_events,
+            &output.voice_data,
+            &output.player_md,
+            gap_seconds,
+        );
+
+        let rows = &voice_activity;
+        let round: Vec<i32> = rows.iter().map(|s| s.round).collect();
+        let steamid: Vec<Option<u64>> = rows.iter().map(|s| s.steamid).collect();
+        let name: Vec<Option<String>> = rows.iter().map(|s| s.name.clone()).collect();
+        let client: Vec<Option<i32>> = rows.iter().map(|s| s.client).collect();
+        let start_tick: Vec<i32> = rows.iter().map(|s| s.start_tick).collect();
+        let end_tick: Vec<i32> = rows.iter().map(|s| s.end_tick).collect();
+        let duration: Vec<f32> = rows.iter().map(|s| s.duration).collect();
+        let packets: Vec<i32> = rows.iter().map(|s| s.packets).collect();
+        let bytes: Vec<u64> = rows.iter().map(|s| s.bytes as u64).collect();
+        let columns = vec![
+            rust_series_to_py_series(&Series::new("round", round))?,
+            rust_series_to_py_series(&Series::new("steamid", steamid))?,
+            rust_series_to_py_series(&Series::new("name", name))?,
+            arr_to_py(Box::new(Int32Array::from(client)))?,
+            rust_series_to_py_series(&Series::new("start_tick", start_tick))?,
+            rust_series_to_py_series(&Series::new("end_tick", end_tick))?,
+            rust_series_to_py_series(&Series::new("duration", duration))?,
+            rust_series_to_py_series(&Series::new("packets", packets))?,
+            rust_series_to_py_series(&Series::new("bytes", bytes))?,
+        ];
+        let column_names = [
+            "round",
+            "steamid",
+            "name",
+            "client",
+            "start_tick",
+            "end_tick",
+            "duration",
+            "packets",
+            "bytes",
+        ];
+        columns_to_pandas(py, columns, &column_names)
+    }
+}
+
+/// https://github.com/pola-rs/polars/blob/master/examples/python_rust_compiled_function/src/ffi.rs
+pub(crate) fn to_py_array(py: Python, pyarrow: &PyModule, array: ArrayRef) -> PyResult<PyObject> {
+    let schema = Box::new(ffi::export_field_to_c(&ArrowField::new(
+        "",
+        array.data_type().clone(),
+        true,
+    )));
+    let array = Box::new(ffi::export_array_to_c(array));
+    let schema_ptr: *const ffi::ArrowSchema = &*schema;
+    let array_ptr: *const ffi::ArrowArray = &*array;
+    let array = pyarrow.getattr("Array")?.call_method1(
+        "_import_from_c",
+        (array_ptr as Py_uintptr_t, schema_ptr as Py_uintptr_t),
+    )?;
+    Ok(array.to_object(py))
+}
+/// https://github.com/pola-rs/polars/blob/master/examples/python_rust_compiled_function/src/ffi.rs
+pub fn rust_series_to_py_series(series: &Series) -> PyResult<PyObject> {
+    let series = series.rechunk();
+    let array = series.to_arrow(0);
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let pyarrow = py.import("pyarrow")?;
+    let pyarrow_array = to_py_array(py, pyarrow, array)?;
+    let polars = py.import("polars")?;
+    let out = polars.call_method1("from_arrow", (pyarrow_array,))?;
+    Ok(out.to_object(py))
+}
+/// https://github.com/pola-rs/polars/blob/master/examples/python_rust_compiled_function/src/ffi.rs
+pub fn arr_to_py(array: Box<dyn Array>) -> PyResult<PyObject> {
+    //let series = series.rechunk();
+    //let array = series.to_arrow(0);
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let pyarrow = py.import("pyarrow")?;
+    let pyarrow_array = to_py_array(py, pyarrow, array)?;
+    let polars = py.import("polars")?;
+    let out = polars.call_method1("from_arrow", (pyarrow_array,))?;
+    Ok(out.to_object(py))
+}
+/// Builds a pandas DF out of already converted columns
+pub fn columns_to_pandas(
+    py: Python,
+    columns: Vec<PyObject>,
+    column_names: &[&str],
+) -> PyResult<Py<PyAny>> {
+    let polars = py.import("polars")?;
+    let all_series_py = columns.to_object(py);
+    let df = polars.call_method1("DataFrame", (all_series_py,))?;
+    df.setattr("columns", column_names.to_object(py))?;
+    // Call to_pandas with use_pyarrow_extension_array = true
+    let kwargs = vec![("use_pyarrow_extension_array", true)].into_py_dict(py);
+    let pandas_df = df.call_method("to_pandas", (), Some(kwargs))?;
+    Ok(pandas_df.to_object(py))
+}
+#[pyclass]
+struct DemoParser {
+    path: String,
+    overview: Option<MapOverview>,
+    zones: Option<Zones>,
+}
+
+#[cfg(feature = "voice")]
+struct LibOpus(opus::Decoder);
+
+#[cfg(feature = "voice")]
+impl OpusDecoder for LibOpus {
+    fn decode(&mut self, frame: &[u8], pcm: &mut [i16]) -> Result<usize, DemoParserError> {
+        match self.0.decode(frame, pcm, false) {
+            Ok(n_samples) => Ok(n_samples),
+            Err(e) => Err(DemoParserError::VoiceDataWriteError(format!("{}", e))),
+        }
+    }
+}
+
+#[cfg(feature = "voice")]
+fn new_opus_decoder() -> Result<LibOpus, DemoParserError> {
+    match opus::Decoder::new(VOICE_SAMPLE_RATE, opus::Channels::Mono) {
+        Ok(decoder) => Ok(LibOpus(decoder)),
+        Err(e) => Err(DemoParserError::VoiceDataWriteError(format!("{}", e))),
+    }
+}
+
+#[cfg(feature = "voice")]
+fn voice_wavs(voice_data: &[VoiceRecord]) -> Result<Vec<PlayerVoiceWav>, DemoParserError> {
+    voice_to_wav(voice_data, new_opus_decoder)
+}
+
+// Decoding needs libopus, which only gets linked with the "voice" feature
+#[cfg(not(feature = "voice"))]
+fn voice_wavs(_voice_data: &[VoiceRecord]) -> Result<Vec<PlayerVoiceWav>, DemoParserError> {
+    Err(DemoParserError::VoiceDataWriteError(
+        "built without the \"voice\" feature".to_string(),
+    ))
 }
 
 pub fn parse_kwargs_ticks(kwargs: Option<&PyDict>) -> (Vec<u64>, Vec<i32>) {
@@ -1,9 +1,57 @@
+use parser::analysis::aim::aim;
+use parser::analysis::aim::aim_inputs;
+use parser::analysis::bomb::bomb_inputs;
+use parser::analysis::bomb::bomb_timeline;
+use parser::analysis::duels::duels;
+use parser::analysis::duels::duels_inputs;
+use parser::analysis::duels::DEFAULT_TRADE_WINDOW_SECONDS;
+use parser::analysis::economy::economy;
+use parser::analysis::economy::economy_inputs;
+use parser::analysis::economy::BuyThresholds;
+use parser::analysis::fires_and_smokes::fires_and_smokes;
+use parser::analysis::fires_and_smokes::fires_and_smokes_inputs;
+use parser::analysis::flashes::flashes;
+use parser::analysis::flashes::flashes_inputs;
+use parser::analysis::flashes::DEFAULT_FLASH_ASSIST_WINDOW_SECONDS;
+use parser::analysis::grenades::grenade_throws;
+use parser::analysis::grenades::grenade_throws_inputs;
+use parser::analysis::highlights::highlights;
+use parser::analysis::highlights::highlights_inputs;
+use parser::analysis::highlights::DEFAULT_HIGHLIGHT_PADDING_SECONDS;
+use parser::analysis::kills::kills;
+use parser::analysis::kills::kills_inputs;
+use parser::analysis::movement::movement;
+use parser::analysis::movement::movement_inputs;
+use parser::analysis::player_stats::player_stats;
+use parser::analysis::player_stats::player_stats_inputs;
+use parser::analysis::recoil::recoil;
+use parser::analysis::recoil::recoil_inputs;
+use parser::analysis::shots::shots;
+use parser::analysis::shots::shots_inputs;
+use parser::analysis::stats_updates::stats_updates;
+use parser::analysis::stats_updates::stats_updates_inputs;
+use parser::analysis::user_cmds::user_cmds_inputs;
+use parser::analysis::visibility::visibility;
+use parser::analysis::visibility::visibility_inputs;
+use parser::analysis::voice_activity::voice_activity;
+use parser::analysis::voice_activity::voice_activity_inputs;
+use parser::analysis::voice_activity::DEFAULT_VOICE_GAP_SECONDS;
+use parser::analysis::weapon_skins::weapon_skins_inputs;
+use parser::analysis::weapons::weapons;
+use parser::analysis::weapons::weapons_inputs;
+use parser::first_pass::parser::parse_file_info;
 use parser::first_pass::parser_settings::rm_user_friendly_names;
 use parser::first_pass::parser_settings::ParserInputs;
+use parser::first_pass::read_bits::DemoParserError;
 use parser::parse_demo::Parser;
+use parser::radar::MapOverview;
 use parser::second_pass::parser_settings::create_huffman_lookup_table;
+use parser::second_pass::parser_settings::PlayerAccolade;
 use parser::second_pass::variants::soa_to_aos;
 use parser::second_pass::variants::OutputSerdeHelperStruct;
+use parser::voice::voice_to_wav;
+use parser::voice::OpusDecoder;
+use parser::zones::Zones;
 use std::collections::HashMap;
 use std::iter::FromIterator;
 use std::result::Result;
@@ -16,6 +64,8 @@ pub fn parseEvent(
     event_name: Option<String>,
     wanted_player_props: Option<Vec<JsValue>>,
     wanted_other_props: Option<Vec<JsValue>>,
+    overview: Option<String>,
+    zones: Option<String>,
 ) -> Result<JsValue, JsError> {
     let player_props = match wanted_player_props {
         Some(p) => p.iter().map(|s| s.as_string().unwrap()).collect::<Vec<_>>(),
@@ -51,12 +101,23 @@ pub fn parseEvent(
         parse_ents: true,
         wanted_ticks: vec![],
         parse_projectiles: false,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: false,
         count_props: false,
         only_convars: false,
         huffman_lookup_table: &arc_huf,
     };
     let mut parser = Parser::new(settings, false);
+    parser.overview = resolve_overview(overview)?;
+    parser.zones = resolve_zones(zones)?;
 
     let output = match parser.parse_demo(&file) {
         Ok(output) => output,
@@ -73,6 +134,8 @@ pub fn parseEvents(
     event_names: Option<Vec<JsValue>>,
     wanted_player_props: Option<Vec<JsValue>>,
     wanted_other_props: Option<Vec<JsValue>>,
+    overview: Option<String>,
+    zones: Option<String>,
 ) -> Result<JsValue, JsError> {
     let event_names = match event_names {
         Some(p) => p.iter().map(|s| s.as_string().unwrap()).collect::<Vec<_>>(),
@@ -112,12 +175,23 @@ pub fn parseEvents(
         parse_ents: true,
         wanted_ticks: vec![],
         parse_projectiles: false,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: false,
         count_props: false,
         only_convars: false,
         huffman_lookup_table: &arc_huf,
     };
     let mut parser = Parser::new(settings, false);
+    parser.overview = resolve_overview(overview)?;
+    parser.zones = resolve_zones(zones)?;
 
     let output = match parser.parse_demo(&file) {
         Ok(output) => output,
@@ -141,6 +215,15 @@ pub fn listGameEvents(fileBytes: Vec<u8>) -> Result<JsValue, JsError> {
         parse_ents: false,
         wanted_ticks: vec![],
         parse_projectiles: false,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: false,
         count_props: false,
         only_convars: false,
@@ -166,6 +249,8 @@ pub fn parseTicks(
     wanted_ticks: Option<Vec<i32>>,
     wanted_players: Option<Vec<JsValue>>,
     struct_of_arrays: Option<bool>,
+    overview: Option<String>,
+    zones: Option<String>,
 ) -> Result<JsValue, JsError> {
     let wanted_props = match wanted_props {
         Some(p) => p.iter().map(|s| s.as_string().unwrap()).collect::<Vec<_>>(),
@@ -200,12 +285,23 @@ pub fn parseTicks(
         parse_ents: true,
         wanted_ticks: wanted_ticks,
         parse_projectiles: false,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: false,
         count_props: false,
         only_convars: false,
         huffman_lookup_table: &arc_huf.clone(),
     };
     let mut parser = Parser::new(settings, false);
+    parser.overview = resolve_overview(overview)?;
+    parser.zones = resolve_zones(zones)?;
 
     let output = match parser.parse_demo(&file) {
         Ok(output) => output,
@@ -246,7 +342,7 @@ pub fn parseTicks(
 }
 
 #[wasm_bindgen]
-pub fn parseGrenades(file: Vec<u8>) -> Result<JsValue, JsError> {
+pub fn parseGrenades(file: Vec<u8>, overview: Option<String>, zones: Option<String>) -> Result<JsValue, JsError> {
     let arc_huf = Arc::new(create_huffman_lookup_table());
 
     let settings = ParserInputs {
@@ -258,12 +354,23 @@ pub fn parseGrenades(file: Vec<u8>) -> Result<JsValue, JsError> {
         parse_ents: true,
         wanted_ticks: vec![],
         parse_projectiles: true,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: true,
         count_props: false,
         only_convars: false,
         huffman_lookup_table: &arc_huf.clone(),
     };
     let mut parser = Parser::new(settings, false);
+    parser.overview = resolve_overview(overview)?;
+    parser.zones = resolve_zones(zones)?;
 
     let output = match parser.parse_demo(&file) {
         Ok(output) => output,
@@ -289,6 +396,15 @@ pub fn parseHeader(file: Vec<u8>) -> Result<JsValue, JsError> {
         parse_ents: false,
         wanted_ticks: vec![],
         parse_projectiles: true,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
         only_header: true,
         count_props: false,
         only_convars: false,
@@ -308,3 +424,567 @@ pub fn parseHeader(file: Vec<u8>) -> Result<JsValue, JsError> {
         Err(e) => return Err(JsError::new(&format!("{}", e))),
     }
 }
+
+#[wasm_bindgen]
+pub fn parseFileInfo(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let file_info = match parse_file_info(&file) {
+        Ok(file_info) => file_info,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    match serde_wasm_bindgen::to_value(&file_info) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseAccolades(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+
+    let settings = ParserInputs {
+        wanted_players: vec![],
+        real_name_to_og_name: HashMap::default().into(),
+        wanted_player_props: vec![],
+        wanted_other_props: vec![],
+        wanted_events: vec![],
+        parse_ents: true,
+        wanted_ticks: vec![],
+        parse_projectiles: false,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
+        only_header: true,
+        count_props: false,
+        only_convars: false,
+        huffman_lookup_table: &arc_huf.clone(),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let accolades: Vec<PlayerAccolade> = output
+        .player_md
+        .iter()
+        .filter_map(|p| p.accolade.clone())
+        .collect();
+    match serde_wasm_bindgen::to_value(&accolades) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parsePlayerStats(file: Vec<u8>, per_round: Option<bool>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match player_stats_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let stats = player_stats(&output.game_events);
+    let s = match per_round {
+        Some(true) => serde_wasm_bindgen::to_value(&stats.per_round),
+        _ => serde_wasm_bindgen::to_value(&stats.per_match),
+    };
+    match s {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseDuels(file: Vec<u8>, trade_window: Option<f32>, per_player: Option<bool>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match duels_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let duels = duels(&output.game_events, trade_window.unwrap_or(DEFAULT_TRADE_WINDOW_SECONDS));
+    let s = match per_player {
+        Some(true) => serde_wasm_bindgen::to_value(&duels.players),
+        _ => serde_wasm_bindgen::to_value(&duels.kills),
+    };
+    match s {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseHighlights(file: Vec<u8>, padding: Option<f32>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match highlights_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let highlights = highlights(&output.game_events, padding.unwrap_or(DEFAULT_HIGHLIGHT_PADDING_SECONDS));
+    match serde_wasm_bindgen::to_value(&highlights) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseFlashes(file: Vec<u8>, flash_assist_window: Option<f32>, per_player: Option<bool>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match flashes_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let flashes = flashes(
+        &output.game_events,
+        &output.projectiles,
+        flash_assist_window.unwrap_or(DEFAULT_FLASH_ASSIST_WINDOW_SECONDS),
+    );
+    let s = match per_player {
+        Some(true) => serde_wasm_bindgen::to_value(&flashes.players),
+        _ => serde_wasm_bindgen::to_value(&flashes.flashes),
+    };
+    match s {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseGrenadeThrows(file: Vec<u8>, zones: Option<String>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match grenade_throws_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    parser.zones = resolve_zones(zones)?;
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let throws = grenade_throws(&output.game_events, &output.projectiles);
+    match serde_wasm_bindgen::to_value(&throws) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseFires(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match fires_and_smokes_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let fires = fires_and_smokes(&output.game_events, &output.infernos).fires;
+    match serde_wasm_bindgen::to_value(&fires) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseSmokes(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match fires_and_smokes_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let smokes = fires_and_smokes(&output.game_events, &output.infernos).smokes;
+    match serde_wasm_bindgen::to_value(&smokes) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseBomb(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match bomb_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let timeline = bomb_timeline(&output.game_events, &output.bombs);
+    match serde_wasm_bindgen::to_value(&timeline.rounds) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseKills(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match kills_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let kills = kills(&output.game_events);
+    match serde_wasm_bindgen::to_value(&kills) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseVisibility(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match visibility_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let visibility = visibility(&output.game_events, &output.spotted);
+    match serde_wasm_bindgen::to_value(&visibility) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseMovement(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match movement_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let movement = movement(&output.game_events, &output.movement);
+    match serde_wasm_bindgen::to_value(&movement) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseShots(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match shots_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let shots = shots(&output.game_events);
+    match serde_wasm_bindgen::to_value(&shots) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseRecoil(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match recoil_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let recoil = recoil(&output.game_events);
+    match serde_wasm_bindgen::to_value(&recoil) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseAim(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match aim_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let aim = aim(&output.game_events, &output.aim);
+    match serde_wasm_bindgen::to_value(&aim) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseEconomy(
+    file: Vec<u8>,
+    eco: Option<i32>,
+    semi_eco: Option<i32>,
+    force: Option<i32>,
+) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match economy_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let defaults = BuyThresholds::default();
+    let thresholds = BuyThresholds {
+        eco: eco.unwrap_or(defaults.eco),
+        semi_eco: semi_eco.unwrap_or(defaults.semi_eco),
+        force: force.unwrap_or(defaults.force),
+    };
+    let economy = economy(&output.game_events, &output.economy, &thresholds);
+    match serde_wasm_bindgen::to_value(&economy) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseWeapons(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match weapons_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let weapons = weapons(&output.game_events);
+    match serde_wasm_bindgen::to_value(&weapons) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseWeaponSkins(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match weapon_skins_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let weapon_skins = output.weapon_skins;
+    match serde_wasm_bindgen::to_value(&weapon_skins) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseStatsUpdates(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match stats_updates_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let stats_updates = stats_updates(
+        &output.game_events,
+        &output.player_stats_updates,
+        &output.match_stats_updates,
+    );
+    match serde_wasm_bindgen::to_value(&stats_updates) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn parseUserCmds(file: Vec<u8>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match user_cmds_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let user_cmds = output.user_cmds;
+    match serde_wasm_bindgen::to_value(&user_cmds) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+// Opus decoder from JS (for example the "opus-decoder" package), there is no libopus in wasm
+struct JsOpus(js_sys::Function);
+
+impl OpusDecoder for JsOpus {
+    fn decode(&mut self, frame: &[u8], pcm: &mut [i16]) -> Result<usize, DemoParserError> {
+        let frame = js_sys::Uint8Array::from(frame);
+        let samples = match self.0.call1(&JsValue::NULL, &frame) {
+            Ok(samples) => js_sys::Int16Array::new(&samples),
+            Err(e) => return Err(DemoParserError::VoiceDataWriteError(format!("{:?}", e))),
+        };
+        let n_samples = (samples.length() as usize).min(pcm.len());
+        samples
+            .subarray(0, n_samples as u32)
+            .copy_to(&mut pcm[..n_samples]);
+        Ok(n_samples)
+    }
+}
+
+// newDecoder is called once per player and returns a function that takes one Opus frame
+// (Uint8Array) and returns the decoded 48 kHz mono samples (Int16Array).
+#[wasm_bindgen]
+pub fn parseVoice(file: Vec<u8>, newDecoder: js_sys::Function) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+
+    let settings = ParserInputs {
+        wanted_players: vec![],
+        real_name_to_og_name: HashMap::default().into(),
+        wanted_player_props: vec![],
+        wanted_other_props: vec![],
+        wanted_events: vec![],
+        parse_ents: false,
+        wanted_ticks: vec![],
+        parse_projectiles: false,
+        collect_bomb: false,
+        collect_infernos: false,
+        collect_weapon_skins: false,
+        collect_economy: false,
+        collect_aim: false,
+        collect_movement: false,
+        collect_spotted: false,
+        track_player_history: false,
+        parse_user_cmds: false,
+        only_header: true,
+        count_props: false,
+        only_convars: false,
+        huffman_lookup_table: &arc_huf.clone(),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let new_decoder = || match newDecoder.call0(&JsValue::NULL) {
+        Ok(decode) => Ok(JsOpus(decode.into())),
+        Err(e) => Err(DemoParserError::VoiceDataWriteError(format!("{:?}", e))),
+    };
+    let wavs = match voice_to_wav(&output.voice_data, new_decoder) {
+        Ok(wavs) => wavs,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let per_player = js_sys::Object::new();
+    for w in wavs {
+        let wav = js_sys::Uint8Array::from(&w.wav[..]);
+        if let Err(e) = js_sys::Reflect::set(&per_player, &w.player_id().into(), &wav) {
+            return Err(JsError::new(&format!("{:?}", e)));
+        }
+    }
+    Ok(per_player.into())
+}
+
+#[wasm_bindgen]
+pub fn parseVoiceActivity(file: Vec<u8>, gap_seconds: Option<f32>) -> Result<JsValue, JsError> {
+    let arc_huf = Arc::new(create_huffman_lookup_table());
+    let settings = match voice_activity_inputs(&arc_huf) {
+        Ok(settings) => settings,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let mut parser = Parser::new(settings, false);
+    let output = match parser.parse_demo(&file) {
+        Ok(output) => output,
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    };
+    let segments = voice_activity(
+        &output.game_events,
+        &output.voice_data,
+        &output.player_md,
+        gap_seconds.unwrap_or(DEFAULT_VOICE_GAP_SECONDS),
+    );
+    match serde_wasm_bindgen::to_value(&segments) {
+        Ok(s) => Ok(s),
+        Err(e) => return Err(JsError::new(&format!("{}", e))),
+    }
+}
+
+// Takes the contents of the overview file, there is no file system to read it from
+fn resolve_overview(overview: Option<String>) -> Result<Option<MapOverview>, JsError> {
+    match overview {
+        Some(text) => match MapOverview::from_overview_txt(&text) {
+            Ok(overview) => Ok(Some(overview)),
+            Err(e) => Err(JsError::new(&format!("{}", e))),
+        },
+        None => Ok(None),
+    }
+}
+
+// Takes the contents of the zones JSON/GeoJSON file
+fn resolve_zones(zones: Option<String>) -> Result<Option<Zones>, JsError> {
+    match zones {
+        Some(text) => match Zones::from_json(&text) {
+            Ok(zones) => Ok(Some(zones)),
+            Err(e) => Err(JsError::new(&format!("{}", e))),
+        },
+        None => Ok(None),
+    }
+}